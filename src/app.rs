@@ -1,10 +1,29 @@
-use crate::jvm::types::JvmInfo;
+use crate::config::{ClassColumn, ExportFormat, ThreadColumn};
+use crate::export::HeapDumpPlan;
+use crate::jvm::types::{ClassInfo, JvmInfo};
+use crate::metrics::alerts::FiredAlert;
+use crate::metrics::collector::{MetricSet, PollStatus};
+use crate::metrics::log::{LogEntry, LogLevel};
+use crate::metrics::ring_buffer::RingBuffer;
 use crate::metrics::store::MetricsStore;
 use crate::theme::Theme;
+use crate::tui::keybindings::KeyMap;
+use chrono::{DateTime, Local};
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How long a toast stays on screen.
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+/// Toasts shown at once; a new one pushes the oldest out.
+const MAX_TOASTS: usize = 4;
+
+/// Entries kept in the 'L' event log.
+const EVENT_LOG_CAPACITY: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Tab {
     Overview,
     Memory,
@@ -45,6 +64,16 @@ impl Tab {
         }
     }
 
+    pub fn index(self) -> usize {
+        match self {
+            Tab::Overview => 0,
+            Tab::Memory => 1,
+            Tab::Threads => 2,
+            Tab::GC => 3,
+            Tab::Classes => 4,
+        }
+    }
+
     pub fn title(&self) -> &str {
         match self {
             Tab::Overview => "Overview",
@@ -55,6 +84,18 @@ impl Tab {
         }
     }
 
+    /// What the collector fetches, besides heap and GC stats, when only the
+    /// visible tab is polled.
+    pub fn metrics(self) -> MetricSet {
+        match self {
+            Tab::Overview => MetricSet::RUNTIME,
+            Tab::Memory => MetricSet::NATIVE_MEMORY,
+            Tab::Threads => MetricSet::THREADS,
+            Tab::GC => MetricSet::GC_DETAILS,
+            Tab::Classes => MetricSet::CLASSES,
+        }
+    }
+
     pub fn all() -> [Tab; 5] {
         [
             Tab::Overview,
@@ -67,56 +108,173 @@ impl Tab {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ExportFormat {
-    Json,
-    Prometheus,
-    Csv,
+pub enum ClassSort {
+    Bytes,
+    Instances,
+    Name,
 }
 
-impl ExportFormat {
+impl ClassSort {
     pub fn next(self) -> Self {
         match self {
-            ExportFormat::Json => ExportFormat::Prometheus,
-            ExportFormat::Prometheus => ExportFormat::Csv,
-            ExportFormat::Csv => ExportFormat::Json,
+            ClassSort::Bytes => ClassSort::Instances,
+            ClassSort::Instances => ClassSort::Name,
+            ClassSort::Name => ClassSort::Bytes,
         }
     }
 
-    pub fn previous(self) -> Self {
+    pub fn display_name(&self) -> &str {
         match self {
-            ExportFormat::Json => ExportFormat::Csv,
-            ExportFormat::Prometheus => ExportFormat::Json,
-            ExportFormat::Csv => ExportFormat::Prometheus,
+            ClassSort::Bytes => "Memory Usage",
+            ClassSort::Instances => "Instance Count",
+            ClassSort::Name => "Class Name",
         }
     }
+}
 
-    pub fn extension(&self) -> &str {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadSort {
+    Dump,
+    Cpu,
+}
+
+impl ThreadSort {
+    pub fn next(self) -> Self {
         match self {
-            ExportFormat::Json => "json",
-            ExportFormat::Prometheus => "prom",
-            ExportFormat::Csv => "csv",
+            ThreadSort::Dump => ThreadSort::Cpu,
+            ThreadSort::Cpu => ThreadSort::Dump,
         }
     }
 
     pub fn display_name(&self) -> &str {
         match self {
-            ExportFormat::Json => "JSON",
-            ExportFormat::Prometheus => "Prometheus",
-            ExportFormat::Csv => "CSV",
+            ThreadSort::Dump => "Dump Order",
+            ThreadSort::Cpu => "CPU Usage",
+        }
+    }
+}
+
+/// Narrowest GC timeline window zooming in will go to, in samples.
+const MIN_TIMELINE_WIDTH: usize = 10;
+
+/// The slice of the GC history the timeline chart shows. While `follow` is
+/// set the window sticks to the newest samples; `width: None` shows all of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineWindow {
+    pub start: usize,
+    pub width: Option<usize>,
+    pub follow: bool,
+}
+
+impl Default for TimelineWindow {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            width: None,
+            follow: true,
+        }
+    }
+}
+
+impl TimelineWindow {
+    /// Visible `start..end` sample range for a history of `len` samples.
+    pub fn range(&self, len: usize) -> std::ops::Range<usize> {
+        let width = self.width.unwrap_or(len).min(len);
+        let start = if self.follow {
+            len - width
+        } else {
+            self.start.min(len - width)
+        };
+        start..start + width
+    }
+
+    pub fn is_whole(&self, len: usize) -> bool {
+        self.range(len).len() == len
+    }
+
+    fn step(&self, len: usize) -> usize {
+        (self.range(len).len() / 4).max(1)
+    }
+
+    pub fn pan_left(&mut self, len: usize) {
+        let range = self.range(len);
+        self.start = range.start.saturating_sub(self.step(len));
+        self.follow = false;
+    }
+
+    /// Reaching the right edge re-engages following.
+    pub fn pan_right(&mut self, len: usize) {
+        let range = self.range(len);
+        self.start = range.start + self.step(len);
+        if self.start + range.len() >= len {
+            self.follow = true;
         }
     }
+
+    /// Halves the window around its centre (or its right edge while following).
+    pub fn zoom_in(&mut self, len: usize) {
+        let range = self.range(len);
+        let width = (range.len() / 2).max(MIN_TIMELINE_WIDTH).min(range.len());
+        self.start = range.start + (range.len() - width) / 2;
+        self.width = Some(width);
+    }
+
+    /// Doubles the window; once it covers everything it goes back to
+    /// following the full history.
+    pub fn zoom_out(&mut self, len: usize) {
+        let range = self.range(len);
+        let width = range.len() * 2;
+        if width >= len {
+            *self = Self::default();
+            return;
+        }
+        self.start = range.start.saturating_sub((width - range.len()) / 2);
+        self.width = Some(width);
+    }
+}
+
+/// An alert that fired during this session.
+#[derive(Debug, Clone)]
+pub struct AlertEvent {
+    pub timestamp: DateTime<Local>,
+    pub rule: String,
+    pub value: f64,
+}
+
+/// A transient message stacked in a corner of the monitoring screen, so
+/// things like "GC triggered" don't take over the whole screen.
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub text: String,
+    pub level: LogLevel,
+    pub expires_at: Instant,
 }
 
 pub enum AppMode {
     Normal,
     Help,
     ConfirmGc,
+    ConfirmHeapDump,
     ConfirmExport,
     SelectExportFormat,
     Error(String),
     Loading(String),
-    ExportSuccess(String),
     Search,
+    ColumnMenu,
+    ShellPrompt,
+    AlertHistory,
+    Command,
+    CommandOutput,
+    RawOutput,
+    SystemProperties,
+    ProcessInfo,
+}
+
+/// How the attached JVM was started, shown in the 'i' popup.
+pub struct ProcessInfo {
+    pub command_line: String,
+    /// `user.dir`, when the connection can read system properties.
+    pub working_dir: Option<String>,
 }
 
 pub struct App {
@@ -126,11 +284,58 @@ pub struct App {
     pub metrics_store: Arc<RwLock<MetricsStore>>,
     pub mode: AppMode,
     pub scroll_offset: usize,
+    /// Scroll positions of the tabs not on screen, restored on return.
+    tab_scroll: HashMap<Tab, usize>,
     pub search_query: String,
     pub search_results: Vec<usize>,
     pub search_index: usize,
+    pub class_filter: String,
+    /// Histogram captured with 'b'; while set the Classes tab shows growth since.
+    pub class_baseline: Option<Vec<ClassInfo>>,
+    pub class_sort: ClassSort,
+    pub thread_sort: ThreadSort,
+    pub gc_timeline: TimelineWindow,
     pub theme: Theme,
     pub selected_export_format: ExportFormat,
+    pub warning: Option<String>,
+    pub visible_thread_columns: Vec<ThreadColumn>,
+    pub visible_class_columns: Vec<ClassColumn>,
+    /// `max_threads_displayed`; 'A' lifts it until pressed again.
+    pub max_threads_displayed: Option<usize>,
+    pub show_all_threads: bool,
+    pub column_menu_index: usize,
+    pub toasts: Vec<Toast>,
+    pub shell_command: String,
+    pub last_export_path: Option<String>,
+    pub heap_dump_plan: Option<HeapDumpPlan>,
+    pub active_alerts: Vec<String>,
+    pub poll_interval: Duration,
+    pub alert_history: Vec<AlertEvent>,
+    pub alert_history_scroll: usize,
+    /// jcmd command typed into the ':' palette, and what it last printed.
+    pub diagnostic_command: String,
+    pub diagnostic_output: String,
+    pub diagnostic_scroll: usize,
+    pub system_properties: Vec<(String, String)>,
+    /// Typed into the properties panel; matches keys and values.
+    pub system_properties_filter: String,
+    pub system_properties_scroll: usize,
+    pub process_info: Option<ProcessInfo>,
+    /// Borderless single-line chrome for small terminals and split panes.
+    pub compact: bool,
+    /// Kind of connection shown next to the poll latency, e.g. "Jolokia".
+    pub connection_label: Option<String>,
+    /// Monitoring-screen keys, with the config's `[keybindings]` applied.
+    pub keymap: KeyMap,
+    pub poll_status: Option<PollStatus>,
+    /// `advanced.allow_trigger_gc`; when off 'g' only explains why.
+    pub allow_trigger_gc: bool,
+    /// `advanced.confirm_destructive`: GC needs "gc" typed, not 'y'.
+    pub confirm_destructive: bool,
+    pub gc_confirmation_input: String,
+    pub event_log: RingBuffer<LogEntry>,
+    /// Bottom pane with the event log, toggled with 'L'.
+    pub show_event_log: bool,
 }
 
 impl App {
@@ -142,11 +347,47 @@ impl App {
             metrics_store,
             mode: AppMode::Normal,
             scroll_offset: 0,
+            tab_scroll: HashMap::new(),
             search_query: String::new(),
             search_results: Vec::new(),
             search_index: 0,
+            class_filter: String::new(),
+            class_baseline: None,
+            class_sort: ClassSort::Bytes,
+            thread_sort: ThreadSort::Dump,
+            gc_timeline: TimelineWindow::default(),
             theme: Theme,
             selected_export_format: ExportFormat::Json,
+            warning: None,
+            visible_thread_columns: ThreadColumn::all().to_vec(),
+            visible_class_columns: ClassColumn::all().to_vec(),
+            max_threads_displayed: None,
+            show_all_threads: false,
+            column_menu_index: 0,
+            toasts: Vec::new(),
+            shell_command: String::new(),
+            last_export_path: None,
+            heap_dump_plan: None,
+            active_alerts: Vec::new(),
+            poll_interval: Duration::from_secs(1),
+            alert_history: Vec::new(),
+            alert_history_scroll: 0,
+            diagnostic_command: String::new(),
+            diagnostic_output: String::new(),
+            diagnostic_scroll: 0,
+            system_properties: Vec::new(),
+            system_properties_filter: String::new(),
+            system_properties_scroll: 0,
+            process_info: None,
+            compact: false,
+            connection_label: None,
+            keymap: KeyMap::default(),
+            poll_status: None,
+            allow_trigger_gc: true,
+            confirm_destructive: false,
+            gc_confirmation_input: String::new(),
+            event_log: RingBuffer::new(EVENT_LOG_CAPACITY),
+            show_event_log: false,
         }
     }
 
@@ -155,26 +396,70 @@ impl App {
     }
 
     pub fn next_tab(&mut self) {
-        self.current_tab = self.current_tab.next();
-        self.scroll_offset = 0;
+        self.switch_tab(self.current_tab.next());
     }
 
     pub fn previous_tab(&mut self) {
-        self.current_tab = self.current_tab.previous();
-        self.scroll_offset = 0;
+        self.switch_tab(self.current_tab.previous());
     }
 
     pub fn select_tab(&mut self, index: usize) {
         if let Some(tab) = Tab::from_index(index) {
-            self.current_tab = tab;
-            self.scroll_offset = 0;
+            self.switch_tab(tab);
         }
     }
 
+    fn switch_tab(&mut self, tab: Tab) {
+        self.tab_scroll.insert(self.current_tab, self.scroll_offset);
+        self.current_tab = tab;
+        self.scroll_offset = self.tab_scroll.get(&tab).copied().unwrap_or(0);
+    }
+
     pub fn set_jvm_info(&mut self, info: JvmInfo) {
         self.jvm_info = Some(info);
     }
 
+    pub fn set_warning(&mut self, warning: String) {
+        self.warning = Some(warning);
+    }
+
+    pub fn show_toast(&mut self, message: String) {
+        self.push_toast(LogLevel::Info, message);
+    }
+
+    /// For failures the user can carry on from; losing the JVM still
+    /// takes the full-screen error.
+    pub fn show_error_toast(&mut self, message: String) {
+        self.push_toast(LogLevel::Error, message);
+    }
+
+    fn push_toast(&mut self, level: LogLevel, text: String) {
+        // Blocking operations end by reporting back, so drop their loading screen
+        self.clear_loading();
+        self.log_event(level, text.clone());
+        if self.toasts.len() >= MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+        self.toasts.push(Toast {
+            text,
+            level,
+            expires_at: Instant::now() + TOAST_DURATION,
+        });
+    }
+
+    /// Drops toasts whose time is up; called once per frame.
+    pub fn expire_toasts(&mut self, now: Instant) {
+        self.toasts.retain(|toast| toast.expires_at > now);
+    }
+
+    pub fn log_event(&mut self, level: LogLevel, message: String) {
+        self.event_log.push(LogEntry::new(level, message));
+    }
+
+    pub fn toggle_event_log(&mut self) {
+        self.show_event_log = !self.show_event_log;
+    }
+
     pub fn toggle_help(&mut self) {
         self.mode = match self.mode {
             AppMode::Help => AppMode::Normal,
@@ -182,10 +467,34 @@ impl App {
         };
     }
 
+    pub fn toggle_compact(&mut self) {
+        self.compact = !self.compact;
+        self.show_toast(if self.compact {
+            "Compact layout on".to_string()
+        } else {
+            "Compact layout off".to_string()
+        });
+    }
+
     pub fn show_gc_confirmation(&mut self) {
+        if !self.allow_trigger_gc {
+            self.show_toast("GC triggering is disabled for this connection".to_string());
+            return;
+        }
+        self.gc_confirmation_input.clear();
         self.mode = AppMode::ConfirmGc;
     }
 
+    /// Whether the typed confirmation spells "gc".
+    pub fn gc_confirmation_matches(&self) -> bool {
+        self.gc_confirmation_input.trim().eq_ignore_ascii_case("gc")
+    }
+
+    pub fn show_heap_dump_confirmation(&mut self, plan: HeapDumpPlan) {
+        self.heap_dump_plan = Some(plan);
+        self.mode = AppMode::ConfirmHeapDump;
+    }
+
     pub fn show_export_format_selector(&mut self) {
         self.mode = AppMode::SelectExportFormat;
     }
@@ -207,11 +516,123 @@ impl App {
     }
 
     pub fn show_export_success(&mut self, path: String) {
-        self.mode = AppMode::ExportSuccess(path);
+        self.show_toast(format!("Exported to {}", path));
+        self.last_export_path = Some(path);
+    }
+
+    pub fn show_heap_dump_success(&mut self, path: String, size_bytes: u64) {
+        self.show_toast(format!(
+            "Heap dump written to {} ({})",
+            path,
+            crate::export::format_megabytes(size_bytes)
+        ));
+        self.last_export_path = Some(path);
+    }
+
+    pub fn record_alert(&mut self, alert: &FiredAlert) {
+        self.alert_history.push(AlertEvent {
+            timestamp: Local::now(),
+            rule: alert.rule.clone(),
+            value: alert.value,
+        });
+    }
+
+    /// How often each rule fired this session, most frequent first.
+    pub fn alert_counts(&self) -> Vec<(String, usize)> {
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for event in &self.alert_history {
+            match counts.iter_mut().find(|(rule, _)| *rule == event.rule) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((event.rule.clone(), 1)),
+            }
+        }
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+    }
+
+    pub fn show_alert_history(&mut self) {
+        self.alert_history_scroll = 0;
+        self.mode = AppMode::AlertHistory;
+    }
+
+    pub fn start_shell_prompt(&mut self) {
+        self.shell_command.clear();
+        self.mode = AppMode::ShellPrompt;
+    }
+
+    pub fn start_command_prompt(&mut self) {
+        self.diagnostic_command.clear();
+        self.mode = AppMode::Command;
+    }
+
+    pub fn show_command_output(&mut self, output: String) {
+        self.diagnostic_output = output;
+        self.diagnostic_scroll = 0;
+        self.mode = AppMode::CommandOutput;
+    }
+
+    /// Pages the latest raw tool output, reusing the command output pager.
+    pub fn show_raw_output(&mut self, output: String) {
+        self.diagnostic_output = output;
+        self.diagnostic_scroll = 0;
+        self.mode = AppMode::RawOutput;
+    }
+
+    /// Moves the output pager by `lines`, keeping the last line reachable.
+    pub fn scroll_command_output(&mut self, lines: isize) {
+        let last_line = self.diagnostic_output.lines().count().saturating_sub(1);
+        self.diagnostic_scroll = self
+            .diagnostic_scroll
+            .saturating_add_signed(lines)
+            .min(last_line);
+    }
+
+    pub fn show_system_properties(&mut self, properties: Vec<(String, String)>) {
+        self.system_properties = properties;
+        self.system_properties_filter.clear();
+        self.system_properties_scroll = 0;
+        self.mode = AppMode::SystemProperties;
+    }
+
+    pub fn show_process_info(&mut self, info: ProcessInfo) {
+        self.process_info = Some(info);
+        self.mode = AppMode::ProcessInfo;
     }
 
-    pub fn scroll_down(&mut self) {
-        self.scroll_offset = self.scroll_offset.saturating_add(1);
+    /// Properties whose key or value contains the filter, ignoring case.
+    pub fn filtered_system_properties(&self) -> Vec<&(String, String)> {
+        let filter = self.system_properties_filter.to_lowercase();
+        self.system_properties
+            .iter()
+            .filter(|(key, value)| {
+                key.to_lowercase().contains(&filter) || value.to_lowercase().contains(&filter)
+            })
+            .collect()
+    }
+
+    pub fn scroll_system_properties(&mut self, rows: isize) {
+        let last_row = self.filtered_system_properties().len().saturating_sub(1);
+        self.system_properties_scroll = self
+            .system_properties_scroll
+            .saturating_add_signed(rows)
+            .min(last_row);
+    }
+
+    /// Environment handed to shell commands so they can target this JVM.
+    pub fn shell_env(&self) -> Vec<(String, String)> {
+        let mut env = Vec::new();
+        if let Some(ref info) = self.jvm_info {
+            env.push(("JVM_TUI_PID".to_string(), info.pid.to_string()));
+        }
+        if let Some(ref path) = self.last_export_path {
+            env.push(("JVM_TUI_LAST_EXPORT".to_string(), path.clone()));
+        }
+        env
+    }
+
+    /// Moves down one row of a list `rows` long, stopping at the last row.
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll_offset = (self.scroll_offset + 1).min(rows.saturating_sub(1));
     }
 
     pub fn scroll_up(&mut self) {
@@ -220,9 +641,11 @@ impl App {
 
     pub fn reset_scroll(&mut self) {
         self.scroll_offset = 0;
+        self.tab_scroll.clear();
     }
 
     pub fn show_error(&mut self, message: String) {
+        self.log_event(LogLevel::Error, message.clone());
         self.mode = AppMode::Error(message);
     }
 
@@ -254,6 +677,118 @@ impl App {
         self.search_query.clear();
         self.search_results.clear();
         self.search_index = 0;
+        if self.current_tab == Tab::Classes {
+            self.class_filter.clear();
+        }
+    }
+
+    pub fn hide_columns(&mut self, threads: &[ThreadColumn], classes: &[ClassColumn]) {
+        self.visible_thread_columns
+            .retain(|column| !threads.contains(column));
+        self.visible_class_columns
+            .retain(|column| !classes.contains(column));
+
+        // Hiding every column would leave an empty table
+        if self.visible_thread_columns.is_empty() {
+            self.visible_thread_columns = ThreadColumn::all().to_vec();
+        }
+        if self.visible_class_columns.is_empty() {
+            self.visible_class_columns = ClassColumn::all().to_vec();
+        }
+    }
+
+    pub fn show_column_menu(&mut self) {
+        if matches!(self.current_tab, Tab::Threads | Tab::Classes) {
+            self.column_menu_index = 0;
+            self.mode = AppMode::ColumnMenu;
+        }
+    }
+
+    /// Column titles for the current tab's table, with their visibility.
+    pub fn column_menu_items(&self) -> Vec<(&'static str, bool)> {
+        match self.current_tab {
+            Tab::Threads => ThreadColumn::all()
+                .iter()
+                .map(|c| (c.title(), self.visible_thread_columns.contains(c)))
+                .collect(),
+            Tab::Classes => ClassColumn::all()
+                .iter()
+                .map(|c| (c.title(), self.visible_class_columns.contains(c)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn column_menu_next(&mut self) {
+        let len = self.column_menu_items().len();
+        if len > 0 {
+            self.column_menu_index = (self.column_menu_index + 1) % len;
+        }
+    }
+
+    pub fn column_menu_previous(&mut self) {
+        let len = self.column_menu_items().len();
+        if len > 0 {
+            self.column_menu_index = (self.column_menu_index + len - 1) % len;
+        }
+    }
+
+    /// Toggles the highlighted column, always leaving at least one visible.
+    pub fn toggle_selected_column(&mut self) {
+        match self.current_tab {
+            Tab::Threads => toggle_column(
+                &mut self.visible_thread_columns,
+                &ThreadColumn::all(),
+                self.column_menu_index,
+            ),
+            Tab::Classes => toggle_column(
+                &mut self.visible_class_columns,
+                &ClassColumn::all(),
+                self.column_menu_index,
+            ),
+            _ => {}
+        }
+    }
+
+    /// Captures `histogram` as the diff baseline, or drops the current one.
+    /// Returns whether a baseline is now set.
+    pub fn toggle_class_baseline(&mut self, histogram: &[ClassInfo]) -> bool {
+        self.scroll_offset = 0;
+        if self.class_baseline.take().is_some() || histogram.is_empty() {
+            return false;
+        }
+        self.class_baseline = Some(histogram.to_vec());
+        true
+    }
+
+    pub fn cycle_class_sort(&mut self) {
+        self.class_sort = self.class_sort.next();
+        self.scroll_offset = 0;
+    }
+
+    pub fn cycle_thread_sort(&mut self) {
+        self.thread_sort = self.thread_sort.next();
+        self.scroll_offset = 0;
+    }
+
+    /// How many threads the Threads tab lists, `None` for all of them.
+    pub fn thread_limit(&self) -> Option<usize> {
+        self.max_threads_displayed
+            .filter(|_| !self.show_all_threads)
+    }
+
+    /// Switches between every thread and the first `max_threads_displayed`,
+    /// keeping the scroll position inside the list.
+    pub fn toggle_show_all_threads(&mut self) {
+        self.show_all_threads = !self.show_all_threads;
+        if let Some(limit) = self.thread_limit() {
+            self.scroll_offset = self.scroll_offset.min(limit.saturating_sub(1));
+        }
+    }
+
+    pub fn apply_class_filter(&mut self) {
+        self.class_filter = self.search_query.clone();
+        self.scroll_offset = 0;
     }
 
     pub fn push_search_char(&mut self, c: char) {
@@ -297,3 +832,249 @@ impl Default for App {
         Self::new(Arc::new(RwLock::new(MetricsStore::new(300))))
     }
 }
+
+fn toggle_column<T: Copy + PartialEq>(visible: &mut Vec<T>, all: &[T], index: usize) {
+    let column = match all.get(index) {
+        Some(column) => *column,
+        None => return,
+    };
+
+    if visible.contains(&column) {
+        if visible.len() > 1 {
+            visible.retain(|c| *c != column);
+        }
+    } else {
+        // Rebuild in canonical order so columns don't move around
+        *visible = all
+            .iter()
+            .copied()
+            .filter(|c| *c == column || visible.contains(c))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_column_keeps_order_and_one_visible() {
+        let all = ThreadColumn::all();
+        let mut visible = all.to_vec();
+
+        toggle_column(&mut visible, &all, 0);
+        toggle_column(&mut visible, &all, 3);
+        toggle_column(&mut visible, &all, 4);
+        toggle_column(&mut visible, &all, 5);
+        toggle_column(&mut visible, &all, 6);
+        assert_eq!(visible, vec![ThreadColumn::Name, ThreadColumn::State]);
+
+        toggle_column(&mut visible, &all, 0);
+        assert_eq!(
+            visible,
+            vec![ThreadColumn::Id, ThreadColumn::Name, ThreadColumn::State]
+        );
+
+        let mut visible = vec![ThreadColumn::Name];
+        toggle_column(&mut visible, &all, 1);
+        assert_eq!(visible, vec![ThreadColumn::Name]);
+    }
+
+    #[test]
+    fn test_timeline_window_pan_and_zoom() {
+        let len = 100;
+        let mut window = TimelineWindow::default();
+        assert_eq!(window.range(len), 0..100);
+
+        window.zoom_in(len);
+        assert_eq!(window.range(len), 50..100);
+        window.zoom_in(len);
+        assert_eq!(window.range(len), 75..100);
+
+        window.pan_left(len);
+        assert!(!window.follow);
+        assert_eq!(window.range(len), 69..94);
+
+        // New samples don't move a paused window
+        assert_eq!(window.range(len + 5), 69..94);
+
+        window.pan_right(len);
+        assert!(window.follow);
+        assert_eq!(window.range(len), 75..100);
+
+        for _ in 0..10 {
+            window.zoom_in(len);
+        }
+        assert_eq!(window.range(len).len(), MIN_TIMELINE_WIDTH);
+
+        for _ in 0..10 {
+            window.zoom_out(len);
+        }
+        assert_eq!(window, TimelineWindow::default());
+
+        // Shorter histories than the window are shown whole
+        let window = TimelineWindow {
+            start: 40,
+            width: Some(50),
+            follow: false,
+        };
+        assert_eq!(window.range(20), 0..20);
+    }
+
+    #[test]
+    fn test_alert_counts() {
+        let store = Arc::new(RwLock::new(MetricsStore::new(10)));
+        let mut app = App::new(store);
+        let fired = |rule: &str, value| FiredAlert {
+            rule: rule.to_string(),
+            value,
+            message: rule.to_string(),
+            action: crate::config::AlertAction::Banner,
+        };
+
+        app.record_alert(&fired("deadlocked_threads > 0", 2.0));
+        app.record_alert(&fired("heap_percent > 90", 91.0));
+        app.record_alert(&fired("heap_percent > 90", 95.5));
+        app.record_alert(&fired("heap_percent > 90", 93.0));
+
+        assert_eq!(app.alert_history.len(), 4);
+        assert_eq!(app.alert_history[2].value, 95.5);
+        assert_eq!(
+            app.alert_counts(),
+            vec![
+                ("heap_percent > 90".to_string(), 3),
+                ("deadlocked_threads > 0".to_string(), 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gc_confirmation_respects_protection() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        app.allow_trigger_gc = false;
+        app.show_gc_confirmation();
+        assert!(matches!(app.mode, AppMode::Normal));
+        assert_eq!(app.toasts.len(), 1);
+
+        app.allow_trigger_gc = true;
+        app.gc_confirmation_input = "stale".to_string();
+        app.show_gc_confirmation();
+        assert!(matches!(app.mode, AppMode::ConfirmGc));
+        assert!(!app.gc_confirmation_matches());
+
+        app.gc_confirmation_input = "GC".to_string();
+        assert!(app.gc_confirmation_matches());
+    }
+
+    #[test]
+    fn test_toasts_stack_and_expire() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        for i in 0..=MAX_TOASTS {
+            app.show_toast(format!("toast {}", i));
+        }
+        app.show_loading("Copying...".to_string());
+        app.show_error_toast("Copy failed".to_string());
+        assert!(matches!(app.mode, AppMode::Normal));
+        assert_eq!(app.toasts.len(), MAX_TOASTS);
+        assert_eq!(app.toasts[0].text, "toast 2");
+        assert_eq!(app.toasts.last().unwrap().level, LogLevel::Error);
+
+        app.expire_toasts(Instant::now());
+        assert_eq!(app.toasts.len(), MAX_TOASTS);
+        app.expire_toasts(Instant::now() + TOAST_DURATION);
+        assert!(app.toasts.is_empty());
+    }
+
+    #[test]
+    fn test_event_log_records_messages_and_errors() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        app.show_toast("Compact layout on".to_string());
+        app.show_error("Export failed: disk full".to_string());
+        app.show_export_success("/tmp/metrics.json".to_string());
+
+        let entries: Vec<(LogLevel, &str)> = app
+            .event_log
+            .iter()
+            .map(|entry| (entry.level, entry.message.as_str()))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (LogLevel::Info, "Compact layout on"),
+                (LogLevel::Error, "Export failed: disk full"),
+                (LogLevel::Info, "Exported to /tmp/metrics.json"),
+            ]
+        );
+
+        for i in 0..EVENT_LOG_CAPACITY {
+            app.log_event(LogLevel::Info, format!("entry {}", i));
+        }
+        assert_eq!(app.event_log.len(), EVENT_LOG_CAPACITY);
+    }
+
+    #[test]
+    fn test_scroll_down_stops_at_last_row() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        app.scroll_down(3);
+        app.scroll_down(3);
+        app.scroll_down(3);
+        assert_eq!(app.scroll_offset, 2);
+        app.scroll_down(0);
+        assert_eq!(app.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_tabs_remember_scroll_position() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        app.select_tab(Tab::Classes.index());
+        app.scroll_down(50);
+        app.scroll_down(50);
+        app.next_tab();
+        assert_eq!(app.scroll_offset, 0);
+        app.scroll_down(50);
+
+        app.select_tab(Tab::Classes.index());
+        assert_eq!(app.scroll_offset, 2);
+        app.previous_tab();
+        app.next_tab();
+        assert_eq!(app.scroll_offset, 2);
+
+        app.reset_scroll();
+        app.next_tab();
+        app.previous_tab();
+        assert_eq!(app.scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_scroll_command_output() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        app.show_command_output("12345:\nline 1\nline 2\nline 3".to_string());
+        assert!(matches!(app.mode, AppMode::CommandOutput));
+
+        app.scroll_command_output(2);
+        assert_eq!(app.diagnostic_scroll, 2);
+        app.scroll_command_output(isize::MAX);
+        assert_eq!(app.diagnostic_scroll, 3);
+        app.scroll_command_output(-20);
+        assert_eq!(app.diagnostic_scroll, 0);
+    }
+
+    #[test]
+    fn test_filter_system_properties() {
+        let mut app = App::new(Arc::new(RwLock::new(MetricsStore::new(10))));
+        app.show_system_properties(vec![
+            ("file.encoding".to_string(), "UTF-8".to_string()),
+            ("user.timezone".to_string(), "Europe/Berlin".to_string()),
+            ("java.version".to_string(), "17".to_string()),
+        ]);
+
+        app.system_properties_filter = "TIMEZONE".to_string();
+        assert_eq!(app.filtered_system_properties().len(), 1);
+        app.system_properties_filter = "utf".to_string();
+        assert_eq!(app.filtered_system_properties()[0].0, "file.encoding");
+
+        app.system_properties_filter.clear();
+        app.scroll_system_properties(10);
+        assert_eq!(app.system_properties_scroll, 2);
+    }
+}
@@ -1,4 +1,8 @@
-use clap::Parser;
+use crate::config::ExportFormat;
+use crate::metrics::collector::MIN_INTERVAL;
+use clap::{Parser, Subcommand};
+use regex::Regex;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -11,6 +15,23 @@ pub struct Cli {
     #[arg(short, long, help = "Attach to specific JVM process ID")]
     pub pid: Option<u32>,
 
+    #[arg(
+        long = "match",
+        value_name = "REGEX",
+        conflicts_with_all = ["pid", "docker"],
+        value_parser = parse_regex,
+        help = "Attach to the local JVM whose main class matches; with several matches, the picker lists only those"
+    )]
+    pub match_pattern: Option<Regex>,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        conflicts_with_all = ["pid", "docker", "match_pattern", "snapshot", "stream", "health"],
+        help = "Connect to a saved connection from the config file without the picker"
+    )]
+    pub profile: Option<String>,
+
     #[arg(
         short = 'i',
         long,
@@ -26,8 +47,184 @@ pub struct Cli {
         env = "JVM_TUI_CONFIG"
     )]
     pub config: Option<PathBuf>,
+
+    #[arg(
+        short,
+        long,
+        help = "Suppress informational output outside the TUI; errors still go to stderr"
+    )]
+    pub quiet: bool,
+
+    #[arg(long, help = "Append metrics to a CSV file on every poll")]
+    pub csv_log: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["snapshot", "stream", "health", "serve", "replay"],
+        help = "Save the session to a file every few seconds and on exit, for --replay"
+    )]
+    pub record: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "FILE",
+        conflicts_with_all = ["pid", "docker", "match_pattern", "profile", "snapshot", "stream", "health", "serve"],
+        help = "Play back a --record file (or a JSON export) in the TUI instead of attaching to a JVM"
+    )]
+    pub replay: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Unified GC log (-Xlog:gc:file=...) of the target JVM to read pauses from"
+    )]
+    pub gc_log: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Keep the raw output of the latest jcmd/jstat runs and Jolokia requests, shown with 'o', to debug parsing"
+    )]
+    pub debug_raw: bool,
+
+    #[arg(
+        long,
+        help = "Attach to the JVM running in a Docker container (name or id)"
+    )]
+    pub docker: Option<String>,
+
+    #[arg(
+        long,
+        help = "Collect one sample, export it and exit without starting the TUI"
+    )]
+    pub snapshot: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        requires = "snapshot",
+        help = "Export format for --snapshot [default: preferences.default_export_format, or json]"
+    )]
+    pub format: Option<ExportFormat>,
+
+    #[arg(
+        long,
+        requires = "snapshot",
+        help = "Write the --snapshot export to stdout instead of a file"
+    )]
+    pub export_stdout: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with = "snapshot",
+        help = "Print a sample per poll to stdout instead of starting the TUI, until Ctrl-C"
+    )]
+    pub stream: Option<StreamFormat>,
+
+    #[arg(
+        long,
+        value_name = "ADDR",
+        conflicts_with_all = ["snapshot", "stream", "health", "profile"],
+        help = "Serve live metrics in Prometheus format at http://ADDR/metrics instead of starting the TUI, until Ctrl-C"
+    )]
+    pub serve: Option<SocketAddr>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["snapshot", "stream"],
+        help = "Read one heap sample, print a status line and exit 0 if healthy, 2 if over a threshold, 1 if the JVM can't be read"
+    )]
+    pub health: bool,
+
+    #[arg(
+        long,
+        requires = "health",
+        default_value_t = 90.0,
+        value_parser = parse_percent,
+        help = "--health threshold: heap usage percent of max at or above which the JVM is unhealthy"
+    )]
+    pub heap_max: f64,
+
+    #[arg(
+        long,
+        value_parser = clap::value_parser!(u64).range(1..),
+        help = "Export at most this many history samples (see preferences.export_sampling)"
+    )]
+    pub export_max_samples: Option<u64>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum StreamFormat {
+    /// One JSON object per line
+    Jsonl,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(about = "Inspect the configuration file")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    #[command(about = "Validate a config file and print the effective configuration")]
+    Check {
+        #[arg(help = "Config file to check (defaults to the normal search path)")]
+        path: Option<PathBuf>,
+    },
+}
+
+fn parse_percent(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(percent) if (0.0..=100.0).contains(&percent) => Ok(percent),
+        _ => Err(format!(
+            "expected a percentage between 0 and 100, got '{}'",
+            s
+        )),
+    }
+}
+
+fn parse_regex(s: &str) -> Result<Regex, regex::Error> {
+    Regex::new(s)
 }
 
-fn parse_duration(s: &str) -> Result<Duration, humantime::DurationError> {
-    humantime::parse_duration(s)
+/// Polling interval with the same floor the config file enforces.
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let interval = humantime::parse_duration(s).map_err(|e| {
+        format!(
+            "'{}' is not a duration ({}); use e.g. 500ms, 1s, 2s or 1m",
+            s, e
+        )
+    })?;
+    if interval < MIN_INTERVAL {
+        return Err(format!(
+            "'{}' is too short; the interval must be at least {}",
+            s,
+            humantime::format_duration(MIN_INTERVAL)
+        ));
+    }
+    Ok(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("100ms"), Ok(MIN_INTERVAL));
+
+        let error = parse_duration("fast").unwrap_err();
+        assert!(error.contains("'fast'") && error.contains("500ms, 1s"));
+
+        let error = parse_duration("10ms").unwrap_err();
+        assert!(error.contains("'10ms'") && error.contains("at least 100ms"));
+    }
 }
@@ -0,0 +1,12 @@
+use crate::error::{AppError, Result};
+
+/// Copies `text` to the system clipboard. Fails when no clipboard is reachable,
+/// e.g. on a headless host or over a plain SSH session.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut clipboard = arboard::Clipboard::new()
+        .map_err(|e| AppError::Clipboard(format!("No clipboard available: {}", e)))?;
+
+    clipboard
+        .set_text(text.to_string())
+        .map_err(|e| AppError::Clipboard(e.to_string()))
+}
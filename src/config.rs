@@ -1,22 +1,50 @@
 use crate::error::AppError;
+use crate::export::{ExportSampling, SampleLimit, FILENAME_PLACEHOLDERS};
+use crate::metrics::alerts::AlertCondition;
+use crate::metrics::collector::{DEFAULT_CLASS_HISTOGRAM_INTERVAL, MIN_INTERVAL};
+use crate::tui::keybindings::{Action, KeyMap};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
     #[serde(default)]
     pub preferences: Preferences,
     #[serde(default)]
     pub connections: Vec<ConnectionProfile>,
     #[serde(default)]
+    pub alerts: Vec<AlertRule>,
+    #[serde(default)]
     pub advanced: AdvancedSettings,
+    /// Keys for monitoring-screen actions, replacing their defaults.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keybindings: BTreeMap<Action, KeyList>,
+}
+
+/// One key or several for an action; an empty list unbinds it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyList {
+    pub fn keys(&self) -> &[String] {
+        match self {
+            KeyList::One(key) => std::slice::from_ref(key),
+            KeyList::Many(keys) => keys,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Preferences {
     #[serde(
         default = "default_interval",
+        serialize_with = "serialize_duration_string",
         deserialize_with = "deserialize_duration_string"
     )]
     pub default_interval: Duration,
@@ -26,6 +54,66 @@ pub struct Preferences {
 
     #[serde(default)]
     pub export_directory: Option<String>,
+
+    #[serde(default)]
+    pub default_export_format: ExportFormat,
+
+    /// Export the session in this format when quitting with 'q'.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_export_on_quit: Option<ExportFormat>,
+
+    /// Export file name without extension, e.g. `"{main_class}_{pid}_{kind}_{ts}"`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_filename_template: Option<String>,
+
+    /// Caps history exports to this many samples; unset exports everything.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export_max_samples: Option<usize>,
+
+    #[serde(default)]
+    pub export_sampling: ExportSampling,
+
+    /// How often to collect the class histogram, which walks the whole heap;
+    /// stretched further on JVMs where it's slow.
+    #[serde(
+        default = "default_class_histogram_interval",
+        serialize_with = "serialize_duration_string",
+        deserialize_with = "deserialize_duration_string"
+    )]
+    pub class_histogram_interval: Duration,
+
+    #[serde(default)]
+    pub adaptive_interval: bool,
+
+    #[serde(
+        default = "default_max_adaptive_interval",
+        serialize_with = "serialize_duration_string",
+        deserialize_with = "deserialize_duration_string"
+    )]
+    pub max_adaptive_interval: Duration,
+
+    /// Skip metrics the visible tab doesn't show; heap and GC stats are
+    /// always collected.
+    #[serde(default)]
+    pub poll_active_tab_only: bool,
+
+    /// Start in the compact layout ('m' toggles it at runtime).
+    #[serde(default)]
+    pub compact_layout: bool,
+
+    #[serde(default)]
+    pub hidden_thread_columns: Vec<ThreadColumn>,
+
+    /// List at most this many threads, in the current sort order, until 'A'
+    /// shows them all; unset lists every thread.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_threads_displayed: Option<usize>,
+
+    #[serde(default)]
+    pub hidden_class_columns: Vec<ClassColumn>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gc_log_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +131,24 @@ pub enum ConnectionProfile {
         username: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         password: Option<String>,
+        /// JMX service URL to reach through a Jolokia agent in proxy mode.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_url: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_username: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_password: Option<String>,
+        #[serde(default)]
+        use_get: bool,
+        /// Accept any server certificate; for self-signed test setups.
+        #[serde(default)]
+        insecure_skip_verify: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        ca_cert_path: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client_cert_path: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client_key_path: Option<String>,
     },
     #[serde(rename = "ssh-jdk")]
     SshJdk {
@@ -57,6 +163,16 @@ pub enum ConnectionProfile {
         ssh_password: Option<String>,
         pid: u32,
     },
+    Kubectl {
+        name: String,
+        pod: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        container: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        namespace: Option<String>,
+        #[serde(default = "default_kubectl_pid")]
+        pid: u32,
+    },
     #[serde(rename = "ssh-jolokia")]
     SshJolokia {
         name: String,
@@ -74,6 +190,35 @@ pub enum ConnectionProfile {
     },
 }
 
+/// Fires when `when` (e.g. `"heap_percent > 90"`) has held for `for`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub when: String,
+
+    #[serde(
+        rename = "for",
+        default,
+        serialize_with = "serialize_duration_string",
+        deserialize_with = "deserialize_duration_string"
+    )]
+    pub for_duration: Duration,
+
+    #[serde(default)]
+    pub action: AlertAction,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertAction {
+    /// Only show the alert banner.
+    #[default]
+    Banner,
+    /// Show the banner and ring the terminal bell.
+    Beep,
+    /// Show the banner and export the metrics as JSON.
+    Export,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdvancedSettings {
     #[serde(default = "default_http_timeout")]
@@ -87,6 +232,144 @@ pub struct AdvancedSettings {
 
     #[serde(default = "default_retry_delay")]
     pub connection_retry_delay_ms: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub java_home: Option<String>,
+
+    /// Lets 'g' trigger a full GC; turn off on shared production hosts.
+    #[serde(default = "default_allow_trigger_gc")]
+    pub allow_trigger_gc: bool,
+
+    /// Confirm GC triggering by typing "gc" rather than pressing 'y'.
+    #[serde(default)]
+    pub confirm_destructive: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Json,
+    Prometheus,
+    Csv,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn next(self) -> Self {
+        match self {
+            ExportFormat::Json => ExportFormat::Prometheus,
+            ExportFormat::Prometheus => ExportFormat::Csv,
+            ExportFormat::Csv => ExportFormat::Markdown,
+            ExportFormat::Markdown => ExportFormat::Json,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            ExportFormat::Json => ExportFormat::Markdown,
+            ExportFormat::Prometheus => ExportFormat::Json,
+            ExportFormat::Csv => ExportFormat::Prometheus,
+            ExportFormat::Markdown => ExportFormat::Csv,
+        }
+    }
+
+    pub fn extension(&self) -> &str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Prometheus => "prom",
+            ExportFormat::Csv => "csv",
+            ExportFormat::Markdown => "md",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            ExportFormat::Json => "JSON",
+            ExportFormat::Prometheus => "Prometheus",
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Markdown => "Markdown report",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThreadColumn {
+    Id,
+    Name,
+    State,
+    Daemon,
+    StackDepth,
+    Cpu,
+    Lock,
+}
+
+impl ThreadColumn {
+    pub fn all() -> [ThreadColumn; 7] {
+        [
+            ThreadColumn::Id,
+            ThreadColumn::Name,
+            ThreadColumn::State,
+            ThreadColumn::Daemon,
+            ThreadColumn::StackDepth,
+            ThreadColumn::Cpu,
+            ThreadColumn::Lock,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ThreadColumn::Id => "ID",
+            ThreadColumn::Name => "Name",
+            ThreadColumn::State => "State",
+            ThreadColumn::Daemon => "Daemon",
+            ThreadColumn::StackDepth => "Stack Depth",
+            ThreadColumn::Cpu => "CPU %",
+            ThreadColumn::Lock => "Lock",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClassColumn {
+    Rank,
+    Instances,
+    Bytes,
+    Megabytes,
+    Name,
+}
+
+impl ClassColumn {
+    pub fn all() -> [ClassColumn; 5] {
+        [
+            ClassColumn::Rank,
+            ClassColumn::Instances,
+            ClassColumn::Bytes,
+            ClassColumn::Megabytes,
+            ClassColumn::Name,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ClassColumn::Rank => "Rank",
+            ClassColumn::Instances => "Instances",
+            ClassColumn::Bytes => "Bytes",
+            ClassColumn::Megabytes => "MB",
+            ClassColumn::Name => "Class Name",
+        }
+    }
+}
+
+impl Preferences {
+    pub fn export_sample_limit(&self) -> Option<SampleLimit> {
+        self.export_max_samples.map(|max_samples| SampleLimit {
+            max_samples,
+            sampling: self.export_sampling,
+        })
+    }
 }
 
 impl Default for Preferences {
@@ -95,6 +378,20 @@ impl Default for Preferences {
             default_interval: default_interval(),
             max_history_samples: default_max_samples(),
             export_directory: None,
+            default_export_format: ExportFormat::default(),
+            auto_export_on_quit: None,
+            export_filename_template: None,
+            export_max_samples: None,
+            export_sampling: ExportSampling::default(),
+            class_histogram_interval: default_class_histogram_interval(),
+            adaptive_interval: false,
+            max_adaptive_interval: default_max_adaptive_interval(),
+            poll_active_tab_only: false,
+            compact_layout: false,
+            hidden_thread_columns: Vec::new(),
+            max_threads_displayed: None,
+            hidden_class_columns: Vec::new(),
+            gc_log_path: None,
         }
     }
 }
@@ -106,16 +403,9 @@ impl Default for AdvancedSettings {
             ssh_timeout_sec: default_ssh_timeout(),
             connection_retry_attempts: default_retry_attempts(),
             connection_retry_delay_ms: default_retry_delay(),
-        }
-    }
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            preferences: Preferences::default(),
-            connections: Vec::new(),
-            advanced: AdvancedSettings::default(),
+            java_home: None,
+            allow_trigger_gc: default_allow_trigger_gc(),
+            confirm_destructive: false,
         }
     }
 }
@@ -133,8 +423,7 @@ impl Config {
         let content = std::fs::read_to_string(path)
             .map_err(|e| AppError::ConfigLoad(format!("Failed to read config file: {}", e)))?;
 
-        let mut config: Config = toml::from_str(&content)
-            .map_err(|e| AppError::ConfigLoad(format!("Failed to parse config: {}", e)))?;
+        let mut config = Self::parse(path, &content)?;
 
         config.expand_environment_variables();
         config.validate()?;
@@ -142,16 +431,21 @@ impl Config {
         Ok(config)
     }
 
-    pub fn find_config_file() -> Option<PathBuf> {
-        let config_locations = Self::config_search_paths();
-
-        for path in config_locations {
-            if path.exists() {
-                return Some(path);
-            }
+    /// YAML for `.yaml`/`.yml` files, TOML for anything else.
+    fn parse(path: &std::path::Path, content: &str) -> Result<Self, AppError> {
+        let parse_error = |e: &dyn std::fmt::Display| {
+            AppError::ConfigLoad(format!("Failed to parse config: {}", e))
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml" | "yml") => serde_norway::from_str(content).map_err(|e| parse_error(&e)),
+            _ => toml::from_str(content).map_err(|e| parse_error(&e)),
         }
+    }
 
-        None
+    pub fn find_config_file() -> Option<PathBuf> {
+        Self::config_search_paths()
+            .into_iter()
+            .find(|path| path.exists())
     }
 
     pub fn config_search_paths() -> Vec<PathBuf> {
@@ -161,16 +455,22 @@ impl Config {
             paths.push(PathBuf::from(custom_path));
         }
 
-        paths.push(PathBuf::from("./config.toml"));
-        paths.push(PathBuf::from("./jvm-tui.toml"));
+        let mut candidates = vec![PathBuf::from("./config"), PathBuf::from("./jvm-tui")];
 
         if let Some(config_dir) = dirs::config_dir() {
-            paths.push(config_dir.join("jvm-tui").join("config.toml"));
+            candidates.push(config_dir.join("jvm-tui").join("config"));
         }
 
         if let Some(home_dir) = dirs::home_dir() {
-            paths.push(home_dir.join(".jvm-tui.toml"));
-            paths.push(home_dir.join(".config").join("jvm-tui").join("config.toml"));
+            candidates.push(home_dir.join(".jvm-tui"));
+            candidates.push(home_dir.join(".config").join("jvm-tui").join("config"));
+        }
+
+        // TOML first, then the YAML spellings of the same file
+        for candidate in candidates {
+            for extension in ["toml", "yaml", "yml"] {
+                paths.push(candidate.with_extension(extension));
+            }
         }
 
         paths
@@ -184,6 +484,14 @@ impl Config {
                 .to_string();
         }
 
+        if let Some(ref mut java_home) = self.advanced.java_home {
+            *java_home = shellexpand::tilde(java_home).to_string();
+        }
+
+        if let Some(ref mut gc_log_path) = self.preferences.gc_log_path {
+            *gc_log_path = shellexpand::tilde(gc_log_path).to_string();
+        }
+
         for connection in &mut self.connections {
             match connection {
                 ConnectionProfile::SshJdk { ssh_key, .. }
@@ -192,6 +500,19 @@ impl Config {
                         *key_path = shellexpand::tilde(key_path).to_string();
                     }
                 }
+                ConnectionProfile::Jolokia {
+                    ca_cert_path,
+                    client_cert_path,
+                    client_key_path,
+                    ..
+                } => {
+                    for path in [ca_cert_path, client_cert_path, client_key_path]
+                        .into_iter()
+                        .flatten()
+                    {
+                        *path = shellexpand::tilde(path).to_string();
+                    }
+                }
                 _ => {}
             }
         }
@@ -204,33 +525,146 @@ impl Config {
             ));
         }
 
-        if self.preferences.default_interval < Duration::from_millis(100) {
+        if self.preferences.default_interval < MIN_INTERVAL {
             return Err(AppError::ConfigLoad(
                 "default_interval must be at least 100ms".to_string(),
             ));
         }
 
-        for (idx, conn) in self.connections.iter().enumerate() {
+        if self.preferences.class_histogram_interval < MIN_INTERVAL {
+            return Err(AppError::ConfigLoad(
+                "class_histogram_interval must be at least 100ms".to_string(),
+            ));
+        }
+
+        if self.preferences.adaptive_interval
+            && self.preferences.max_adaptive_interval < self.preferences.default_interval
+        {
+            return Err(AppError::ConfigLoad(
+                "max_adaptive_interval must not be shorter than default_interval".to_string(),
+            ));
+        }
+
+        if self.preferences.export_max_samples == Some(0) {
+            return Err(AppError::ConfigLoad(
+                "export_max_samples must be greater than 0".to_string(),
+            ));
+        }
+
+        if self.preferences.max_threads_displayed == Some(0) {
+            return Err(AppError::ConfigLoad(
+                "max_threads_displayed must be greater than 0".to_string(),
+            ));
+        }
+
+        if let Some(template) = &self.preferences.export_filename_template {
+            let mut rest = template.as_str();
+            while let Some(start) = rest.find('{') {
+                let end = rest[start..].find('}').map(|end| start + end + 1);
+                let placeholder = &rest[start..end.unwrap_or(rest.len())];
+                if !FILENAME_PLACEHOLDERS.contains(&placeholder) {
+                    return Err(AppError::ConfigLoad(format!(
+                        "export_filename_template: unknown placeholder '{}' (known: {})",
+                        placeholder,
+                        FILENAME_PLACEHOLDERS.join(", ")
+                    )));
+                }
+                rest = &rest[end.unwrap_or(rest.len())..];
+            }
+        }
+
+        KeyMap::new(&self.keybindings)
+            .map_err(|e| AppError::ConfigLoad(format!("keybindings: {}", e)))?;
+
+        for (index, alert) in self.alerts.iter().enumerate() {
+            AlertCondition::parse(&alert.when).map_err(|e| {
+                AppError::ConfigLoad(format!("Alert {} ('{}'): {}", index + 1, alert.when, e))
+            })?;
+        }
+
+        for conn in &self.connections {
             match conn {
-                ConnectionProfile::Jolokia { url, .. } => {
+                ConnectionProfile::Jolokia {
+                    url,
+                    target_url,
+                    target_username,
+                    target_password,
+                    use_get,
+                    insecure_skip_verify,
+                    ca_cert_path,
+                    client_cert_path,
+                    client_key_path,
+                    ..
+                } => {
                     if !url.starts_with("http://") && !url.starts_with("https://") {
                         return Err(AppError::ConfigLoad(format!(
                             "Connection '{}': Jolokia URL must start with http:// or https://",
-                            idx
+                            conn.name()
+                        )));
+                    }
+                    let uses_tls = *insecure_skip_verify
+                        || ca_cert_path.is_some()
+                        || client_cert_path.is_some()
+                        || client_key_path.is_some();
+                    if uses_tls && !url.starts_with("https://") {
+                        return Err(AppError::ConfigLoad(format!(
+                            "Connection '{}': TLS settings need an https:// URL",
+                            conn.name()
+                        )));
+                    }
+                    if *insecure_skip_verify && ca_cert_path.is_some() {
+                        return Err(AppError::ConfigLoad(format!(
+                            "Connection '{}': insecure_skip_verify and ca_cert_path are \
+                             mutually exclusive",
+                            conn.name()
+                        )));
+                    }
+                    if client_cert_path.is_some() != client_key_path.is_some() {
+                        return Err(AppError::ConfigLoad(format!(
+                            "Connection '{}': client_cert_path and client_key_path must be \
+                             set together",
+                            conn.name()
                         )));
                     }
+                    match target_url {
+                        Some(target_url) => {
+                            if !target_url.starts_with("service:jmx:") {
+                                return Err(AppError::ConfigLoad(format!(
+                                    "Connection '{}': target_url must be a JMX service URL \
+                                     (service:jmx:...)",
+                                    conn.name()
+                                )));
+                            }
+                            if *use_get {
+                                return Err(AppError::ConfigLoad(format!(
+                                    "Connection '{}': proxy requests (target_url) need POST, \
+                                     remove use_get",
+                                    conn.name()
+                                )));
+                            }
+                        }
+                        None => {
+                            if target_username.is_some() || target_password.is_some() {
+                                return Err(AppError::ConfigLoad(format!(
+                                    "Connection '{}': target_username/target_password \
+                                     require target_url",
+                                    conn.name()
+                                )));
+                            }
+                        }
+                    }
                 }
                 ConnectionProfile::SshJdk { ssh_host, pid, .. } => {
                     if ssh_host.is_empty() {
                         return Err(AppError::ConfigLoad(format!(
                             "Connection '{}': ssh_host cannot be empty",
-                            idx
+                            conn.name()
                         )));
                     }
                     if *pid == 0 {
                         return Err(AppError::ConfigLoad(format!(
                             "Connection '{}': pid must be greater than 0",
-                            idx
+                            conn.name()
                         )));
                     }
                 }
@@ -242,13 +676,27 @@ impl Config {
                     if ssh_host.is_empty() {
                         return Err(AppError::ConfigLoad(format!(
                             "Connection '{}': ssh_host cannot be empty",
-                            idx
+                            conn.name()
                         )));
                     }
                     if *jolokia_port == 0 {
                         return Err(AppError::ConfigLoad(format!(
                             "Connection '{}': jolokia_port must be greater than 0",
-                            idx
+                            conn.name()
+                        )));
+                    }
+                }
+                ConnectionProfile::Kubectl { pod, pid, .. } => {
+                    if pod.is_empty() {
+                        return Err(AppError::ConfigLoad(format!(
+                            "Connection '{}': pod cannot be empty",
+                            conn.name()
+                        )));
+                    }
+                    if *pid == 0 {
+                        return Err(AppError::ConfigLoad(format!(
+                            "Connection '{}': pid must be greater than 0",
+                            conn.name()
                         )));
                     }
                 }
@@ -259,11 +707,48 @@ impl Config {
         Ok(())
     }
 
+    /// Pretty-printed TOML of the effective config, with passwords masked.
+    pub fn to_redacted_toml(&self) -> Result<String, AppError> {
+        let mut config = self.clone();
+        for connection in &mut config.connections {
+            match connection {
+                ConnectionProfile::Jolokia {
+                    password,
+                    target_password,
+                    ..
+                } => {
+                    for password in [password, target_password] {
+                        if password.is_some() {
+                            *password = Some("********".to_string());
+                        }
+                    }
+                }
+                ConnectionProfile::SshJdk {
+                    ssh_password: password,
+                    ..
+                }
+                | ConnectionProfile::SshJolokia {
+                    ssh_password: password,
+                    ..
+                } => {
+                    if password.is_some() {
+                        *password = Some("********".to_string());
+                    }
+                }
+                ConnectionProfile::Local { .. } | ConnectionProfile::Kubectl { .. } => {}
+            }
+        }
+
+        toml::to_string_pretty(&config)
+            .map_err(|e| AppError::Config(format!("Failed to serialize config: {}", e)))
+    }
+
     pub fn get_connection(&self, name: &str) -> Option<&ConnectionProfile> {
         self.connections.iter().find(|c| match c {
             ConnectionProfile::Local { name: n, .. } => n == name,
             ConnectionProfile::Jolokia { name: n, .. } => n == name,
             ConnectionProfile::SshJdk { name: n, .. } => n == name,
+            ConnectionProfile::Kubectl { name: n, .. } => n == name,
             ConnectionProfile::SshJolokia { name: n, .. } => n == name,
         })
     }
@@ -275,6 +760,7 @@ impl ConnectionProfile {
             ConnectionProfile::Local { name, .. } => name,
             ConnectionProfile::Jolokia { name, .. } => name,
             ConnectionProfile::SshJdk { name, .. } => name,
+            ConnectionProfile::Kubectl { name, .. } => name,
             ConnectionProfile::SshJolokia { name, .. } => name,
         }
     }
@@ -284,6 +770,7 @@ impl ConnectionProfile {
             ConnectionProfile::Local { .. } => "Local",
             ConnectionProfile::Jolokia { .. } => "Jolokia (HTTP)",
             ConnectionProfile::SshJdk { .. } => "SSH + JDK Tools",
+            ConnectionProfile::Kubectl { .. } => "kubectl exec",
             ConnectionProfile::SshJolokia { .. } => "SSH + Jolokia",
         }
     }
@@ -297,10 +784,22 @@ fn default_max_samples() -> usize {
     300
 }
 
+fn default_class_histogram_interval() -> Duration {
+    DEFAULT_CLASS_HISTOGRAM_INTERVAL
+}
+
+fn default_max_adaptive_interval() -> Duration {
+    Duration::from_secs(10)
+}
+
 fn default_ssh_port() -> u16 {
     22
 }
 
+fn default_kubectl_pid() -> u32 {
+    1
+}
+
 fn default_http_timeout() -> u64 {
     5000
 }
@@ -317,6 +816,17 @@ fn default_retry_delay() -> u64 {
     1000
 }
 
+fn default_allow_trigger_gc() -> bool {
+    true
+}
+
+fn serialize_duration_string<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&humantime::format_duration(*duration).to_string())
+}
+
 fn deserialize_duration_string<'de, D>(deserializer: D) -> Result<Duration, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -328,13 +838,375 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
 
     #[test]
     fn test_default_config() {
         let config = Config::default();
         assert_eq!(config.preferences.default_interval, Duration::from_secs(1));
         assert_eq!(config.preferences.max_history_samples, 300);
+        assert_eq!(
+            config.preferences.class_histogram_interval,
+            Duration::from_secs(30)
+        );
         assert!(config.connections.is_empty());
+        assert!(config.advanced.allow_trigger_gc);
+        assert!(!config.advanced.confirm_destructive);
+    }
+
+    #[test]
+    fn test_parse_gc_protection() {
+        let config: Config = toml::from_str(
+            r#"
+            [advanced]
+            allow_trigger_gc = false
+            confirm_destructive = true
+            "#,
+        )
+        .unwrap();
+
+        assert!(!config.advanced.allow_trigger_gc);
+        assert!(config.advanced.confirm_destructive);
+    }
+
+    #[test]
+    fn test_yaml_and_toml_parse_alike() {
+        let toml = r#"
+            [preferences]
+            default_interval = "500ms"
+            hidden_thread_columns = ["cpu"]
+
+            [[connections]]
+            name = "Prod"
+            type = "jolokia"
+            url = "https://prod:8778/jolokia"
+            username = "admin"
+
+            [[alerts]]
+            when = "heap_percent > 90"
+            for = "30s"
+            action = "beep"
+        "#;
+        let yaml = r#"
+preferences:
+  default_interval: 500ms
+  hidden_thread_columns: [cpu]
+connections:
+  - name: Prod
+    type: jolokia
+    url: https://prod:8778/jolokia
+    username: admin
+alerts:
+  - when: heap_percent > 90
+    for: 30s
+    action: beep
+"#;
+
+        let from_toml = Config::parse(Path::new("config.toml"), toml).unwrap();
+        let from_yaml = Config::parse(Path::new("config.yaml"), yaml).unwrap();
+        assert_eq!(format!("{:?}", from_toml), format!("{:?}", from_yaml));
+        assert_eq!(from_yaml.connections[0].name(), "Prod");
+
+        // Either format round-trips through the other
+        let as_yaml = serde_norway::to_string(&from_toml).unwrap();
+        let reparsed = Config::parse(Path::new("config.yml"), &as_yaml).unwrap();
+        assert_eq!(format!("{:?}", reparsed), format!("{:?}", from_toml));
+
+        assert!(Config::parse(Path::new("config.yaml"), toml).is_err());
+    }
+
+    #[test]
+    fn test_config_search_paths_include_yaml() {
+        let paths = Config::config_search_paths();
+        let local: Vec<&Path> = paths
+            .iter()
+            .map(PathBuf::as_path)
+            .filter(|path| path.starts_with("./"))
+            .collect();
+        assert_eq!(
+            local,
+            vec![
+                Path::new("./config.toml"),
+                Path::new("./config.yaml"),
+                Path::new("./config.yml"),
+                Path::new("./jvm-tui.toml"),
+                Path::new("./jvm-tui.yaml"),
+                Path::new("./jvm-tui.yml"),
+            ]
+        );
+        if let Some(home_dir) = dirs::home_dir() {
+            assert!(paths.contains(&home_dir.join(".jvm-tui.yml")));
+        }
+    }
+
+    #[test]
+    fn test_validation_error_names_connection() {
+        let toml = r#"
+            [[connections]]
+            name = "Staging API"
+            type = "jolokia"
+            url = "localhost:8778/jolokia"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("'Staging API'"), "{}", err);
+    }
+
+    #[test]
+    fn test_redacted_toml_round_trips() {
+        let toml = r#"
+            [preferences]
+            default_interval = "500ms"
+
+            [[connections]]
+            name = "Prod"
+            type = "jolokia"
+            url = "https://prod:8778/jolokia"
+            username = "admin"
+            password = "secret"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let printed = config.to_redacted_toml().unwrap();
+        assert!(!printed.contains("secret"));
+
+        let reparsed: Config = toml::from_str(&printed).unwrap();
+        assert_eq!(
+            reparsed.preferences.default_interval,
+            Duration::from_millis(500)
+        );
+        assert_eq!(reparsed.connections[0].name(), "Prod");
+    }
+
+    #[test]
+    fn test_parse_alerts() {
+        let toml = r#"
+            [[alerts]]
+            when = "heap_percent > 90"
+            for = "30s"
+            action = "beep"
+
+            [[alerts]]
+            when = "deadlocked_threads > 0"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(config.alerts.len(), 2);
+        assert_eq!(config.alerts[0].for_duration, Duration::from_secs(30));
+        assert_eq!(config.alerts[0].action, AlertAction::Beep);
+        assert_eq!(config.alerts[1].for_duration, Duration::ZERO);
+        assert_eq!(config.alerts[1].action, AlertAction::Banner);
+    }
+
+    #[test]
+    fn test_invalid_alert_is_rejected() {
+        let toml = r#"
+            [[alerts]]
+            when = "heap > 90"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("unknown metric 'heap'"), "{}", err);
+    }
+
+    #[test]
+    fn test_jolokia_proxy_validation() {
+        let toml = r#"
+            [[connections]]
+            name = "Via Proxy"
+            type = "jolokia"
+            url = "http://proxy:8080/jolokia"
+            target_url = "service:jmx:rmi:///jndi/rmi://app:9010/jmxrmi"
+            target_username = "monitor"
+            target_password = "secret"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+        assert!(!config.to_redacted_toml().unwrap().contains("secret"));
+
+        let with_get = toml.replace("target_password", "use_get = true\ntarget_password");
+        let config: Config = toml::from_str(&with_get).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("need POST"), "{}", err);
+
+        let toml = r#"
+            [[connections]]
+            name = "No Target"
+            type = "jolokia"
+            url = "http://proxy:8080/jolokia"
+            target_password = "secret"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("require target_url"), "{}", err);
+    }
+
+    #[test]
+    fn test_jolokia_tls_validation() {
+        let toml = r#"
+            [[connections]]
+            name = "Internal"
+            type = "jolokia"
+            url = "https://app.internal:8778/jolokia"
+            ca_cert_path = "~/certs/corp-ca.pem"
+            client_cert_path = "/etc/jvm-tui/client.pem"
+            client_key_path = "/etc/jvm-tui/client.key"
+        "#;
+        let mut config: Config = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+        config.expand_environment_variables();
+        match &config.connections[0] {
+            ConnectionProfile::Jolokia {
+                ca_cert_path,
+                insecure_skip_verify,
+                ..
+            } => {
+                assert!(!ca_cert_path.as_deref().unwrap().starts_with('~'));
+                assert!(!insecure_skip_verify);
+            }
+            _ => panic!("Expected Jolokia connection"),
+        }
+
+        let cases = [
+            (
+                toml.replace("client_key_path", "# client_key_path"),
+                "set together",
+            ),
+            (
+                toml.replace("ca_cert_path", "insecure_skip_verify = true\nca_cert_path"),
+                "mutually exclusive",
+            ),
+            (toml.replace("https://", "http://"), "https:// URL"),
+        ];
+        for (toml, expected) in cases {
+            let config: Config = toml::from_str(&toml).unwrap();
+            let err = config.validate().unwrap_err().to_string();
+            assert!(err.contains(expected), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_parse_kubectl_connection() {
+        let toml = r#"
+            [[connections]]
+            name = "Orders Pod"
+            type = "kubectl"
+            pod = "orders-7d9f"
+            container = "app"
+            namespace = "prod"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        config.validate().unwrap();
+
+        match &config.connections[0] {
+            ConnectionProfile::Kubectl {
+                pod,
+                container,
+                namespace,
+                pid,
+                ..
+            } => {
+                assert_eq!(pod, "orders-7d9f");
+                assert_eq!(container.as_deref(), Some("app"));
+                assert_eq!(namespace.as_deref(), Some("prod"));
+                assert_eq!(*pid, 1);
+            }
+            _ => panic!("Expected Kubectl connection"),
+        }
+    }
+
+    #[test]
+    fn test_parse_default_export_format() {
+        let config: Config = toml::from_str("[preferences]\n").unwrap();
+        assert_eq!(config.preferences.default_export_format, ExportFormat::Json);
+
+        let toml = r#"
+            [preferences]
+            default_export_format = "prometheus"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.preferences.default_export_format,
+            ExportFormat::Prometheus
+        );
+        assert_eq!(config.preferences.auto_export_on_quit, None);
+
+        let toml = r#"
+            [preferences]
+            auto_export_on_quit = "markdown"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.preferences.auto_export_on_quit,
+            Some(ExportFormat::Markdown)
+        );
+    }
+
+    #[test]
+    fn test_parse_export_sample_limit() {
+        let config: Config = toml::from_str("[preferences]\n").unwrap();
+        assert_eq!(config.preferences.export_sample_limit(), None);
+
+        let toml = r#"
+            [preferences]
+            export_max_samples = 500
+            export_sampling = "downsample"
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.preferences.export_sample_limit(),
+            Some(SampleLimit {
+                max_samples: 500,
+                sampling: ExportSampling::Downsample
+            })
+        );
+
+        let mut config = Config::default();
+        config.preferences.export_max_samples = Some(0);
+        assert!(config.validate().is_err());
+
+        let mut config = Config::default();
+        config.preferences.max_threads_displayed = Some(0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_export_filename_template() {
+        let mut config = Config::default();
+        config.preferences.export_filename_template = Some("{main_class}_{pid}_{ts}".to_string());
+        assert!(config.validate().is_ok());
+
+        config.preferences.export_filename_template = Some("{service}_{ts}".to_string());
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("'{service}'"), "{}", err);
+
+        config.preferences.export_filename_template = Some("{ts".to_string());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_hidden_columns() {
+        let toml = r#"
+            [preferences]
+            hidden_thread_columns = ["stack_depth"]
+            hidden_class_columns = ["rank", "bytes"]
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(
+            config.preferences.hidden_thread_columns,
+            vec![ThreadColumn::StackDepth]
+        );
+        assert_eq!(
+            config.preferences.hidden_class_columns,
+            vec![ClassColumn::Rank, ClassColumn::Bytes]
+        );
     }
 
     #[test]
@@ -426,6 +1298,10 @@ mod tests {
         config.preferences.default_interval = Duration::from_millis(50);
 
         assert!(config.validate().is_err());
+
+        let mut config = Config::default();
+        config.preferences.class_histogram_interval = Duration::ZERO;
+        assert!(config.validate().is_err());
     }
 
     #[test]
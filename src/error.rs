@@ -25,9 +25,103 @@ pub enum AppError {
     #[error("Configuration load error: {0}")]
     ConfigLoad(String),
 
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+
     #[error("Process error: {0}")]
     Process(String),
 
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("No JVM found: {0}")]
+    NoJvm(String),
+
+    /// The JVM is reachable but can't provide this metric, e.g. heap info
+    /// from an OpenJ9 JVM through jcmd.
+    #[error("{0}")]
+    Unsupported(String),
+
+    /// Something typed into the TUI that was refused before reaching the JVM.
+    #[error("{0}")]
+    InvalidInput(String),
+}
+
+/// Process exit codes, so scripts can tell failures apart. clap already exits
+/// with 2 on an invalid command line.
+pub mod exit_code {
+    pub const FAILURE: i32 = 1;
+    pub const CONFIG: i32 = 3;
+    pub const NO_JVM: i32 = 4;
+    pub const CONNECTION: i32 = 5;
+
+    /// `--health` follows the monitoring-plugin convention instead: 0 when
+    /// healthy, [`HEALTH_CRITICAL`] over a threshold and [`FAILURE`] when the
+    /// JVM couldn't be read.
+    pub const HEALTH_CRITICAL: i32 = 2;
+}
+
+impl AppError {
+    /// Whether the JVM, or the way to it, could not be reached, as opposed
+    /// to it answering with something we couldn't use.
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            AppError::Connection(_) | AppError::Process(_) | AppError::Io(_) => true,
+            AppError::JdkTools(e) => {
+                !matches!(e, crate::jvm::jdk_tools::JdkToolsError::ParseError(_))
+            }
+            _ => false,
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::Config(_) | AppError::ConfigLoad(_) => exit_code::CONFIG,
+            AppError::NoJvm(_) => exit_code::NO_JVM,
+            AppError::Connection(_) | AppError::JdkTools(_) | AppError::Process(_) => {
+                exit_code::CONNECTION
+            }
+            _ => exit_code::FAILURE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct_per_failure_kind() {
+        assert_eq!(
+            AppError::ConfigLoad("bad toml".to_string()).exit_code(),
+            exit_code::CONFIG
+        );
+        assert_eq!(
+            AppError::NoJvm("none running".to_string()).exit_code(),
+            exit_code::NO_JVM
+        );
+        assert_eq!(
+            AppError::Connection("refused".to_string()).exit_code(),
+            exit_code::CONNECTION
+        );
+        assert_eq!(
+            AppError::Parse("garbage".to_string()).exit_code(),
+            exit_code::FAILURE
+        );
+    }
+
+    #[test]
+    fn test_connection_errors() {
+        assert!(AppError::Connection("refused".to_string()).is_connection_error());
+        assert!(
+            AppError::JdkTools(crate::jvm::jdk_tools::JdkToolsError::Failed {
+                command: "jstat -gcutil 42".to_string(),
+                message: "MonitorException: 42 not found".to_string(),
+            })
+            .is_connection_error()
+        );
+        assert!(!AppError::Parse("garbage".to_string()).is_connection_error());
+        assert!(!AppError::Unsupported("OpenJ9".to_string()).is_connection_error());
+        assert!(!AppError::InvalidInput("help;".to_string()).is_connection_error());
+    }
 }
@@ -1,10 +1,173 @@
 use crate::error::Result;
-use crate::jvm::types::ThreadInfo;
+use crate::jvm::types::{GcStats, HeapInfo, JvmInfo, ThreadInfo, ThreadStateCounts};
+use crate::metrics::ring_buffer::RingBuffer;
 use crate::metrics::store::MetricsStore;
-use chrono::Local;
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
+use chrono::{DateTime, Datelike, Local, Timelike};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, Write};
+use std::path::{Path, PathBuf};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const CSV_HEADER: &str = "metric_name,value,unit,timestamp";
+
+/// File name (without extension) used when no `export_filename_template` is set.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{kind}_{ts}";
+
+/// Heap dumps have always carried the PID, so their default keeps it.
+const DEFAULT_HEAP_DUMP_TEMPLATE: &str = "{kind}_{pid}_{ts}";
+
+/// Placeholders `export_filename_template` understands.
+pub const FILENAME_PLACEHOLDERS: [&str; 5] =
+    ["{kind}", "{ts}", "{format}", "{pid}", "{main_class}"];
+
+/// Classes listed in the Markdown report's histogram table.
+const REPORT_TOP_CLASSES: usize = 10;
+
+/// Which samples survive when an export is capped to fewer than the buffer holds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportSampling {
+    /// The most recent samples.
+    #[default]
+    Latest,
+    /// Evenly spaced samples across the whole history, first and last included.
+    Downsample,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleLimit {
+    pub max_samples: usize,
+    pub sampling: ExportSampling,
+}
+
+impl SampleLimit {
+    /// Indices (ascending) of the samples to keep out of `len`.
+    pub fn indices(&self, len: usize) -> Vec<usize> {
+        if len <= self.max_samples {
+            return (0..len).collect();
+        }
+        match self.sampling {
+            ExportSampling::Latest => (len - self.max_samples..len).collect(),
+            ExportSampling::Downsample if self.max_samples == 1 => vec![len - 1],
+            ExportSampling::Downsample => (0..self.max_samples)
+                .map(|i| i * (len - 1) / (self.max_samples - 1))
+                .collect(),
+        }
+    }
+
+    fn apply_to_buffer<T: Clone>(&self, buffer: &RingBuffer<T>) -> RingBuffer<T> {
+        let items: Vec<&T> = buffer.iter().collect();
+        let mut limited = RingBuffer::new(self.max_samples);
+        for index in self.indices(items.len()) {
+            limited.push(items[index].clone());
+        }
+        limited
+    }
+}
+
+/// Where exports are written and how their files are named.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportTarget<'a> {
+    pub directory: Option<&'a str>,
+    pub filename_template: Option<&'a str>,
+    pub jvm_info: Option<&'a JvmInfo>,
+    pub sample_limit: Option<SampleLimit>,
+}
+
+impl<'a> ExportTarget<'a> {
+    pub fn new(directory: Option<&'a str>) -> Self {
+        Self {
+            directory,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_filename_template(mut self, template: Option<&'a str>) -> Self {
+        self.filename_template = template;
+        self
+    }
+
+    pub fn with_jvm_info(mut self, jvm_info: Option<&'a JvmInfo>) -> Self {
+        self.jvm_info = jvm_info;
+        self
+    }
+
+    pub fn with_sample_limit(mut self, limit: Option<SampleLimit>) -> Self {
+        self.sample_limit = limit;
+        self
+    }
+
+    /// The store as it should be exported: its histories reduced to the
+    /// sample limit, or borrowed untouched when there is none.
+    pub fn limit<'s>(&self, store: &'s MetricsStore) -> Cow<'s, MetricsStore> {
+        let Some(limit) = self.sample_limit else {
+            return Cow::Borrowed(store);
+        };
+
+        let mut limited = store.clone();
+        limited.heap_history = limit.apply_to_buffer(&store.heap_history);
+        limited.gc_history = limit.apply_to_buffer(&store.gc_history);
+        limited.thread_state_history = limit.apply_to_buffer(&store.thread_state_history);
+        limited.gc_pauses = limit
+            .indices(store.gc_pauses.len())
+            .into_iter()
+            .map(|index| store.gc_pauses[index].clone())
+            .collect();
+        Cow::Owned(limited)
+    }
+
+    /// Expands the template into `<name>.<extension>`, with path separators
+    /// in the expanded name replaced so exports stay in the export directory.
+    /// A template ending in `{format}` already carries the extension.
+    pub fn filename(&self, kind: &str, extension: &str) -> String {
+        let default = if extension == "hprof" {
+            DEFAULT_HEAP_DUMP_TEMPLATE
+        } else {
+            DEFAULT_FILENAME_TEMPLATE
+        };
+        let template = self.filename_template.unwrap_or(default);
+
+        let pid = self
+            .jvm_info
+            .map(|info| info.pid.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        let main_class = self
+            .jvm_info
+            .map(|info| info.main_class.as_str())
+            .filter(|main_class| !main_class.is_empty())
+            .unwrap_or("unknown");
+
+        let name = template
+            .replace("{kind}", kind)
+            .replace("{ts}", &Local::now().format("%Y%m%d_%H%M%S").to_string())
+            .replace("{format}", extension)
+            .replace("{pid}", &pid)
+            .replace("{main_class}", main_class);
+        let name = sanitize_filename(&name);
+
+        // A template that expands to nothing usable falls back to the kind
+        if name.trim_matches('.').trim().is_empty() {
+            format!("{}.{}", kind, extension)
+        } else if template.ends_with("{format}") {
+            name
+        } else {
+            format!("{}.{}", name, extension)
+        }
+    }
+}
+
+fn sanitize_filename(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
 
 pub enum ExportFormat {
     Json,
@@ -12,60 +175,87 @@ pub enum ExportFormat {
     Csv,
 }
 
-pub fn export_thread_dump(threads: &[ThreadInfo], base_dir: Option<&str>) -> Result<PathBuf> {
-    let dir = if let Some(custom_dir) = base_dir {
-        PathBuf::from(shellexpand::tilde(custom_dir).to_string())
-    } else {
-        directories::ProjectDirs::from("com", "jvmtui", "JVM-TUI")
-            .map(|dirs| dirs.data_dir().to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."))
-    };
+/// Formats all threads the way `write_thread_dump` writes them.
+pub fn format_thread_dump(threads: &[ThreadInfo]) -> String {
+    let mut out = String::new();
 
-    std::fs::create_dir_all(&dir)?;
+    out.push_str("JVM-TUI Thread Dump\n");
+    out.push_str(&format!("Generated: {}\n", Local::now()));
+    out.push_str(&format!("Total Threads: {}\n\n", threads.len()));
+    out.push_str(&format!("{}\n\n", "=".repeat(80)));
+
+    for thread in threads {
+        out.push_str(&format_thread(thread));
+        out.push('\n');
+    }
 
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("thread_dump_{}.txt", timestamp);
-    let filepath = dir.join(&filename);
+    out.push_str(&format!("{}\n", "=".repeat(80)));
+    out.push_str("End of thread dump\n");
+    out
+}
 
-    let mut file = File::create(&filepath)?;
+/// Formats a single thread's header and stack trace.
+pub fn format_thread(thread: &ThreadInfo) -> String {
+    let mut out = String::new();
 
-    writeln!(file, "JVM-TUI Thread Dump")?;
-    writeln!(file, "Generated: {}", Local::now())?;
-    writeln!(file, "Total Threads: {}\n", threads.len())?;
-    writeln!(file, "{}", "=".repeat(80))?;
-    writeln!(file)?;
+    out.push_str(&format!("Thread #{}: \"{}\"\n", thread.id, thread.name));
+    out.push_str(&format!("  State: {:?}\n", thread.state));
+    if let Some(cpu_time_ns) = thread.cpu_time_ns {
+        out.push_str(&format!(
+            "  CPU Time: {:.2}ms\n",
+            cpu_time_ns as f64 / 1_000_000.0
+        ));
+    }
+    out.push_str(&format!(
+        "  Stack Trace ({} frames):\n",
+        thread.stack_trace.len()
+    ));
 
-    for thread in threads {
-        writeln!(file, "Thread #{}: \"{}\"", thread.id, thread.name)?;
-        writeln!(file, "  State: {:?}", thread.state)?;
-        writeln!(file, "  Stack Trace ({} frames):", thread.stack_trace.len())?;
-
-        for (i, frame) in thread.stack_trace.iter().enumerate() {
-            let location = if let (Some(file), Some(line)) = (&frame.file_name, frame.line_number) {
-                format!("({}:{})", file, line)
-            } else if let Some(file) = &frame.file_name {
-                format!("({})", file)
-            } else {
-                String::from("(Unknown Source)")
-            };
+    for (i, frame) in thread.stack_trace.iter().enumerate() {
+        let location = if let (Some(file), Some(line)) = (&frame.file_name, frame.line_number) {
+            format!("({}:{})", file, line)
+        } else if let Some(file) = &frame.file_name {
+            format!("({})", file)
+        } else {
+            String::from("(Unknown Source)")
+        };
 
-            writeln!(
-                file,
-                "    #{}: {}.{} {}",
-                i, frame.class_name, frame.method_name, location
-            )?;
-        }
+        out.push_str(&format!(
+            "    #{}: {}.{} {}\n",
+            i, frame.class_name, frame.method_name, location
+        ));
+    }
 
-        writeln!(file)?;
+    if !thread.locks.is_empty() {
+        out.push_str("  Locks:\n");
+        for lock in &thread.locks {
+            out.push_str(&format!(
+                "    - {} <{}> ({})\n",
+                lock.kind.description(),
+                lock.address.as_deref().unwrap_or("no object reference"),
+                lock.class_name.as_deref().unwrap_or("unknown")
+            ));
+        }
     }
 
-    writeln!(file, "{}", "=".repeat(80))?;
-    writeln!(file, "End of thread dump")?;
+    out
+}
+
+pub fn export_thread_dump(threads: &[ThreadInfo], target: &ExportTarget) -> Result<PathBuf> {
+    let (filepath, mut file) = create_export_file(target, "thread_dump", "txt")?;
+    write_thread_dump(threads, &mut file)?;
 
     Ok(filepath)
 }
 
-pub fn export_metrics_json(store: &MetricsStore, base_dir: Option<&str>) -> Result<PathBuf> {
+pub fn write_thread_dump(threads: &[ThreadInfo], out: &mut impl Write) -> Result<()> {
+    out.write_all(format_thread_dump(threads).as_bytes())?;
+    Ok(())
+}
+
+/// Resolves the export directory (the configured one, or the platform data
+/// directory) and makes sure it exists.
+fn export_dir(base_dir: Option<&str>) -> Result<PathBuf> {
     let dir = if let Some(custom_dir) = base_dir {
         PathBuf::from(shellexpand::tilde(custom_dir).to_string())
     } else {
@@ -75,201 +265,659 @@ pub fn export_metrics_json(store: &MetricsStore, base_dir: Option<&str>) -> Resu
     };
 
     std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// Creates the templated `<kind>` export file in the export directory.
+fn create_export_file(
+    target: &ExportTarget,
+    kind: &str,
+    extension: &str,
+) -> Result<(PathBuf, File)> {
+    let filepath = export_dir(target.directory)?.join(target.filename(kind, extension));
+    let file = File::create(&filepath)?;
+    Ok((filepath, file))
+}
+
+/// Where a heap dump would be written and whether it is likely to fit.
+#[derive(Debug, Clone)]
+pub struct HeapDumpPlan {
+    pub path: PathBuf,
+    pub estimated_bytes: u64,
+    /// Only looked up for local JVMs; a remote one writes to its own disk.
+    pub local: bool,
+    pub available_bytes: Option<u64>,
+}
 
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("metrics_{}.json", timestamp);
-    let filepath = dir.join(&filename);
+impl HeapDumpPlan {
+    /// A dump is roughly the size of the used heap, so anything less free
+    /// space than that is likely to fill the disk.
+    pub fn insufficient_space(&self) -> bool {
+        self.available_bytes
+            .is_some_and(|available| available < self.estimated_bytes)
+    }
+
+    pub fn confirmation_message(&self) -> String {
+        let mut message = format!(
+            "Write a heap dump of about {} to:\n\n{}\n",
+            format_megabytes(self.estimated_bytes),
+            self.path.display()
+        );
+        match self.available_bytes {
+            _ if !self.local => {}
+            Some(available) if self.insufficient_space() => message.push_str(&format!(
+                "\nWARNING: only {} free on the target filesystem!\n",
+                format_megabytes(available)
+            )),
+            Some(available) => message.push_str(&format!(
+                "\n{} free on the target filesystem\n",
+                format_megabytes(available)
+            )),
+            None => message.push_str("\nFree space on the target filesystem is unknown\n"),
+        }
+        message.push_str("\nThe JVM is paused while the dump is written.");
+        message
+    }
+}
+
+/// Picks an absolute `.hprof` path in the export directory and, for a
+/// `local` JVM, checks the free space there. The path must be absolute
+/// because the target JVM, not us, resolves it.
+pub fn plan_heap_dump(
+    estimated_bytes: u64,
+    target: &ExportTarget,
+    local: bool,
+) -> Result<HeapDumpPlan> {
+    let dir = export_dir(target.directory)?.canonicalize()?;
+
+    Ok(HeapDumpPlan {
+        path: dir.join(target.filename("heap_dump", "hprof")),
+        estimated_bytes,
+        local,
+        available_bytes: local.then(|| fs2::available_space(&dir).ok()).flatten(),
+    })
+}
+
+pub fn format_megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1024.0 / 1024.0)
+}
 
-    let json = serde_json::to_string_pretty(&store)?;
-    std::fs::write(&filepath, json)?;
+/// Writes the metrics export for `format`, returning the file's path.
+pub fn export_metrics(
+    store: &MetricsStore,
+    target: &ExportTarget,
+    format: crate::config::ExportFormat,
+) -> Result<PathBuf> {
+    use crate::config::ExportFormat as Format;
+
+    match format {
+        Format::Json => export_metrics_json(store, target),
+        Format::Prometheus => export_metrics_prometheus(store, target),
+        Format::Csv => export_metrics_csv(store, target),
+        Format::Markdown => export_report_markdown(store, target),
+    }
+}
+
+pub fn export_metrics_json(store: &MetricsStore, target: &ExportTarget) -> Result<PathBuf> {
+    let (filepath, mut file) = create_export_file(target, "metrics", "json")?;
+    write_metrics_json(&target.limit(store), &mut file)?;
 
     Ok(filepath)
 }
 
-pub fn export_metrics_prometheus(store: &MetricsStore, base_dir: Option<&str>) -> Result<PathBuf> {
-    let dir = if let Some(custom_dir) = base_dir {
-        PathBuf::from(shellexpand::tilde(custom_dir).to_string())
-    } else {
-        directories::ProjectDirs::from("com", "jvmtui", "JVM-TUI")
-            .map(|dirs| dirs.data_dir().to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."))
-    };
+pub fn write_metrics_json(store: &MetricsStore, out: &mut impl Write) -> Result<()> {
+    serde_json::to_writer_pretty(&mut *out, store)?;
+    writeln!(out)?;
+    Ok(())
+}
 
-    std::fs::create_dir_all(&dir)?;
+/// One line of `--stream jsonl` output: the latest values after a poll.
+#[derive(Debug, Serialize)]
+pub struct StreamSample<'a> {
+    pub timestamp: DateTime<Local>,
+    pub heap: Option<&'a HeapInfo>,
+    pub gc: Option<&'a GcStats>,
+    pub threads: usize,
+    pub thread_states: Option<ThreadStateCounts>,
+    /// Summed thread CPU usage in percent of one core; `None` until two
+    /// thread samples with CPU times exist.
+    pub cpu_percent: Option<f64>,
+}
+
+impl<'a> StreamSample<'a> {
+    pub fn from_store(store: &'a MetricsStore) -> Self {
+        let cpu_percent = if store.thread_cpu_usage.is_empty() {
+            None
+        } else {
+            Some(store.thread_cpu_usage.values().sum())
+        };
+
+        Self {
+            timestamp: Local::now(),
+            heap: store.heap_history.latest(),
+            gc: store.gc_history.latest(),
+            threads: store.thread_snapshot.len(),
+            thread_states: store.thread_state_history.latest().copied(),
+            cpu_percent,
+        }
+    }
+}
+
+/// Writes the latest sample as a single JSON line and flushes, so readers
+/// at the other end of a pipe see it straight away.
+pub fn write_stream_sample(store: &MetricsStore, out: &mut impl Write) -> Result<()> {
+    // Serialized up front so write failures such as a closed pipe surface
+    // as plain IO errors
+    let line = serde_json::to_string(&StreamSample::from_store(store))?;
+    writeln!(out, "{}", line)?;
+    out.flush()?;
+    Ok(())
+}
 
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("metrics_{}.prom", timestamp);
-    let filepath = dir.join(&filename);
+pub fn export_metrics_prometheus(store: &MetricsStore, target: &ExportTarget) -> Result<PathBuf> {
+    let (filepath, mut file) = create_export_file(target, "metrics", "prom")?;
+    write_metrics_prometheus(&target.limit(store), &mut file)?;
 
-    let mut file = File::create(&filepath)?;
+    Ok(filepath)
+}
+
+/// The Prometheus exposition as a string, for serving over HTTP.
+pub fn render_metrics_prometheus(store: &MetricsStore) -> Result<String> {
+    let mut out = Vec::new();
+    write_metrics_prometheus(store, &mut out)?;
+    Ok(String::from_utf8_lossy(&out).into_owned())
+}
 
-    writeln!(file, "# JVM-TUI Metrics Export")?;
-    writeln!(file, "# Generated: {}", Local::now())?;
-    writeln!(file)?;
+pub fn write_metrics_prometheus(store: &MetricsStore, out: &mut impl Write) -> Result<()> {
+    writeln!(out, "# JVM-TUI Metrics Export")?;
+    writeln!(out, "# Generated: {}", Local::now())?;
+    writeln!(out)?;
 
-    if let Some(heap) = store.heap_history.iter().last() {
+    if let Some(heap) = store.heap_history.latest() {
         writeln!(
-            file,
+            out,
             "# HELP jvm_memory_heap_used_bytes Heap memory used in bytes"
         )?;
-        writeln!(file, "# TYPE jvm_memory_heap_used_bytes gauge")?;
-        writeln!(file, "jvm_memory_heap_used_bytes {}", heap.used_bytes)?;
-        writeln!(file)?;
+        writeln!(out, "# TYPE jvm_memory_heap_used_bytes gauge")?;
+        writeln!(out, "jvm_memory_heap_used_bytes {}", heap.used_bytes)?;
+        writeln!(out)?;
 
         writeln!(
-            file,
+            out,
             "# HELP jvm_memory_heap_max_bytes Heap memory max in bytes"
         )?;
-        writeln!(file, "# TYPE jvm_memory_heap_max_bytes gauge")?;
-        writeln!(file, "jvm_memory_heap_max_bytes {}", heap.max_bytes)?;
-        writeln!(file)?;
+        writeln!(out, "# TYPE jvm_memory_heap_max_bytes gauge")?;
+        writeln!(out, "jvm_memory_heap_max_bytes {}", heap.max_bytes)?;
+        writeln!(out)?;
 
         writeln!(
-            file,
+            out,
             "# HELP jvm_memory_heap_committed_bytes Heap memory committed in bytes"
         )?;
-        writeln!(file, "# TYPE jvm_memory_heap_committed_bytes gauge")?;
+        writeln!(out, "# TYPE jvm_memory_heap_committed_bytes gauge")?;
         writeln!(
-            file,
+            out,
             "jvm_memory_heap_committed_bytes {}",
             heap.committed_bytes
         )?;
-        writeln!(file)?;
+        writeln!(out)?;
     }
 
-    if let Some(gc) = store.gc_history.iter().last() {
+    if let Some(gc) = store.gc_history.latest() {
         writeln!(
-            file,
+            out,
             "# HELP jvm_gc_collections_total Total number of GC collections"
         )?;
-        writeln!(file, "# TYPE jvm_gc_collections_total counter")?;
+        writeln!(out, "# TYPE jvm_gc_collections_total counter")?;
         writeln!(
-            file,
+            out,
             "jvm_gc_collections_total{{gc=\"young\"}} {}",
-            gc.young_gc_count
+            gc.young_gc_count()
         )?;
         writeln!(
-            file,
+            out,
             "jvm_gc_collections_total{{gc=\"old\"}} {}",
-            gc.old_gc_count
+            gc.old_gc_count()
         )?;
-        writeln!(file)?;
+        writeln!(out)?;
 
         writeln!(
-            file,
+            out,
             "# HELP jvm_gc_time_seconds_total Total time spent in GC in seconds"
         )?;
-        writeln!(file, "# TYPE jvm_gc_time_seconds_total counter")?;
+        writeln!(out, "# TYPE jvm_gc_time_seconds_total counter")?;
         writeln!(
-            file,
+            out,
             "jvm_gc_time_seconds_total{{gc=\"young\"}} {:.3}",
-            gc.young_gc_time_ms as f64 / 1000.0
+            gc.young_gc_time_ms() as f64 / 1000.0
         )?;
         writeln!(
-            file,
+            out,
             "jvm_gc_time_seconds_total{{gc=\"old\"}} {:.3}",
-            gc.old_gc_time_ms as f64 / 1000.0
+            gc.old_gc_time_ms() as f64 / 1000.0
         )?;
-        writeln!(file)?;
+        writeln!(out)?;
     }
 
-    if let Some(heap) = store.heap_history.iter().last() {
-        for pool in &heap.pools {
+    if let Some(heap) = store.heap_history.latest() {
+        if !heap.pools.is_empty() {
             writeln!(
-                file,
+                out,
                 "# HELP jvm_memory_pool_used_bytes Memory pool used in bytes"
             )?;
-            writeln!(file, "# TYPE jvm_memory_pool_used_bytes gauge")?;
-            writeln!(
-                file,
-                "jvm_memory_pool_used_bytes{{pool=\"{}\"}} {}",
-                pool.name, pool.used_bytes
-            )?;
-            writeln!(file)?;
+            writeln!(out, "# TYPE jvm_memory_pool_used_bytes gauge")?;
+            for pool in &heap.pools {
+                writeln!(
+                    out,
+                    "jvm_memory_pool_used_bytes{{pool=\"{}\"}} {}",
+                    escape_label_value(&pool.name),
+                    pool.used_bytes
+                )?;
+            }
+            writeln!(out)?;
 
             writeln!(
-                file,
+                out,
                 "# HELP jvm_memory_pool_max_bytes Memory pool max in bytes"
             )?;
-            writeln!(file, "# TYPE jvm_memory_pool_max_bytes gauge")?;
-            writeln!(
-                file,
-                "jvm_memory_pool_max_bytes{{pool=\"{}\"}} {}",
-                pool.name, pool.max_bytes
-            )?;
-            writeln!(file)?;
+            writeln!(out, "# TYPE jvm_memory_pool_max_bytes gauge")?;
+            for pool in &heap.pools {
+                writeln!(
+                    out,
+                    "jvm_memory_pool_max_bytes{{pool=\"{}\"}} {}",
+                    escape_label_value(&pool.name),
+                    pool.max_bytes
+                )?;
+            }
+            writeln!(out)?;
 
             writeln!(
-                file,
+                out,
                 "# HELP jvm_memory_pool_committed_bytes Memory pool committed in bytes"
             )?;
-            writeln!(file, "# TYPE jvm_memory_pool_committed_bytes gauge")?;
-            writeln!(
-                file,
-                "jvm_memory_pool_committed_bytes{{pool=\"{}\"}} {}",
-                pool.name, pool.committed_bytes
-            )?;
-            writeln!(file)?;
+            writeln!(out, "# TYPE jvm_memory_pool_committed_bytes gauge")?;
+            for pool in &heap.pools {
+                writeln!(
+                    out,
+                    "jvm_memory_pool_committed_bytes{{pool=\"{}\"}} {}",
+                    escape_label_value(&pool.name),
+                    pool.committed_bytes
+                )?;
+            }
+            writeln!(out)?;
         }
     }
 
-    let thread_counts: std::collections::HashMap<_, _> =
+    // Sorted so every scrape lists the states in the same order
+    let thread_counts: std::collections::BTreeMap<_, _> =
         store
             .thread_snapshot
             .iter()
-            .fold(std::collections::HashMap::new(), |mut acc, thread| {
+            .fold(std::collections::BTreeMap::new(), |mut acc, thread| {
                 *acc.entry(format!("{:?}", thread.state)).or_insert(0) += 1;
                 acc
             });
 
     writeln!(
-        file,
+        out,
         "# HELP jvm_threads_total Total number of threads by state"
     )?;
-    writeln!(file, "# TYPE jvm_threads_total gauge")?;
+    writeln!(out, "# TYPE jvm_threads_total gauge")?;
     for (state, count) in &thread_counts {
-        writeln!(file, "jvm_threads_total{{state=\"{}\"}} {}", state, count)?;
+        writeln!(
+            out,
+            "jvm_threads_total{{state=\"{}\"}} {}",
+            escape_label_value(state),
+            count
+        )?;
     }
-    writeln!(file)?;
+    writeln!(out)?;
 
     writeln!(
-        file,
+        out,
         "# HELP jvm_classes_loaded_total Total number of classes loaded"
     )?;
-    writeln!(file, "# TYPE jvm_classes_loaded_total gauge")?;
+    writeln!(out, "# TYPE jvm_classes_loaded_total gauge")?;
     let total_classes: u64 = store.class_histogram.iter().map(|c| c.instances).sum();
-    writeln!(file, "jvm_classes_loaded_total {}", total_classes)?;
-    writeln!(file)?;
+    writeln!(out, "jvm_classes_loaded_total {}", total_classes)?;
+    writeln!(out)?;
+
+    Ok(())
+}
+
+/// Lowercases a metric name component and replaces anything outside
+/// `[a-z0-9_]` with `_`, so names are valid in both CSV and Prometheus.
+pub fn sanitize_metric_name(name: &str) -> String {
+    name.chars()
+        .flat_map(char::to_lowercase)
+        .map(|c| {
+            if c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Escapes a Prometheus label value per the text exposition format.
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn export_metrics_csv(store: &MetricsStore, target: &ExportTarget) -> Result<PathBuf> {
+    let (filepath, mut file) = create_export_file(target, "metrics", "csv")?;
+    write_metrics_csv(&target.limit(store), &mut file)?;
 
     Ok(filepath)
 }
 
-pub fn export_metrics_csv(store: &MetricsStore, base_dir: Option<&str>) -> Result<PathBuf> {
-    let dir = if let Some(custom_dir) = base_dir {
-        PathBuf::from(shellexpand::tilde(custom_dir).to_string())
-    } else {
-        directories::ProjectDirs::from("com", "jvmtui", "JVM-TUI")
-            .map(|dirs| dirs.data_dir().to_path_buf())
-            .unwrap_or_else(|| PathBuf::from("."))
-    };
+pub fn write_metrics_csv(store: &MetricsStore, out: &mut impl Write) -> Result<()> {
+    writeln!(out, "{}", CSV_HEADER)?;
+    write_csv_rows(out, store)
+}
 
-    std::fs::create_dir_all(&dir)?;
+pub fn export_report_markdown(store: &MetricsStore, target: &ExportTarget) -> Result<PathBuf> {
+    let (filepath, mut file) = create_export_file(target, "report", "md")?;
+    write_report_markdown(&target.limit(store), &mut file)?;
+
+    Ok(filepath)
+}
+
+/// Writes a GitHub-flavored Markdown report meant to be pasted into an issue
+/// or pull request.
+pub fn write_report_markdown(store: &MetricsStore, out: &mut impl Write) -> Result<()> {
+    let heap = store.heap_history.latest();
+    let gc = store.gc_history.latest();
+    let counts = ThreadStateCounts::from_threads(&store.thread_snapshot);
+
+    writeln!(out, "## JVM Report")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "_Generated by JVM-TUI at {}_",
+        Local::now().to_rfc3339()
+    )?;
+    writeln!(out)?;
+
+    writeln!(out, "```text")?;
+    match heap {
+        Some(heap) => writeln!(
+            out,
+            "Heap:       {} / {}",
+            format_megabytes(heap.used_bytes),
+            format_megabytes(heap.max_bytes)
+        )?,
+        None => writeln!(out, "Heap:       n/a")?,
+    }
+    match gc {
+        Some(gc) => writeln!(
+            out,
+            "GC:         {} young ({:.2}s), {} full ({:.2}s)",
+            gc.young_gc_count(),
+            gc.young_gc_time_ms() as f64 / 1000.0,
+            gc.old_gc_count(),
+            gc.old_gc_time_ms() as f64 / 1000.0
+        )?,
+        None => writeln!(out, "GC:         n/a")?,
+    }
+    if let Some(rate) = store.gc_rate_per_min() {
+        writeln!(out, "GC rate:    {:.1} collections/min", rate)?;
+    }
+    if let Some(throughput) = store.gc_throughput_percent() {
+        writeln!(out, "Throughput: {:.2}%", throughput)?;
+    }
+    writeln!(
+        out,
+        "Threads:    {} ({} blocked, {} deadlocked)",
+        store.thread_snapshot.len(),
+        counts.blocked,
+        store.deadlocked_threads.len()
+    )?;
+    writeln!(out, "```")?;
+    writeln!(out)?;
+
+    if let Some(heap) = heap {
+        writeln!(out, "### Heap")?;
+        writeln!(out)?;
+        writeln!(out, "| Pool | Used | Committed | Max |")?;
+        writeln!(out, "| --- | ---: | ---: | ---: |")?;
+        writeln!(
+            out,
+            "| **Total** | {} | {} | {} |",
+            format_megabytes(heap.used_bytes),
+            format_megabytes(heap.committed_bytes),
+            format_megabytes(heap.max_bytes)
+        )?;
+        for pool in &heap.pools {
+            writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                escape_markdown_cell(&pool.name),
+                format_megabytes(pool.used_bytes),
+                format_megabytes(pool.committed_bytes),
+                format_megabytes(pool.max_bytes)
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    if let Some(gc) = gc {
+        writeln!(out, "### GC")?;
+        writeln!(out)?;
+        writeln!(out, "| Collector | Count | Total Time | Avg Pause |")?;
+        writeln!(out, "| --- | ---: | ---: | ---: |")?;
+        for (name, count, time_ms, average) in [
+            (
+                "Young",
+                gc.young_gc_count(),
+                gc.young_gc_time_ms(),
+                gc.avg_young_gc_ms(),
+            ),
+            (
+                "Full",
+                gc.old_gc_count(),
+                gc.old_gc_time_ms(),
+                gc.avg_old_gc_ms(),
+            ),
+        ] {
+            writeln!(
+                out,
+                "| {} | {} | {:.2}s | {:.2}ms |",
+                name,
+                count,
+                time_ms as f64 / 1000.0,
+                average
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    if !store.thread_snapshot.is_empty() {
+        writeln!(out, "### Threads")?;
+        writeln!(out)?;
+        writeln!(out, "| State | Count |")?;
+        writeln!(out, "| --- | ---: |")?;
+        for (state, count) in [
+            ("Runnable", counts.runnable),
+            ("Blocked", counts.blocked),
+            ("Waiting", counts.waiting),
+            ("Timed Waiting", counts.timed_waiting),
+            ("Other", counts.other),
+        ] {
+            writeln!(out, "| {} | {} |", state, count)?;
+        }
+        writeln!(out)?;
+    }
+
+    if !store.class_histogram.is_empty() {
+        writeln!(out, "### Top Classes")?;
+        writeln!(out)?;
+        writeln!(out, "| # | Class | Instances | Size |")?;
+        writeln!(out, "| ---: | --- | ---: | ---: |")?;
+        for class in store.class_histogram.iter().take(REPORT_TOP_CLASSES) {
+            writeln!(
+                out,
+                "| {} | `{}` | {} | {} |",
+                class.rank,
+                escape_markdown_cell(&class.name),
+                class.instances,
+                format_megabytes(class.bytes)
+            )?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Pipes would otherwise end the table cell early.
+fn escape_markdown_cell(value: &str) -> String {
+    value.replace('|', "\\|")
+}
+
+/// Writes one timestamped zip for support tickets: the metrics JSON, a
+/// thread dump, VM flags and system properties, plus a manifest.
+pub fn export_support_bundle(
+    store: &MetricsStore,
+    threads: &[ThreadInfo],
+    system_properties: &[(String, String)],
+    target: &ExportTarget,
+) -> Result<PathBuf> {
+    let (filepath, file) = create_export_file(target, "support_bundle", "zip")?;
+    write_support_bundle(
+        &target.limit(store),
+        threads,
+        system_properties,
+        target.jvm_info,
+        file,
+    )?;
+
+    Ok(filepath)
+}
+
+pub fn write_support_bundle(
+    store: &MetricsStore,
+    threads: &[ThreadInfo],
+    system_properties: &[(String, String)],
+    jvm_info: Option<&JvmInfo>,
+    out: impl Write + Seek,
+) -> Result<()> {
+    let captured_at = Local::now();
+    let mut metrics = Vec::new();
+    write_metrics_json(store, &mut metrics)?;
+    let vm_flags = jvm_info
+        .map(|info| info.vm_flags.join("\n"))
+        .unwrap_or_default();
+    let properties: String = system_properties
+        .iter()
+        .map(|(key, value)| format!("{}={}\n", key, value))
+        .collect();
 
-    let timestamp = Local::now().format("%Y%m%d_%H%M%S");
-    let filename = format!("metrics_{}.csv", timestamp);
-    let filepath = dir.join(&filename);
+    let files = [
+        ("metrics.json", "Metrics history".to_string(), metrics),
+        (
+            "thread_dump.txt",
+            format!("Thread dump ({} threads)", threads.len()),
+            format_thread_dump(threads).into_bytes(),
+        ),
+        (
+            "vm_flags.txt",
+            "VM flags".to_string(),
+            vm_flags.into_bytes(),
+        ),
+        (
+            "system_properties.txt",
+            format!("System properties ({})", system_properties.len()),
+            properties.into_bytes(),
+        ),
+    ];
 
-    let mut file = File::create(&filepath)?;
+    let mut manifest = String::from("JVM-TUI support bundle\n");
+    manifest.push_str(&format!(
+        "Tool version: jvm-tui {}\n",
+        env!("CARGO_PKG_VERSION")
+    ));
+    manifest.push_str(&format!(
+        "Captured:     {}\n",
+        captured_at.format("%Y-%m-%d %H:%M:%S %:z")
+    ));
+    if let Some(info) = jvm_info {
+        manifest.push_str(&format!("PID:          {}\n", info.pid));
+        manifest.push_str(&format!("Main class:   {}\n", info.main_class));
+        manifest.push_str(&format!("JVM version:  {}\n", info.version));
+    }
+    manifest.push_str("\nFiles:\n");
+    for (name, description, _) in &files {
+        manifest.push_str(&format!("  {:<23}{}\n", name, description));
+    }
+
+    let mut zip = ZipWriter::new(out);
+    let mut options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    // Entries are otherwise dated 1980
+    if let Ok(modified) = zip::DateTime::from_date_and_time(
+        captured_at.year() as u16,
+        captured_at.month() as u8,
+        captured_at.day() as u8,
+        captured_at.hour() as u8,
+        captured_at.minute() as u8,
+        captured_at.second() as u8,
+    ) {
+        options = options.last_modified_time(modified);
+    }
+    zip.start_file("manifest.txt", options)
+        .map_err(std::io::Error::from)?;
+    zip.write_all(manifest.as_bytes())?;
+    for (name, _, contents) in &files {
+        zip.start_file(*name, options)
+            .map_err(std::io::Error::from)?;
+        zip.write_all(contents)?;
+    }
+    zip.finish().map_err(std::io::Error::from)?;
+    Ok(())
+}
+
+/// Appends the current metrics to `path` using the `export_metrics_csv` schema,
+/// writing the header only when the file is new or empty.
+pub fn append_metrics_csv(store: &MetricsStore, path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
 
-    writeln!(file, "metric_name,value,unit,timestamp")?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", CSV_HEADER)?;
+    }
+    write_csv_rows(&mut file, store)?;
+    file.flush()?;
+
+    Ok(())
+}
 
+fn write_csv_rows(file: &mut impl Write, store: &MetricsStore) -> Result<()> {
     let ts = Local::now().to_rfc3339();
 
-    if let Some(heap) = store.heap_history.iter().last() {
+    if let Some(heap) = store.heap_history.latest() {
         writeln!(file, "heap_used,{},bytes,{}", heap.used_bytes, ts)?;
         writeln!(file, "heap_max,{},bytes,{}", heap.max_bytes, ts)?;
         writeln!(file, "heap_committed,{},bytes,{}", heap.committed_bytes, ts)?;
-        let usage_percent = (heap.used_bytes as f64 / heap.max_bytes as f64) * 100.0;
+        let usage_percent = if heap.max_bytes > 0 {
+            (heap.used_bytes as f64 / heap.max_bytes as f64) * 100.0
+        } else {
+            0.0
+        };
         writeln!(
             file,
             "heap_usage_percent,{:.2},percent,{}",
@@ -277,24 +925,26 @@ pub fn export_metrics_csv(store: &MetricsStore, base_dir: Option<&str>) -> Resul
         )?;
     }
 
-    if let Some(gc) = store.gc_history.iter().last() {
-        writeln!(file, "young_gc_count,{},count,{}", gc.young_gc_count, ts)?;
-        writeln!(file, "old_gc_count,{},count,{}", gc.old_gc_count, ts)?;
+    if let Some(gc) = store.gc_history.latest() {
+        writeln!(file, "young_gc_count,{},count,{}", gc.young_gc_count(), ts)?;
+        writeln!(file, "old_gc_count,{},count,{}", gc.old_gc_count(), ts)?;
         writeln!(
             file,
             "young_gc_time_ms,{},milliseconds,{}",
-            gc.young_gc_time_ms, ts
+            gc.young_gc_time_ms(),
+            ts
         )?;
         writeln!(
             file,
             "old_gc_time_ms,{},milliseconds,{}",
-            gc.old_gc_time_ms, ts
+            gc.old_gc_time_ms(),
+            ts
         )?;
     }
 
-    if let Some(heap) = store.heap_history.iter().last() {
+    if let Some(heap) = store.heap_history.latest() {
         for pool in &heap.pools {
-            let pool_name = pool.name.replace(',', "_");
+            let pool_name = sanitize_metric_name(&pool.name);
             writeln!(
                 file,
                 "pool_{}_used,{},bytes,{}",
@@ -326,7 +976,7 @@ pub fn export_metrics_csv(store: &MetricsStore, base_dir: Option<&str>) -> Resul
         writeln!(
             file,
             "threads_{},{},count,{}",
-            state.to_lowercase(),
+            sanitize_metric_name(state),
             count,
             ts
         )?;
@@ -335,5 +985,530 @@ pub fn export_metrics_csv(store: &MetricsStore, base_dir: Option<&str>) -> Resul
     let total_classes: u64 = store.class_histogram.iter().map(|c| c.instances).sum();
     writeln!(file, "classes_loaded,{},count,{}", total_classes, ts)?;
 
-    Ok(filepath)
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jvm::types::{ClassInfo, GcStats, HeapInfo, MemoryPool, PoolType, ThreadState};
+    use std::collections::HashSet;
+
+    fn temp_export_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("jvm-tui-export-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn sample_store() -> MetricsStore {
+        let mut store = MetricsStore::new(10);
+        store.record_heap(HeapInfo {
+            used_bytes: 256 * 1024 * 1024,
+            max_bytes: 1024 * 1024 * 1024,
+            committed_bytes: 512 * 1024 * 1024,
+            pools: vec![
+                MemoryPool {
+                    name: "G1 Eden Space".to_string(),
+                    pool_type: PoolType::Eden,
+                    used_bytes: 64 * 1024 * 1024,
+                    max_bytes: 0,
+                    committed_bytes: 128 * 1024 * 1024,
+                },
+                MemoryPool {
+                    name: "Metaspace".to_string(),
+                    pool_type: PoolType::Metaspace,
+                    used_bytes: 48 * 1024 * 1024,
+                    max_bytes: 256 * 1024 * 1024,
+                    committed_bytes: 50 * 1024 * 1024,
+                },
+            ],
+            timestamp: Local::now(),
+        });
+        store.record_gc(GcStats::from_generations(42, 1234, 2, 567, Local::now()));
+        store.record_threads(vec![
+            ThreadInfo {
+                id: 1,
+                name: "main".to_string(),
+                state: ThreadState::Runnable,
+                stack_trace: vec![],
+                cpu_time_ns: None,
+                daemon: None,
+                locks: Vec::new(),
+            },
+            ThreadInfo {
+                id: 2,
+                name: "worker-1".to_string(),
+                state: ThreadState::Waiting,
+                stack_trace: vec![],
+                cpu_time_ns: None,
+                daemon: None,
+                locks: Vec::new(),
+            },
+        ]);
+        store.record_class_histogram(vec![ClassInfo {
+            rank: 1,
+            instances: 1000,
+            bytes: 64000,
+            name: "[B".to_string(),
+        }]);
+        store
+    }
+
+    fn sample_jvm_info() -> JvmInfo {
+        JvmInfo {
+            pid: 4242,
+            main_class: "com.example.OrderService".to_string(),
+            version: "17.0.2".to_string(),
+            uptime_seconds: 60,
+            vm_flags: Vec::new(),
+            container_memory_limit: None,
+        }
+    }
+
+    #[test]
+    fn test_filename_template() {
+        let info = sample_jvm_info();
+        let target = ExportTarget::new(None)
+            .with_filename_template(Some("{main_class}/{pid}-{kind}.{format}"))
+            .with_jvm_info(Some(&info));
+        assert_eq!(
+            target.filename("metrics", "csv"),
+            "com.example.OrderService_4242-metrics.csv"
+        );
+
+        let default = ExportTarget::new(None).filename("report", "md");
+        assert!(default.starts_with("report_20"));
+        assert!(default.ends_with(".md"));
+
+        // Without a connected JVM the JVM placeholders still expand
+        let target = ExportTarget::new(None).with_filename_template(Some("{pid}_{main_class}"));
+        assert_eq!(target.filename("metrics", "json"), "unknown_unknown.json");
+
+        let target = ExportTarget::new(None).with_filename_template(Some("../.."));
+        assert_eq!(target.filename("metrics", "json"), ".._...json");
+        let target = ExportTarget::new(None).with_filename_template(Some(".."));
+        assert_eq!(target.filename("metrics", "json"), "metrics.json");
+    }
+
+    #[test]
+    fn test_templated_export_stays_in_directory() {
+        let dir = temp_export_dir("template");
+        let info = JvmInfo {
+            main_class: "/opt/app/service.jar".to_string(),
+            ..sample_jvm_info()
+        };
+        let target = ExportTarget::new(dir.to_str())
+            .with_filename_template(Some("{main_class}-{pid}"))
+            .with_jvm_info(Some(&info));
+        let path = export_metrics_json(&sample_store(), &target).unwrap();
+
+        assert_eq!(path.parent().unwrap(), dir);
+        assert_eq!(path.file_name().unwrap(), "_opt_app_service.jar-4242.json");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_sample_limit_indices() {
+        let latest = SampleLimit {
+            max_samples: 3,
+            sampling: ExportSampling::Latest,
+        };
+        assert_eq!(latest.indices(10), vec![7, 8, 9]);
+        assert_eq!(latest.indices(2), vec![0, 1]);
+
+        let downsample = SampleLimit {
+            max_samples: 4,
+            sampling: ExportSampling::Downsample,
+        };
+        assert_eq!(downsample.indices(10), vec![0, 3, 6, 9]);
+        assert_eq!(downsample.indices(4), vec![0, 1, 2, 3]);
+
+        let single = SampleLimit {
+            max_samples: 1,
+            sampling: ExportSampling::Downsample,
+        };
+        assert_eq!(single.indices(5), vec![4]);
+    }
+
+    #[test]
+    fn test_export_json_honours_sample_limit() {
+        let mut store = MetricsStore::new(100);
+        for used_mb in 0..50 {
+            store.record_heap(HeapInfo {
+                used_bytes: used_mb,
+                max_bytes: 100,
+                committed_bytes: 100,
+                pools: Vec::new(),
+                timestamp: Local::now(),
+            });
+        }
+
+        let dir = temp_export_dir("sample-limit");
+        let target = ExportTarget::new(dir.to_str()).with_sample_limit(Some(SampleLimit {
+            max_samples: 5,
+            sampling: ExportSampling::Downsample,
+        }));
+        let path = export_metrics_json(&store, &target).unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        let used: Vec<u64> = value["heap_history"]["buffer"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|heap| heap["used_bytes"].as_u64().unwrap())
+            .collect();
+        assert_eq!(used, vec![0, 12, 24, 36, 49]);
+        // The live store is untouched
+        assert_eq!(store.heap_history.len(), 50);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_json_round_trips() {
+        let dir = temp_export_dir("json");
+        let path = export_metrics_json(&sample_store(), &ExportTarget::new(dir.to_str())).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&content).unwrap();
+
+        assert_eq!(path.extension().unwrap(), "json");
+        assert_eq!(
+            value["heap_history"]["buffer"][0]["used_bytes"],
+            256 * 1024 * 1024
+        );
+        assert_eq!(value["gc_history"]["buffer"][0]["young_gc_count"], 42);
+        assert_eq!(value["thread_snapshot"].as_array().unwrap().len(), 2);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_write_stream_sample() {
+        let store = sample_store();
+        let mut buffer = Vec::new();
+        write_stream_sample(&store, &mut buffer).unwrap();
+        write_stream_sample(&store, &mut buffer).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert_eq!(output.lines().count(), 2);
+
+        let value: serde_json::Value =
+            serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(value["heap"]["used_bytes"], 256 * 1024 * 1024);
+        assert_eq!(value["gc"]["young_gc_count"], 42);
+        assert_eq!(value["threads"], 2);
+        assert_eq!(value["thread_states"]["waiting"], 1);
+        assert!(value["cpu_percent"].is_null());
+        assert!(value["timestamp"].is_string());
+
+        let empty_store = MetricsStore::new(1);
+        let empty = StreamSample::from_store(&empty_store);
+        assert!(empty.heap.is_none());
+        assert_eq!(empty.threads, 0);
+    }
+
+    #[test]
+    fn test_write_metrics_matches_file_export() {
+        let store = sample_store();
+        let dir = temp_export_dir("writer");
+        let path = export_metrics_json(&store, &ExportTarget::new(dir.to_str())).unwrap();
+
+        let mut buffer = Vec::new();
+        write_metrics_json(&store, &mut buffer).unwrap();
+        assert_eq!(buffer, std::fs::read(&path).unwrap());
+
+        let mut buffer = Vec::new();
+        write_metrics_csv(&store, &mut buffer).unwrap();
+        let csv = String::from_utf8(buffer).unwrap();
+        assert_eq!(csv.lines().next(), Some(CSV_HEADER));
+
+        // Identical apart from the generation timestamp
+        let path = export_metrics_prometheus(&store, &ExportTarget::new(dir.to_str())).unwrap();
+        let without_timestamp = |text: &str| {
+            text.lines()
+                .filter(|l| !l.starts_with("# Generated"))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            without_timestamp(&std::fs::read_to_string(&path).unwrap()),
+            without_timestamp(&render_metrics_prometheus(&store).unwrap())
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_report_markdown() {
+        let dir = temp_export_dir("markdown");
+        let path =
+            export_report_markdown(&sample_store(), &ExportTarget::new(dir.to_str())).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+
+        assert_eq!(path.extension().unwrap(), "md");
+        assert!(content.starts_with("## JVM Report"));
+        assert_eq!(content.matches("```").count(), 2);
+        assert!(content.contains("Heap:       256.0 MB / 1024.0 MB"));
+        assert!(content.contains("| Young | 42 | 1.23s | 29.38ms |"));
+        assert!(content.contains("| Runnable | 1 |"));
+        assert!(content.contains("| 1 | `[B` | 1000 | 0.1 MB |"));
+
+        // Every table row has as many cells as its header
+        for table in content.split("\n\n").filter(|block| block.starts_with('|')) {
+            let widths: HashSet<usize> = table
+                .lines()
+                .map(|row| row.matches(" | ").count())
+                .collect();
+            assert_eq!(widths.len(), 1, "ragged table:\n{}", table);
+        }
+
+        assert_eq!(escape_markdown_cell("a|b"), "a\\|b");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_prometheus_is_well_formed() {
+        let content = render_metrics_prometheus(&sample_store()).unwrap();
+
+        let mut typed = HashSet::new();
+        for line in content.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                let name = rest.split_whitespace().next().unwrap().to_string();
+                assert!(typed.insert(name), "duplicate TYPE line: {}", line);
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(typed.contains(name), "sample without TYPE: {}", line);
+
+            let value = line.rsplit(' ').next().unwrap();
+            let value: f64 = value.parse().unwrap();
+            assert!(value.is_finite(), "non-finite value: {}", line);
+        }
+
+        assert!(content.contains("jvm_memory_heap_used_bytes 268435456"));
+        assert!(content.contains("jvm_gc_collections_total{gc=\"young\"} 42"));
+    }
+
+    #[test]
+    fn test_sanitize_metric_name() {
+        assert_eq!(sanitize_metric_name("G1 Eden Space"), "g1_eden_space");
+        assert_eq!(
+            sanitize_metric_name("CodeHeap 'non-nmethods'"),
+            "codeheap__non_nmethods_"
+        );
+        assert_eq!(sanitize_metric_name("a,b.c/d"), "a_b_c_d");
+        assert_eq!(sanitize_metric_name("TimedWaiting"), "timedwaiting");
+    }
+
+    #[test]
+    fn test_escape_label_value() {
+        assert_eq!(escape_label_value("G1 Eden Space"), "G1 Eden Space");
+        assert_eq!(
+            escape_label_value("Weird \"Pool\"\\Name\nX"),
+            "Weird \\\"Pool\\\"\\\\Name\\nX"
+        );
+    }
+
+    #[test]
+    fn test_export_prometheus_escapes_pool_names() {
+        let mut store = sample_store();
+        store.record_heap(HeapInfo {
+            used_bytes: 1,
+            max_bytes: 2,
+            committed_bytes: 2,
+            pools: vec![MemoryPool {
+                name: "Vendor \"Pool\"\\1\nnext".to_string(),
+                pool_type: PoolType::Other,
+                used_bytes: 1,
+                max_bytes: 2,
+                committed_bytes: 2,
+            }],
+            timestamp: Local::now(),
+        });
+
+        let content = render_metrics_prometheus(&store).unwrap();
+
+        assert!(content
+            .contains("jvm_memory_pool_used_bytes{pool=\"Vendor \\\"Pool\\\"\\\\1\\nnext\"} 1"));
+        assert!(content
+            .lines()
+            .filter(|l| l.starts_with("jvm_memory_pool_"))
+            .all(|l| l.ends_with(" 1") || l.ends_with(" 2")));
+    }
+
+    #[test]
+    fn test_export_csv_has_header_and_consistent_rows() {
+        let dir = temp_export_dir("csv");
+        let path = export_metrics_csv(&sample_store(), &ExportTarget::new(dir.to_str())).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some("metric_name,value,unit,timestamp"));
+
+        for line in lines {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields.len(), 4, "malformed row: {}", line);
+            let value: f64 = fields[1].parse().unwrap();
+            assert!(value.is_finite(), "non-finite value: {}", line);
+        }
+
+        for line in content.lines().skip(1) {
+            let name = line.split(',').next().unwrap();
+            assert_eq!(
+                name,
+                sanitize_metric_name(name),
+                "unsanitized name: {}",
+                name
+            );
+        }
+
+        assert!(content.contains("pool_g1_eden_space_used,"));
+        assert!(content.contains("young_gc_count,42,count,"));
+        assert!(content.contains("heap_usage_percent,25.00,percent,"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_append_metrics_csv_writes_header_once() {
+        let dir = temp_export_dir("csv-log");
+        let path = dir.join("session.csv");
+        let store = sample_store();
+
+        append_metrics_csv(&store, &path).unwrap();
+        let first_len = std::fs::read_to_string(&path).unwrap().lines().count();
+        append_metrics_csv(&store, &path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+
+        assert_eq!(
+            content
+                .lines()
+                .filter(|l| *l == "metric_name,value,unit,timestamp")
+                .count(),
+            1
+        );
+        assert_eq!(content.lines().count(), first_len * 2 - 1);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_thread_dump() {
+        let dir = temp_export_dir("threads");
+        let store = sample_store();
+        let path =
+            export_thread_dump(&store.thread_snapshot, &ExportTarget::new(dir.to_str())).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+
+        assert!(content.starts_with("JVM-TUI Thread Dump"));
+        assert!(content.contains("Total Threads: 2"));
+        assert!(content.contains("Thread #2: \"worker-1\""));
+        assert!(content.ends_with("End of thread dump\n"));
+
+        let mut buffer = Vec::new();
+        write_thread_dump(&store.thread_snapshot, &mut buffer).unwrap();
+        let written = String::from_utf8(buffer).unwrap();
+        assert_eq!(written.lines().count(), content.lines().count());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_plan_heap_dump() {
+        let dir = temp_export_dir("heap-dump");
+        let info = sample_jvm_info();
+        let target = ExportTarget::new(dir.to_str()).with_jvm_info(Some(&info));
+        let plan = plan_heap_dump(512 * 1024 * 1024, &target, true).unwrap();
+
+        assert!(plan.path.is_absolute());
+        assert!(plan.path.starts_with(dir.canonicalize().unwrap()));
+        let filename = plan.path.file_name().unwrap().to_string_lossy();
+        assert!(filename.starts_with("heap_dump_4242_"));
+        assert!(filename.ends_with(".hprof"));
+        assert!(plan.confirmation_message().contains("512.0 MB"));
+
+        let tight = HeapDumpPlan {
+            available_bytes: Some(100 * 1024 * 1024),
+            ..plan
+        };
+        assert!(tight.insufficient_space());
+        assert!(tight.confirmation_message().contains("WARNING"));
+
+        let remote = plan_heap_dump(512 * 1024 * 1024, &target, false).unwrap();
+        assert_eq!(remote.available_bytes, None);
+        assert!(!remote.confirmation_message().contains("free"));
+    }
+
+    #[test]
+    fn test_format_thread_matches_dump_section() {
+        let store = sample_store();
+        let thread = &store.thread_snapshot[1];
+        let single = format_thread(thread);
+
+        assert!(single.starts_with("Thread #2: \"worker-1\"\n  State: "));
+        assert!(format_thread_dump(&store.thread_snapshot).contains(&single));
+    }
+
+    #[test]
+    fn test_support_bundle_contents() {
+        use std::io::Read;
+
+        let store = sample_store();
+        let mut jvm_info = sample_jvm_info();
+        jvm_info.vm_flags = vec!["-XX:+UseG1GC".to_string(), "-Xmx1g".to_string()];
+        let properties = vec![("java.version".to_string(), "17.0.2".to_string())];
+
+        let mut buffer = std::io::Cursor::new(Vec::new());
+        write_support_bundle(
+            &store,
+            &store.thread_snapshot,
+            &properties,
+            Some(&jvm_info),
+            &mut buffer,
+        )
+        .unwrap();
+
+        let mut archive = zip::ZipArchive::new(buffer).unwrap();
+        let names: Vec<&str> = archive.file_names().collect();
+        for name in [
+            "manifest.txt",
+            "metrics.json",
+            "thread_dump.txt",
+            "vm_flags.txt",
+            "system_properties.txt",
+        ] {
+            assert!(names.contains(&name), "missing {}", name);
+        }
+
+        let read = |archive: &mut zip::ZipArchive<_>, name: &str| {
+            let mut contents = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut contents)
+                .unwrap();
+            contents
+        };
+        let manifest = read(&mut archive, "manifest.txt");
+        assert!(manifest.contains(&format!("jvm-tui {}", env!("CARGO_PKG_VERSION"))));
+        assert!(manifest.contains("PID:          4242"));
+        assert!(read(&mut archive, "thread_dump.txt").contains("\"worker-1\""));
+        assert_eq!(read(&mut archive, "vm_flags.txt"), "-XX:+UseG1GC\n-Xmx1g");
+        assert_eq!(
+            read(&mut archive, "system_properties.txt"),
+            "java.version=17.0.2\n"
+        );
+        let metrics: serde_json::Value =
+            serde_json::from_str(&read(&mut archive, "metrics.json")).unwrap();
+        assert!(metrics.get("heap_history").is_some());
+    }
 }
@@ -0,0 +1,127 @@
+use std::path::{Path, PathBuf};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// cgroup v1 reports "no limit" as LONG_MAX rounded down to a page, so
+/// anything this large is treated as unlimited.
+const V1_UNLIMITED: u64 = 1 << 62;
+
+/// Memory limit of the cgroup `pid` runs in, i.e. what the kernel OOM-kills
+/// it at inside Docker or Kubernetes. `None` when there is no limit, or
+/// when it can't be read (not Linux, or the process is gone).
+pub fn read_cgroup_memory_limit(pid: u32) -> Option<u64> {
+    let membership = std::fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+    cgroup_memory_limit(&membership, |path| std::fs::read_to_string(path).ok())
+}
+
+/// Resolves the limit from `/proc/<pid>/cgroup` contents, reading control
+/// files through `read`. v2 is tried first; on hybrid hosts the memory
+/// controller is still mounted as v1.
+fn cgroup_memory_limit(membership: &str, read: impl Fn(&Path) -> Option<String>) -> Option<u64> {
+    let mut v2_path = None;
+    let mut v1_path = None;
+    for line in membership.lines() {
+        let mut fields = line.splitn(3, ':');
+        let (Some(id), Some(controllers), Some(path)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if id == "0" && controllers.is_empty() {
+            v2_path = Some(path);
+        } else if controllers.split(',').any(|c| c == "memory") {
+            v1_path = Some(path);
+        }
+    }
+
+    let first_readable = |files: Vec<PathBuf>| files.iter().find_map(|file| read(file));
+
+    if let Some(path) = v2_path {
+        // A container with its own cgroup namespace sees itself at the root
+        let candidates = vec![
+            Path::new(CGROUP_ROOT)
+                .join(relative(path))
+                .join("memory.max"),
+            Path::new(CGROUP_ROOT).join("memory.max"),
+        ];
+        if let Some(contents) = first_readable(candidates) {
+            return parse_memory_limit(&contents);
+        }
+    }
+
+    let path = v1_path?;
+    let controller = Path::new(CGROUP_ROOT).join("memory");
+    let candidates = vec![
+        controller
+            .join(relative(path))
+            .join("memory.limit_in_bytes"),
+        controller.join("memory.limit_in_bytes"),
+    ];
+    first_readable(candidates).and_then(|contents| parse_memory_limit(&contents))
+}
+
+fn relative(path: &str) -> &str {
+    path.trim_start_matches('/')
+}
+
+/// `memory.max` / `memory.limit_in_bytes` contents; `max` means unlimited.
+fn parse_memory_limit(contents: &str) -> Option<u64> {
+    contents
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|limit| *limit < V1_UNLIMITED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn reader(files: &[(&str, &str)]) -> impl Fn(&Path) -> Option<String> {
+        let files: HashMap<PathBuf, String> = files
+            .iter()
+            .map(|(path, contents)| (PathBuf::from(path), contents.to_string()))
+            .collect();
+        move |path| files.get(path).cloned()
+    }
+
+    #[test]
+    fn test_cgroup_v2_limit() {
+        let membership = "0::/kubepods/pod1/container1\n";
+        let read = reader(&[(
+            "/sys/fs/cgroup/kubepods/pod1/container1/memory.max",
+            "536870912\n",
+        )]);
+        assert_eq!(cgroup_memory_limit(membership, read), Some(536870912));
+
+        // Namespaced: the container's cgroup is mounted at the root
+        let read = reader(&[("/sys/fs/cgroup/memory.max", "268435456\n")]);
+        assert_eq!(cgroup_memory_limit("0::/\n", read), Some(268435456));
+
+        let read = reader(&[("/sys/fs/cgroup/memory.max", "max\n")]);
+        assert_eq!(cgroup_memory_limit("0::/\n", read), None);
+    }
+
+    #[test]
+    fn test_cgroup_v1_limit() {
+        let membership = "5:cpuacct,cpu:/docker/abc\n4:memory:/docker/abc\n0::/\n";
+        let read = reader(&[(
+            "/sys/fs/cgroup/memory/docker/abc/memory.limit_in_bytes",
+            "1073741824\n",
+        )]);
+        assert_eq!(cgroup_memory_limit(membership, read), Some(1073741824));
+
+        let read = reader(&[(
+            "/sys/fs/cgroup/memory/docker/abc/memory.limit_in_bytes",
+            "9223372036854771712\n",
+        )]);
+        assert_eq!(cgroup_memory_limit(membership, read), None);
+    }
+
+    #[test]
+    fn test_no_cgroup() {
+        assert_eq!(cgroup_memory_limit("", reader(&[])), None);
+        assert_eq!(cgroup_memory_limit("0::/user.slice\n", reader(&[])), None);
+    }
+}
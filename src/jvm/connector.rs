@@ -1,6 +1,12 @@
 use crate::error::Result;
-use crate::jvm::types::{ClassInfo, GcStats, HeapInfo, JvmInfo, ThreadInfo};
+use crate::jvm::raw_output::RawOutput;
+use crate::jvm::types::{
+    ClassInfo, CompilerStats, GcPause, GcStats, HeapInfo, HumongousStats, JvmInfo, NativeMemory,
+    ThreadInfo,
+};
 use async_trait::async_trait;
+use std::collections::HashMap;
+use std::path::Path;
 
 #[async_trait]
 pub trait JvmConnector: Send + Sync {
@@ -22,5 +28,329 @@ pub trait JvmConnector: Send + Sync {
 
     async fn get_class_histogram(&self) -> Result<Vec<ClassInfo>>;
 
+    /// IDs of threads the JVM reports as deadlocked.
+    async fn get_deadlocked_threads(&self) -> Result<Vec<u64>> {
+        Ok(Vec::new())
+    }
+
+    /// Threads and the IDs of the deadlocked ones. Connectors that read both
+    /// from one thread dump override this so a poll takes the dump once.
+    async fn get_threads_and_deadlocks(&self) -> Result<(Vec<ThreadInfo>, Vec<u64>)> {
+        let threads = self.get_thread_info().await?;
+        let deadlocked = self.get_deadlocked_threads().await.unwrap_or_default();
+        Ok((threads, deadlocked))
+    }
+
+    /// Cumulative CPU time per thread ID, in nanoseconds. Only consulted when
+    /// `get_thread_info` left every thread's `cpu_time_ns` unset.
+    async fn get_thread_cpu_times(&self) -> Result<HashMap<u64, u64>> {
+        Ok(HashMap::new())
+    }
+
+    /// Recent GC pauses, oldest first. Empty when the connector has no pause source.
+    async fn get_recent_gc_pauses(&self) -> Result<Vec<GcPause>> {
+        Ok(Vec::new())
+    }
+
+    /// G1 humongous region usage; `None` when the JVM isn't running G1.
+    async fn get_humongous_stats(&self) -> Result<Option<HumongousStats>> {
+        Ok(None)
+    }
+
+    /// Native Memory Tracking summary, or `Disabled` when NMT is off.
+    async fn get_native_memory(&self) -> Result<NativeMemory> {
+        Err(crate::error::AppError::Connection(
+            "Native memory tracking not supported by this connector".to_string(),
+        ))
+    }
+
+    /// JIT compilation counts and code cache usage.
+    async fn get_compiler_stats(&self) -> Result<CompilerStats> {
+        Err(crate::error::AppError::Connection(
+            "Compiler stats are not supported for this connection".to_string(),
+        ))
+    }
+
+    /// Numeric hsperfdata counters (`jcmd PerfCounter.print`), keyed by name.
+    async fn get_perf_counters(&self) -> Result<HashMap<String, i64>> {
+        Err(crate::error::AppError::Connection(
+            "Performance counters are not supported for this connection".to_string(),
+        ))
+    }
+
+    /// [`get_compiler_stats`](Self::get_compiler_stats) from counters already
+    /// read with `get_perf_counters`, sparing its tool calls. `None` falls
+    /// back to `get_compiler_stats`.
+    async fn compiler_stats_from_counters(
+        &self,
+        _counters: &HashMap<String, i64>,
+    ) -> Option<CompilerStats> {
+        None
+    }
+
     async fn trigger_gc(&self) -> Result<()>;
+
+    /// How the JVM was started: its arguments, main class and program arguments.
+    async fn get_process_command_line(&self) -> Result<String> {
+        Err(crate::error::AppError::Connection(
+            "The process command line is not supported for this connection".to_string(),
+        ))
+    }
+
+    /// JVM system properties as `(key, value)` pairs, sorted by key.
+    async fn get_system_properties(&self) -> Result<Vec<(String, String)>> {
+        Err(crate::error::AppError::Connection(
+            "System properties are not supported for this connection".to_string(),
+        ))
+    }
+
+    /// Runs a jcmd diagnostic command, already checked with
+    /// [`sanitize_diagnostic_command`], and returns its raw output.
+    async fn run_diagnostic(&self, _command: &str) -> Result<String> {
+        Err(crate::error::AppError::Connection(
+            "Diagnostic commands are not supported for this connection".to_string(),
+        ))
+    }
+
+    /// Writes an hprof heap dump to `path` on the machine running the target JVM.
+    async fn dump_heap(&self, _path: &Path) -> Result<()> {
+        Err(crate::error::AppError::Connection(
+            "Heap dumps are not supported for this connection".to_string(),
+        ))
+    }
+
+    /// Whether [`dump_heap`](Self::dump_heap) works here, checked before
+    /// asking to confirm one.
+    fn supports_heap_dump(&self) -> bool {
+        false
+    }
+
+    /// Whether the JVM runs on this machine, so our disk is the one it
+    /// writes to.
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    /// Starts keeping what each tool run or request returned, before parsing
+    /// (`--debug-raw`).
+    fn capture_raw_output(&mut self) {}
+
+    /// Latest raw output per command, most recent first; `None` when capture
+    /// is off or the connector keeps none.
+    fn last_raw_output(&self) -> Option<Vec<RawOutput>> {
+        None
+    }
+}
+
+/// The main class or jar and program arguments the launcher recorded in
+/// `sun.java.command`, for when the full command line isn't available.
+pub fn java_command(properties: &[(String, String)]) -> Option<&str> {
+    properties
+        .iter()
+        .find(|(key, _)| key == "sun.java.command")
+        .map(|(_, value)| value.as_str())
+        .filter(|value| !value.is_empty())
+}
+
+/// `PerfCounter.print` prints nothing numeric when the JVM runs with
+/// `-XX:-UsePerfData`; report that rather than an all-zero reading.
+pub fn non_empty_counters(counters: HashMap<String, i64>) -> Result<HashMap<String, i64>> {
+    if counters.is_empty() {
+        return Err(crate::error::AppError::Parse(
+            "No performance counters (is the JVM running with -XX:-UsePerfData?)".to_string(),
+        ));
+    }
+    Ok(counters)
+}
+
+/// Diagnostic commands the palette refuses, with what to do instead. A
+/// trailing `.*` blocks a whole command family, and a second word blocks the
+/// command only when one of its arguments starts with it.
+const BLOCKED_DIAGNOSTIC_COMMANDS: [(&str, &str); 14] = [
+    ("GC.run", "press 'g' to trigger a GC"),
+    ("GC.heap_dump", "press 'D' to write a heap dump"),
+    ("JVMTI.agent_load", "loading agents is not allowed"),
+    ("VM.set_flag", "changing VM flags is not allowed"),
+    ("VM.log", "changing JVM logging is not allowed"),
+    (
+        "ManagementAgent.*",
+        "starting or stopping the management agent is not allowed",
+    ),
+    ("JFR.start", "starting flight recordings is not allowed"),
+    (
+        "JFR.dump filename=",
+        "writing recordings to files is not allowed",
+    ),
+    (
+        "JFR.stop filename=",
+        "writing recordings to files is not allowed",
+    ),
+    (
+        "Compiler.directives_add",
+        "changing compiler directives is not allowed",
+    ),
+    (
+        "Compiler.directives_remove",
+        "changing compiler directives is not allowed",
+    ),
+    (
+        "Compiler.directives_clear",
+        "changing compiler directives is not allowed",
+    ),
+    (
+        "System.dump_map",
+        "writing files on the JVM host is not allowed",
+    ),
+    (
+        "Thread.dump_to_file",
+        "press 'e' on the Threads tab to export a thread dump",
+    ),
+];
+
+/// Whether `words` (command name first) match a
+/// [`BLOCKED_DIAGNOSTIC_COMMANDS`] entry.
+fn is_blocked(entry: &str, words: &[&str]) -> bool {
+    let (command, argument) = match entry.split_once(' ') {
+        Some((command, argument)) => (command, Some(argument)),
+        None => (entry, None),
+    };
+    let name = words[0].to_ascii_lowercase();
+    let command = command.to_ascii_lowercase();
+    let name_matches = match command.strip_suffix('*') {
+        Some(family) => name.starts_with(family),
+        None => name == command,
+    };
+    name_matches
+        && argument.map_or(true, |argument| {
+            let argument = argument.to_ascii_lowercase();
+            words[1..]
+                .iter()
+                .any(|word| word.to_ascii_lowercase().starts_with(&argument))
+        })
+}
+
+/// Everything jcmd arguments need (names, options, `key=value`, paths).
+/// Anything else could be expanded or split by the remote shell over SSH.
+fn is_allowed_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "._=,:/-".contains(c)
+}
+
+/// Checks a command typed into the palette and strips a leading `jcmd` and
+/// PID, so it always goes to the connected JVM.
+pub fn sanitize_diagnostic_command(input: &str, pid: u32) -> Result<String> {
+    let invalid = |message: String| crate::error::AppError::InvalidInput(message);
+
+    if let Some(c) = input
+        .chars()
+        .find(|c| !c.is_ascii_whitespace() && !is_allowed_char(*c))
+    {
+        return Err(invalid(format!(
+            "'{}' is not allowed in diagnostic commands",
+            c
+        )));
+    }
+
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    if words.first() == Some(&"jcmd") {
+        words.remove(0);
+    }
+    if let Some(target) = words.first().and_then(|word| word.parse::<u32>().ok()) {
+        if target != pid {
+            return Err(invalid(format!(
+                "Commands run against the connected JVM (PID {}), not {}",
+                pid, target
+            )));
+        }
+        words.remove(0);
+    }
+
+    let name = words
+        .first()
+        .ok_or_else(|| invalid("Type a jcmd command, e.g. Thread.print or help".to_string()))?;
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '_')
+    {
+        return Err(invalid(format!("'{}' is not a jcmd command", name)));
+    }
+    if let Some((blocked, hint)) = BLOCKED_DIAGNOSTIC_COMMANDS
+        .iter()
+        .find(|(blocked, _)| is_blocked(blocked, &words))
+    {
+        return Err(invalid(format!("{} is blocked here: {}", blocked, hint)));
+    }
+
+    Ok(words.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_diagnostic_command() {
+        assert_eq!(
+            sanitize_diagnostic_command("  Thread.print -l ", 42).unwrap(),
+            "Thread.print -l"
+        );
+        assert_eq!(
+            sanitize_diagnostic_command("jcmd 42 VM.system_properties", 42).unwrap(),
+            "VM.system_properties"
+        );
+
+        assert!(sanitize_diagnostic_command("jcmd 7 help", 42).is_err());
+        assert!(sanitize_diagnostic_command("", 42).is_err());
+        assert!(sanitize_diagnostic_command("help; rm -rf /", 42).is_err());
+        assert!(sanitize_diagnostic_command("VM.version $(id)", 42).is_err());
+        for input in [
+            "GC.class_histogram *",
+            "VM.flags ~/x",
+            "Thread.print -l #",
+            "VM.system_properties {a,b}",
+            "VM.flags [ab]?",
+        ] {
+            assert!(
+                matches!(
+                    sanitize_diagnostic_command(input, 42),
+                    Err(crate::error::AppError::InvalidInput(_))
+                ),
+                "{} should be rejected",
+                input
+            );
+        }
+        assert_eq!(
+            sanitize_diagnostic_command("VM.native_memory\tsummary", 42).unwrap(),
+            "VM.native_memory summary"
+        );
+        assert!(sanitize_diagnostic_command("gc.heap_dump /tmp/x.hprof", 42)
+            .unwrap_err()
+            .to_string()
+            .contains("'D'"));
+    }
+
+    #[test]
+    fn test_state_changing_commands_are_blocked() {
+        for command in [
+            "ManagementAgent.start jmxremote.port=9999",
+            "managementagent.start_local",
+            "JFR.start duration=60s",
+            "JFR.dump name=1 filename=/tmp/x.jfr",
+            "JFR.stop name=1 FILENAME=/tmp/x.jfr",
+            "Compiler.directives_add /tmp/directives.json",
+            "System.dump_map -F=/tmp/map",
+            "VM.log output=/tmp/gc.log what=gc",
+            "Thread.dump_to_file /tmp/threads.txt",
+        ] {
+            assert!(
+                sanitize_diagnostic_command(command, 42).is_err(),
+                "{} should be blocked",
+                command
+            );
+        }
+
+        // Reading recordings and directives stays allowed
+        for command in ["JFR.check", "JFR.dump name=1", "Compiler.directives_print"] {
+            assert_eq!(sanitize_diagnostic_command(command, 42).unwrap(), command);
+        }
+    }
 }
@@ -1,7 +1,8 @@
 use crate::error::Result;
 use crate::jvm::jdk_tools::detector::{JdkToolsStatus, ToolStatus};
 use crate::jvm::jdk_tools::executor::execute_command;
-use std::path::PathBuf;
+use regex::Regex;
+use std::path::Path;
 
 #[derive(Debug, Clone)]
 pub struct DiscoveredJvm {
@@ -9,6 +10,14 @@ pub struct DiscoveredJvm {
     pub main_class: String,
 }
 
+/// JVMs whose main class (or jar, with its arguments as listed by `jcmd -l`)
+/// matches `pattern` anywhere.
+pub fn filter_by_main_class(jvms: Vec<DiscoveredJvm>, pattern: &Regex) -> Vec<DiscoveredJvm> {
+    jvms.into_iter()
+        .filter(|jvm| pattern.is_match(&jvm.main_class))
+        .collect()
+}
+
 pub async fn discover_local_jvms() -> Result<Vec<DiscoveredJvm>> {
     let status = JdkToolsStatus::detect();
 
@@ -23,7 +32,7 @@ pub async fn discover_local_jvms() -> Result<Vec<DiscoveredJvm>> {
     }
 }
 
-async fn discover_via_jcmd(jcmd_path: &PathBuf) -> Result<Vec<DiscoveredJvm>> {
+async fn discover_via_jcmd(jcmd_path: &Path) -> Result<Vec<DiscoveredJvm>> {
     let output = execute_command(
         jcmd_path.to_str().unwrap(),
         &["-l"],
@@ -35,7 +44,7 @@ async fn discover_via_jcmd(jcmd_path: &PathBuf) -> Result<Vec<DiscoveredJvm>> {
     Ok(parse_jcmd_list(&stdout))
 }
 
-async fn discover_via_jps(jps_path: &PathBuf) -> Result<Vec<DiscoveredJvm>> {
+async fn discover_via_jps(jps_path: &Path) -> Result<Vec<DiscoveredJvm>> {
     let output = execute_command(
         jps_path.to_str().unwrap(),
         &["-l"],
@@ -47,7 +56,7 @@ async fn discover_via_jps(jps_path: &PathBuf) -> Result<Vec<DiscoveredJvm>> {
     Ok(parse_jps_list(&stdout))
 }
 
-fn parse_jcmd_list(output: &str) -> Vec<DiscoveredJvm> {
+pub(crate) fn parse_jcmd_list(output: &str) -> Vec<DiscoveredJvm> {
     output
         .lines()
         .filter_map(|line| {
@@ -143,6 +152,28 @@ mod tests {
         assert_eq!(jvms[1].pid, 67890);
     }
 
+    #[test]
+    fn test_filter_by_main_class() {
+        let jvms = parse_jcmd_list(
+            "100 com.example.OrderService
+200 com.example.billing.InvoiceService --port 8080
+300 org.gradle.launcher.daemon.bootstrap.GradleDaemon 8.5",
+        );
+
+        let pattern = Regex::new("com.example.*Service").unwrap();
+        let pids: Vec<u32> = filter_by_main_class(jvms.clone(), &pattern)
+            .iter()
+            .map(|jvm| jvm.pid)
+            .collect();
+        assert_eq!(pids, vec![100, 200]);
+
+        let pattern = Regex::new("^org\\.gradle").unwrap();
+        assert_eq!(filter_by_main_class(jvms.clone(), &pattern).len(), 1);
+
+        let pattern = Regex::new("Kafka").unwrap();
+        assert!(filter_by_main_class(jvms, &pattern).is_empty());
+    }
+
     #[test]
     fn test_filter_jdk_tools() {
         assert!(should_filter("jdk.jcmd/sun.tools.jcmd.JCmd"));
@@ -0,0 +1,122 @@
+use crate::error::{AppError, Result};
+use crate::jvm::connector::JvmConnector;
+use crate::jvm::discovery::parse_jcmd_list;
+use crate::jvm::jdk_tools::executor::execute_command;
+use crate::jvm::jdk_tools::JdkToolsError;
+use crate::jvm::remote_jdk::connector::{RemoteJdkConnector, ToolTransport};
+use async_trait::async_trait;
+use std::time::Duration;
+
+const DOCKER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs jcmd/jstat inside a container via `docker exec`, so the tools see the
+/// container's PID namespace.
+pub type DockerJdkConnector = RemoteJdkConnector<DockerTransport>;
+
+pub struct DockerTransport {
+    container: String,
+}
+
+impl DockerJdkConnector {
+    pub fn new(container: String, pid: u32) -> Self {
+        Self::from_transport(DockerTransport { container }, pid)
+    }
+
+    /// Resolves the container and its main Java process, returning a connected
+    /// connector.
+    pub async fn attach(container: &str) -> Result<Self> {
+        let pid = resolve_java_pid(container).await?;
+        let mut connector = Self::new(container.to_string(), pid);
+        connector.connect(pid).await?;
+        Ok(connector)
+    }
+}
+
+#[async_trait]
+impl ToolTransport for DockerTransport {
+    async fn run(&self, command: &[&str]) -> Result<String> {
+        docker(&exec_args(&self.container, command)).await
+    }
+
+    fn label(&self) -> String {
+        format!("Container JVM ({})", self.container)
+    }
+}
+
+async fn docker(args: &[&str]) -> Result<String> {
+    let output = execute_command("docker", args, Some(DOCKER_TIMEOUT))
+        .await
+        .map_err(|e| match e {
+            JdkToolsError::ExecutionFailed { ref source, .. }
+                if source.kind() == std::io::ErrorKind::NotFound =>
+            {
+                AppError::Connection("docker is not available in PATH".to_string())
+            }
+            other => AppError::JdkTools(other),
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::Connection(format!(
+            "docker {} failed: {}",
+            args.first().unwrap_or(&""),
+            stderr.trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn exec_args<'a>(container: &'a str, command: &[&'a str]) -> Vec<&'a str> {
+    let mut args = vec!["exec", container];
+    args.extend_from_slice(command);
+    args
+}
+
+/// Finds the PID (inside the container) of the main Java process.
+pub async fn resolve_java_pid(container: &str) -> Result<u32> {
+    let running = docker(&["inspect", "-f", "{{.State.Running}}", container]).await?;
+    if running.trim() != "true" {
+        return Err(AppError::Connection(format!(
+            "Container {} is not running",
+            container
+        )));
+    }
+
+    let output = docker(&exec_args(container, &["jcmd", "-l"])).await?;
+    main_java_pid(&output).ok_or_else(|| {
+        AppError::Connection(format!("No Java process found in container {}", container))
+    })
+}
+
+/// The container entrypoint normally has the lowest PID in `jcmd -l`.
+fn main_java_pid(jcmd_list: &str) -> Option<u32> {
+    parse_jcmd_list(jcmd_list)
+        .into_iter()
+        .map(|jvm| jvm.pid)
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_args() {
+        assert_eq!(
+            exec_args("orders", &["jcmd", "1", "Thread.print"]),
+            vec!["exec", "orders", "jcmd", "1", "Thread.print"]
+        );
+    }
+
+    #[test]
+    fn test_main_java_pid() {
+        let output = "87 jdk.jcmd/sun.tools.jcmd.JCmd -l\n\
+                      42 org.example.Worker\n\
+                      1 /app/orders.jar --spring.profiles.active=prod\n";
+        assert_eq!(main_java_pid(output), Some(1));
+
+        // Only jcmd itself is running
+        assert_eq!(main_java_pid("87 jdk.jcmd/sun.tools.jcmd.JCmd -l\n"), None);
+    }
+}
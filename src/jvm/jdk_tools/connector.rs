@@ -1,30 +1,68 @@
 use crate::error::Result;
-use crate::jvm::connector::JvmConnector;
-use crate::jvm::jdk_tools::detector::{JdkToolsStatus, ToolStatus};
-use crate::jvm::jdk_tools::executor::execute_command;
-use crate::jvm::jdk_tools::parsers::{jcmd, jstat};
-use crate::jvm::types::{ClassInfo, GcStats, HeapInfo, JvmInfo, ThreadInfo};
+use crate::jvm::cgroup::read_cgroup_memory_limit;
+use crate::jvm::connector::{java_command, non_empty_counters, JvmConnector};
+use crate::jvm::jdk_tools::detector::{
+    parse_major_version, version_mismatch_warning, JdkToolsStatus, ToolStatus,
+};
+use crate::jvm::jdk_tools::executor::execute_command_with_env;
+use crate::jvm::jdk_tools::parsers::{gc_log, jcmd, jstat};
+use crate::jvm::jdk_tools::JdkToolsError;
+use crate::jvm::raw_output::{RawOutput, RawOutputCapture};
+use crate::jvm::types::{
+    ClassInfo, CompilerStats, GcPause, GcStats, HeapInfo, HumongousStats, JvmInfo, NativeMemory,
+    PoolType, ThreadInfo, VmVendor,
+};
 use async_trait::async_trait;
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
 use tokio::sync::RwLock;
 
+/// How much of the end of a GC log is read on each poll.
+const GC_LOG_TAIL_BYTES: u64 = 256 * 1024;
+const MAX_GC_PAUSES: usize = 200;
+/// Dumping a large heap takes far longer than the default tool timeout.
+const HEAP_DUMP_TIMEOUT: Duration = Duration::from_secs(600);
+const OPENJ9_UNSUPPORTED: &str = "OpenJ9 detected; heap info unsupported (its jcmd has no \
+     GC.heap_info and its jstat only -class). Attach over Jolokia for heap and GC metrics";
+
 pub struct JdkToolsConnector {
     pid: Option<u32>,
+    /// Kept across `disconnect` so `reconnect` can attach again.
+    last_pid: Option<u32>,
     tools: JdkToolsStatus,
     jcmd_path: Option<PathBuf>,
     jstat_path: Option<PathBuf>,
+    jmap_path: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    gc_log: Option<PathBuf>,
+    raw_output: RawOutputCapture,
     cache: Arc<RwLock<ConnectorCache>>,
 }
 
 struct ConnectorCache {
     jvm_info: Option<JvmInfo>,
+    vendor: VmVendor,
     vm_flags: Option<Vec<String>>,
+    /// Summed generation maximums from `jstat -gccapacity`; fixed for the
+    /// JVM's lifetime, so read once.
+    max_heap_bytes: Option<u64>,
 }
 
 impl JdkToolsConnector {
     pub fn new() -> Self {
-        let tools = JdkToolsStatus::detect();
+        Self::with_tools(JdkToolsStatus::detect())
+    }
+
+    /// Uses the JDK tools from `java_home` and runs them with `JAVA_HOME` set to it.
+    pub fn with_java_home(java_home: PathBuf) -> Self {
+        Self::with_tools(JdkToolsStatus::detect_with_java_home(Some(java_home)))
+    }
+
+    fn with_tools(tools: JdkToolsStatus) -> Self {
         let jcmd_path = if let ToolStatus::Available { path, .. } = &tools.jcmd {
             Some(path.clone())
         } else {
@@ -36,19 +74,53 @@ impl JdkToolsConnector {
             None
         };
 
+        let jmap_path = if let ToolStatus::Available { path, .. } = &tools.jmap {
+            Some(path.clone())
+        } else {
+            None
+        };
+
+        // Keep JAVA_HOME in step with the JDK the tools were picked from
+        let env = tools
+            .java_home
+            .as_ref()
+            .map(|home| vec![("JAVA_HOME".to_string(), home.display().to_string())])
+            .unwrap_or_default();
+
         Self {
             pid: None,
+            last_pid: None,
             tools,
             jcmd_path,
             jstat_path,
+            jmap_path,
+            env,
+            gc_log: None,
+            raw_output: RawOutputCapture::default(),
             cache: Arc::new(RwLock::new(ConnectorCache {
                 jvm_info: None,
+                vendor: VmVendor::HotSpot,
                 vm_flags: None,
+                max_heap_bytes: None,
             })),
         }
     }
 
+    /// Reads GC pauses from a unified GC log (`-Xlog:gc:file=...`) written by the target JVM.
+    pub fn with_gc_log(mut self, path: PathBuf) -> Self {
+        self.gc_log = Some(path);
+        self
+    }
+
     async fn execute_jcmd(&self, command: &str) -> Result<String> {
+        self.execute_jcmd_with_timeout(command, None).await
+    }
+
+    async fn execute_jcmd_with_timeout(
+        &self,
+        command: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String> {
         let pid = self
             .pid
             .ok_or_else(|| crate::error::AppError::Connection("Not connected".to_string()))?;
@@ -58,14 +130,8 @@ impl JdkToolsConnector {
             .as_ref()
             .ok_or_else(|| crate::error::AppError::Connection("jcmd not available".to_string()))?;
 
-        let output = execute_command(
-            jcmd_path.to_str().unwrap(),
-            &[&pid.to_string(), command],
-            None,
-        )
-        .await?;
-
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        self.run_tool(jcmd_path, &[&pid.to_string(), command], timeout)
+            .await
     }
 
     async fn execute_jstat(&self, option: &str) -> Result<String> {
@@ -78,53 +144,217 @@ impl JdkToolsConnector {
             .as_ref()
             .ok_or_else(|| crate::error::AppError::Connection("jstat not available".to_string()))?;
 
-        let output = execute_command(
-            jstat_path.to_str().unwrap(),
-            &[option, &pid.to_string()],
-            None,
-        )
-        .await?;
+        self.run_tool(jstat_path, &[option, &pid.to_string()], None)
+            .await
+    }
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    async fn execute_jmap(&self, option: &str) -> Result<String> {
+        let pid = self
+            .pid
+            .ok_or_else(|| crate::error::AppError::Connection("Not connected".to_string()))?;
+
+        let jmap_path = self
+            .jmap_path
+            .as_ref()
+            .ok_or_else(|| crate::error::AppError::Connection("jmap not available".to_string()))?;
+
+        self.run_tool(jmap_path, &[option, &pid.to_string()], None)
+            .await
     }
-}
 
-impl Default for JdkToolsConnector {
-    fn default() -> Self {
-        Self::new()
+    /// Fills in the code cache from `Compiler.codecache`, which needs JDK 9+;
+    /// the stats are kept as they are without it.
+    async fn read_code_cache(&self, stats: &mut CompilerStats) {
+        if let Ok(output) = self.execute_jcmd("Compiler.codecache").await {
+            if let Ok((used, max, enabled)) = jcmd::parse_code_cache(&output) {
+                stats.code_cache_used_bytes = used;
+                stats.code_cache_max_bytes = max;
+                stats.compilation_enabled = enabled;
+            }
+        }
     }
-}
 
-#[async_trait]
-impl JvmConnector for JdkToolsConnector {
-    async fn connect(&mut self, pid: u32) -> Result<()> {
-        self.tools.validate()?;
-        self.pid = Some(pid);
+    /// Runs a JDK tool with the connector's environment and returns its stdout.
+    async fn run_tool(
+        &self,
+        tool: &Path,
+        args: &[&str],
+        timeout: Option<Duration>,
+    ) -> Result<String> {
+        let tool = tool.to_str().unwrap();
+        let command = format!("{} {}", tool, args.join(" "));
+        let output = execute_command_with_env(tool, args, timeout, &self.env).await?;
+        self.raw_output.record_process(&command, &output);
+
+        // jcmd and jstat exit with 1 once the JVM is gone, printing the
+        // exception and its stack trace after jcmd's "<pid>:" line
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let message = [stderr, stdout]
+                .iter()
+                .find_map(|text| text.lines().find(|line| is_error_line(line)))
+                .map(|line| line.trim_end().to_string())
+                .unwrap_or_else(|| output.status.to_string());
+            return Err(JdkToolsError::Failed { command, message }.into());
+        }
 
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Reads version, uptime and flags of `pid` into the cache.
+    async fn load_jvm_info(&self, pid: u32) -> Result<()> {
+        if self.jcmd_path.is_none() {
+            return self.load_jvm_info_from_jstat(pid).await;
+        }
+
+        // jcmd prints the attach failure instead of a version when the
+        // process is gone or can't be attached to
         let version_output = self.execute_jcmd("VM.version").await?;
-        let version = jcmd::parse_jvm_version(&version_output)
-            .map_err(|e| crate::error::AppError::Parse(e))?;
+        let version = jcmd::parse_jvm_version(&version_output).map_err(|_| {
+            crate::error::AppError::Connection(format!("Could not attach to PID {}", pid))
+        })?;
 
-        let uptime_output = self.execute_jcmd("VM.uptime").await?;
-        let uptime_seconds =
-            jcmd::parse_vm_uptime(&uptime_output).map_err(|e| crate::error::AppError::Parse(e))?;
+        let vendor = jcmd::parse_vm_vendor(&version_output);
 
-        let flags_output = self.execute_jcmd("VM.flags").await?;
-        let vm_flags =
-            jcmd::parse_vm_flags(&flags_output).map_err(|e| crate::error::AppError::Parse(e))?;
+        // OpenJ9's jcmd has neither command
+        let (uptime_seconds, vm_flags) = if vendor == VmVendor::OpenJ9 {
+            (0, Vec::new())
+        } else {
+            let uptime_output = self.execute_jcmd("VM.uptime").await?;
+            let uptime_seconds =
+                jcmd::parse_vm_uptime(&uptime_output).map_err(crate::error::AppError::Parse)?;
+
+            let flags_output = self.execute_jcmd("VM.flags").await?;
+            let vm_flags =
+                jcmd::parse_vm_flags(&flags_output).map_err(crate::error::AppError::Parse)?;
+            (uptime_seconds, vm_flags)
+        };
+
+        let main_class = match self.execute_jcmd("VM.command_line").await {
+            Ok(output) => jcmd::parse_main_class(&output),
+            Err(_) => None,
+        };
 
         let jvm_info = JvmInfo {
             pid,
-            main_class: format!("PID {}", pid),
+            main_class: main_class.unwrap_or_else(|| format!("PID {}", pid)),
             version,
             uptime_seconds,
             vm_flags: vm_flags.clone(),
+            container_memory_limit: read_cgroup_memory_limit(pid),
         };
 
         let mut cache = self.cache.write().await;
         cache.jvm_info = Some(jvm_info);
+        cache.vendor = vendor;
         cache.vm_flags = Some(vm_flags);
+        cache.max_heap_bytes = None;
+
+        Ok(())
+    }
+
+    /// Without jcmd only jstat can tell whether `pid` is a JVM; its version,
+    /// uptime and flags stay unknown.
+    async fn load_jvm_info_from_jstat(&self, pid: u32) -> Result<()> {
+        let output = self.execute_jstat("-gc").await?;
+        jstat::parse_gc_heap(&output).map_err(|_| {
+            crate::error::AppError::Connection(format!("Could not attach to PID {}", pid))
+        })?;
+
+        let jvm_info = JvmInfo {
+            pid,
+            main_class: format!("PID {}", pid),
+            version: "unknown".to_string(),
+            uptime_seconds: 0,
+            vm_flags: Vec::new(),
+            container_memory_limit: read_cgroup_memory_limit(pid),
+        };
+
+        let mut cache = self.cache.write().await;
+        cache.jvm_info = Some(jvm_info);
+        cache.vendor = VmVendor::HotSpot;
+        cache.vm_flags = Some(Vec::new());
+        cache.max_heap_bytes = None;
+
+        Ok(())
+    }
+
+    /// Vendor of the attached JVM, read from `VM.version` on connect.
+    pub async fn vendor(&self) -> VmVendor {
+        self.cache.read().await.vendor
+    }
+
+    /// Why metrics will be missing for this JVM, if they will.
+    pub async fn vendor_warning(&self) -> Option<String> {
+        (self.vendor().await == VmVendor::OpenJ9).then(|| OPENJ9_UNSUPPORTED.to_string())
+    }
+
+    async fn ensure_hotspot(&self) -> Result<()> {
+        match self.vendor().await {
+            VmVendor::HotSpot => Ok(()),
+            VmVendor::OpenJ9 => Err(crate::error::AppError::Unsupported(
+                OPENJ9_UNSUPPORTED.to_string(),
+            )),
+        }
+    }
+
+    /// Maximum heap size per `jstat -gccapacity`, or `None` without jstat.
+    async fn max_heap_bytes(&self) -> Option<u64> {
+        if let Some(max) = self.cache.read().await.max_heap_bytes {
+            return Some(max);
+        }
+
+        let output = self.execute_jstat("-gccapacity").await.ok()?;
+        let max = jstat::parse_gc_capacity(&output).ok()?.max_heap_bytes();
+        self.cache.write().await.max_heap_bytes = Some(max);
+        Some(max)
+    }
+
+    /// Major JDK version of the jcmd in use, asking the launcher JVM when the
+    /// detected version string doesn't carry one.
+    pub async fn jcmd_major_version(&self) -> Option<u32> {
+        if let Some(major) = self.tools.jcmd.major_version() {
+            return Some(major);
+        }
+
+        let jcmd_path = self.jcmd_path.as_ref()?;
+        let output =
+            execute_command_with_env(jcmd_path.to_str()?, &["-J-version"], None, &self.env)
+                .await
+                .ok()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        stderr
+            .lines()
+            .find(|line| line.contains("version"))
+            .and_then(parse_major_version)
+    }
+
+    pub async fn tool_version_warning(&self, target_version: &str) -> Option<String> {
+        let tool_major = self.jcmd_major_version().await?;
+        version_mismatch_warning(tool_major, target_version)
+    }
+}
+
+impl Default for JdkToolsConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl JvmConnector for JdkToolsConnector {
+    async fn connect(&mut self, pid: u32) -> Result<()> {
+        self.tools.validate()?;
+        self.pid = Some(pid);
+
+        // A PID that isn't (or is no longer) a JVM must not look connected
+        if let Err(e) = self.load_jvm_info(pid).await {
+            self.pid = None;
+            return Err(e);
+        }
 
+        self.last_pid = Some(pid);
         Ok(())
     }
 
@@ -132,7 +362,9 @@ impl JvmConnector for JdkToolsConnector {
         self.pid = None;
         let mut cache = self.cache.write().await;
         cache.jvm_info = None;
+        cache.vendor = VmVendor::HotSpot;
         cache.vm_flags = None;
+        cache.max_heap_bytes = None;
         Ok(())
     }
 
@@ -140,15 +372,14 @@ impl JvmConnector for JdkToolsConnector {
         self.pid.is_some()
     }
 
+    /// Re-attaches to the last PID, refreshing the cached JVM info, e.g.
+    /// after the process restarted under the same PID.
     async fn reconnect(&mut self) -> Result<()> {
-        if let Some(pid) = self.pid {
-            self.disconnect().await?;
-            self.connect(pid).await
-        } else {
-            Err(crate::error::AppError::Connection(
-                "No PID to reconnect to".to_string(),
-            ))
-        }
+        let pid = self.pid.or(self.last_pid).ok_or_else(|| {
+            crate::error::AppError::Connection("No PID to reconnect to".to_string())
+        })?;
+        self.disconnect().await?;
+        self.connect(pid).await
     }
 
     async fn get_jvm_info(&self) -> Result<JvmInfo> {
@@ -160,29 +391,241 @@ impl JvmConnector for JdkToolsConnector {
     }
 
     async fn get_heap_info(&self) -> Result<HeapInfo> {
-        let output = self.execute_jcmd("GC.heap_info").await?;
-        jcmd::parse_heap_info(&output).map_err(|e| crate::error::AppError::Parse(e))
+        self.ensure_hotspot().await?;
+        let heap = self.execute_jcmd("GC.heap_info").await.and_then(|output| {
+            jcmd::parse_heap_info(&output).map_err(crate::error::AppError::Parse)
+        });
+        let mut heap = match heap {
+            Ok(heap) => heap,
+            // Without jcmd, or when some collectors and locales print no
+            // total/used line
+            Err(e) => match self.execute_jstat("-gc").await {
+                Ok(output) => {
+                    jstat::parse_gc_heap(&output).map_err(crate::error::AppError::Parse)?
+                }
+                Err(_) => return Err(e),
+            },
+        };
+
+        // GC.heap_info's "total" is only the committed size on collectors
+        // such as Serial and Parallel, which would pin the gauge at 100%
+        if let Some(max) = self.max_heap_bytes().await {
+            heap.max_bytes = heap.max_bytes.max(max);
+        }
+
+        // GC.heap_info breaks out only Metaspace; one jstat -gc has the
+        // generations (and the jstat fallback above already added them)
+        let has_generations = heap.pools.iter().any(|pool| {
+            matches!(
+                pool.pool_type,
+                PoolType::Eden | PoolType::Survivor | PoolType::Old
+            )
+        });
+        if !has_generations {
+            if let Ok(output) = self.execute_jstat("-gc").await {
+                if let Ok(mut pools) = jstat::parse_gc_generations(&output) {
+                    pools.append(&mut heap.pools);
+                    heap.pools = pools;
+                }
+            }
+        }
+
+        Ok(heap)
     }
 
     async fn get_gc_stats(&self) -> Result<GcStats> {
+        self.ensure_hotspot().await?;
         let output = self.execute_jstat("-gcutil").await?;
-        jstat::parse_gc_stats(&output).map_err(|e| crate::error::AppError::Parse(e))
+        jstat::parse_gc_stats(&output).map_err(crate::error::AppError::Parse)
     }
 
     async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
         let output = self.execute_jcmd("Thread.print").await?;
-        jcmd::parse_thread_dump(&output).map_err(|e| crate::error::AppError::Parse(e))
+        jcmd::parse_thread_dump(&output).map_err(crate::error::AppError::Parse)
     }
 
     async fn get_class_histogram(&self) -> Result<Vec<ClassInfo>> {
-        let output = self.execute_jcmd("GC.class_histogram").await?;
-        jcmd::parse_class_histogram(&output).map_err(|e| crate::error::AppError::Parse(e))
+        // jmap -histo:live prints the same table layout as GC.class_histogram
+        let output = if self.jcmd_path.is_some() {
+            self.execute_jcmd("GC.class_histogram").await?
+        } else {
+            self.execute_jmap("-histo:live").await?
+        };
+        jcmd::parse_class_histogram(&output).map_err(crate::error::AppError::Parse)
+    }
+
+    async fn get_deadlocked_threads(&self) -> Result<Vec<u64>> {
+        Ok(self.get_threads_and_deadlocks().await?.1)
+    }
+
+    async fn get_threads_and_deadlocks(&self) -> Result<(Vec<ThreadInfo>, Vec<u64>)> {
+        let output = self.execute_jcmd("Thread.print").await?;
+        jcmd::parse_thread_print(&output).map_err(crate::error::AppError::Parse)
+    }
+
+    async fn get_recent_gc_pauses(&self) -> Result<Vec<GcPause>> {
+        match &self.gc_log {
+            Some(path) => read_gc_log_tail(path).await,
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn get_humongous_stats(&self) -> Result<Option<HumongousStats>> {
+        // VM.info is large; only ask when the region table will be there
+        let uses_g1 = self
+            .cache
+            .read()
+            .await
+            .vm_flags
+            .as_ref()
+            .is_some_and(|flags| flags.iter().any(|flag| flag == "-XX:+UseG1GC"));
+        if !uses_g1 {
+            return Ok(None);
+        }
+
+        let output = self.execute_jcmd("VM.info").await?;
+        Ok(jcmd::parse_humongous_regions(&output))
+    }
+
+    async fn get_process_command_line(&self) -> Result<String> {
+        let output = self.execute_jcmd("VM.command_line").await?;
+        match jcmd::parse_command_line(&output) {
+            Ok(command_line) => Ok(command_line),
+            Err(e) => java_command(&self.get_system_properties().await?)
+                .map(str::to_string)
+                .ok_or(crate::error::AppError::Parse(e)),
+        }
+    }
+
+    async fn get_system_properties(&self) -> Result<Vec<(String, String)>> {
+        let output = self.execute_jcmd("VM.system_properties").await?;
+        Ok(jcmd::parse_system_properties(&output))
+    }
+
+    async fn run_diagnostic(&self, command: &str) -> Result<String> {
+        self.execute_jcmd(command).await
+    }
+
+    async fn get_native_memory(&self) -> Result<NativeMemory> {
+        let output = self.execute_jcmd("VM.native_memory summary").await?;
+        jcmd::parse_native_memory(&output).map_err(crate::error::AppError::Parse)
+    }
+
+    async fn get_compiler_stats(&self) -> Result<CompilerStats> {
+        let output = self.execute_jstat("-compiler").await?;
+        let mut stats =
+            jstat::parse_compiler_stats(&output).map_err(crate::error::AppError::Parse)?;
+        self.read_code_cache(&mut stats).await;
+        Ok(stats)
+    }
+
+    async fn get_perf_counters(&self) -> Result<HashMap<String, i64>> {
+        let output = self.execute_jcmd("PerfCounter.print").await?;
+        non_empty_counters(jcmd::parse_perf_counters(&output))
+    }
+
+    async fn compiler_stats_from_counters(
+        &self,
+        counters: &HashMap<String, i64>,
+    ) -> Option<CompilerStats> {
+        let code_cache_max_bytes = self
+            .cache
+            .read()
+            .await
+            .vm_flags
+            .as_ref()
+            .and_then(|flags| {
+                flags.iter().find_map(|flag| {
+                    flag.strip_prefix("-XX:ReservedCodeCacheSize=")?
+                        .parse()
+                        .ok()
+                })
+            })
+            .unwrap_or(0);
+        let mut stats = CompilerStats::from_perf_counters(counters, code_cache_max_bytes)?;
+
+        // Only a nearly full code cache is worth asking whether compilation stopped
+        if stats.code_cache_used_bytes * 10 >= stats.code_cache_max_bytes * 9 {
+            self.read_code_cache(&mut stats).await;
+        }
+        Some(stats)
     }
 
     async fn trigger_gc(&self) -> Result<()> {
         self.execute_jcmd("GC.run").await?;
         Ok(())
     }
+
+    fn capture_raw_output(&mut self) {
+        self.raw_output.enable();
+    }
+
+    fn last_raw_output(&self) -> Option<Vec<RawOutput>> {
+        self.raw_output.latest()
+    }
+
+    async fn dump_heap(&self, path: &std::path::Path) -> Result<()> {
+        // OpenJ9 writes a PHD-format dump with Dump.heap
+        let command = match self.vendor().await {
+            VmVendor::HotSpot => "GC.heap_dump",
+            VmVendor::OpenJ9 => "Dump.heap",
+        };
+        let output = self
+            .execute_jcmd_with_timeout(
+                &format!("{} {}", command, path.display()),
+                Some(HEAP_DUMP_TIMEOUT),
+            )
+            .await?;
+        // The JVM resolves the path in its own filesystem, which may not be
+        // ours (e.g. a containerized JVM), so trust what jcmd reports
+        jcmd::parse_heap_dump_result(&output).map_err(|message| {
+            crate::error::AppError::Connection(format!(
+                "jcmd did not write the heap dump: {}",
+                message
+            ))
+        })
+    }
+
+    fn supports_heap_dump(&self) -> bool {
+        true
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+}
+
+/// Skips blank lines, stack frames and jcmd's `<pid>:` header.
+fn is_error_line(line: &str) -> bool {
+    let header = line
+        .trim_end()
+        .strip_suffix(':')
+        .is_some_and(|pid| pid.chars().all(|c| c.is_ascii_digit()));
+    !line.trim().is_empty() && !line.starts_with(char::is_whitespace) && !header
+}
+
+async fn read_gc_log_tail(path: &std::path::Path) -> Result<Vec<GcPause>> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let start = len.saturating_sub(GC_LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start)).await?;
+
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf).await?;
+    let content = String::from_utf8_lossy(&buf);
+
+    // Drop the partial first line when starting mid-file
+    let content = if start > 0 {
+        content.split_once('\n').map(|(_, rest)| rest).unwrap_or("")
+    } else {
+        &content
+    };
+
+    let mut pauses = gc_log::parse_gc_log(content);
+    if pauses.len() > MAX_GC_PAUSES {
+        pauses.drain(..pauses.len() - MAX_GC_PAUSES);
+    }
+    Ok(pauses)
 }
 
 #[cfg(test)]
@@ -195,6 +638,116 @@ mod tests {
         assert!(!connector.is_connected().await);
     }
 
+    #[tokio::test]
+    async fn test_reconnect_without_previous_connection_fails() {
+        let mut connector = JdkToolsConnector::new();
+        assert!(connector.reconnect().await.is_err());
+        assert!(!connector.is_connected().await);
+    }
+
+    #[tokio::test]
+    async fn test_failed_connect_leaves_disconnected() {
+        let mut connector = JdkToolsConnector::new();
+        // Far above any real PID, so jcmd can't attach whether or not it exists
+        assert!(connector.connect(u32::MAX - 1).await.is_err());
+        assert!(!connector.is_connected().await);
+        assert!(connector.reconnect().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_gc_pauses_from_log() {
+        let path = std::env::temp_dir().join(format!("jvm-tui-gc-{}.log", std::process::id()));
+        std::fs::write(
+            &path,
+            include_str!("../../../assets/sample_outputs/gc_unified.log"),
+        )
+        .unwrap();
+
+        let connector = JdkToolsConnector::new().with_gc_log(path.clone());
+        let pauses = connector.get_recent_gc_pauses().await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(pauses.len(), 5);
+        assert_eq!(pauses[4].cause.as_deref(), Some("System.gc()"));
+    }
+
+    #[test]
+    fn test_is_error_line() {
+        let output = "999999:\njava.io.IOException: No such process\n\
+                      \tat jdk.attach/sun.tools.attach.VirtualMachineImpl.sendQuitTo(Native Method)\n";
+        assert_eq!(
+            output.lines().find(|line| is_error_line(line)),
+            Some("java.io.IOException: No such process")
+        );
+        assert!(!is_error_line(""));
+        assert!(is_error_line("Command not found: Foo.bar:"));
+    }
+
+    /// Writes a shell script standing in for a JDK tool that prints the
+    /// sample output named after its first argument.
+    #[cfg(unix)]
+    fn fake_tool(dir: &std::path::Path, name: &str, outputs: &[(&str, &str)]) -> ToolStatus {
+        use std::os::unix::fs::PermissionsExt;
+
+        let samples = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/sample_outputs");
+        let cases: String = outputs
+            .iter()
+            .map(|(arg, file)| format!("  {}) cat {}/{} ;;\n", arg, samples, file))
+            .collect();
+        let path = dir.join(name);
+        std::fs::write(
+            &path,
+            format!("#!/bin/sh\ncase \"$1\" in\n{}  *) exit 1 ;;\nesac\n", cases),
+        )
+        .unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        ToolStatus::Available {
+            path,
+            version: "21.0.1".to_string(),
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_connect_without_jcmd_uses_jstat_and_jmap() {
+        let dir = std::env::temp_dir().join(format!("jvm-tui-no-jcmd-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let tools = JdkToolsStatus {
+            jcmd: ToolStatus::NotFound,
+            jstat: fake_tool(
+                &dir,
+                "jstat",
+                &[
+                    ("-gc", "jstat_gc.txt"),
+                    ("-gccapacity", "jstat_gccapacity.txt"),
+                ],
+            ),
+            jps: fake_tool(&dir, "jps", &[]),
+            jmap: fake_tool(&dir, "jmap", &[("-histo:live", "jcmd_class_histogram.txt")]),
+            java_home: None,
+        };
+
+        let mut connector = JdkToolsConnector::with_tools(tools);
+        connector.connect(4242).await.unwrap();
+        let jvm_info = connector.get_jvm_info().await.unwrap();
+        let heap = connector.get_heap_info().await.unwrap();
+        let classes = connector.get_class_histogram().await.unwrap();
+        // The fake jstat exits with 1 for options it doesn't know, as jstat
+        // does once the JVM has exited
+        let gc_error = connector.get_gc_stats().await.unwrap_err();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(jvm_info.pid, 4242);
+        assert_eq!(jvm_info.version, "unknown");
+        // -Xmx128m, per jstat -gccapacity
+        assert_eq!(heap.max_bytes, 128 * 1024 * 1024);
+        assert_eq!(heap.pools[0].name, "Eden");
+        assert!(classes.len() > 100);
+        assert_eq!(classes[0].rank, 1);
+        assert!(gc_error.is_connection_error(), "{}", gc_error);
+    }
+
     #[tokio::test]
     async fn test_connect_to_real_jvm() {
         let mut connector = JdkToolsConnector::new();
@@ -224,5 +777,9 @@ mod tests {
 
         connector.disconnect().await.unwrap();
         assert!(!connector.is_connected().await);
+
+        connector.reconnect().await.unwrap();
+        assert!(connector.is_connected().await);
+        assert_eq!(connector.get_jvm_info().await.unwrap().pid, test_pid);
     }
 }
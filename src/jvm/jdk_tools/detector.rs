@@ -7,6 +7,7 @@ pub struct JdkToolsStatus {
     pub jcmd: ToolStatus,
     pub jstat: ToolStatus,
     pub jps: ToolStatus,
+    pub jmap: ToolStatus,
     pub java_home: Option<PathBuf>,
 }
 
@@ -29,6 +30,43 @@ impl ToolStatus {
             ToolStatus::NotFound => None,
         }
     }
+
+    pub fn major_version(&self) -> Option<u32> {
+        match self {
+            ToolStatus::Available { version, .. } => parse_major_version(version),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts the Java feature release from strings like `17.0.2`, `1.8.0_392`,
+/// `openjdk version "21.0.1"` or `JDK 21.0.1+12-LTS`.
+pub fn parse_major_version(version: &str) -> Option<u32> {
+    let start = version.find(|c: char| c.is_ascii_digit())?;
+    let mut parts = version[start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|part| !part.is_empty());
+
+    let major = parts.next()?.parse::<u32>().ok()?;
+    if major == 1 {
+        parts.next()?.parse::<u32>().ok()
+    } else {
+        Some(major)
+    }
+}
+
+/// Warning shown when jcmd and the target JVM come from different JDK releases.
+pub fn version_mismatch_warning(tool_major: u32, target_version: &str) -> Option<String> {
+    let target_major = parse_major_version(target_version)?;
+    if tool_major == target_major {
+        return None;
+    }
+
+    Some(format!(
+        "jcmd is JDK {}, target is JDK {} \u{2014} attach may fail. \
+         Point JAVA_HOME at a JDK {} installation.",
+        tool_major, target_major, target_major
+    ))
 }
 
 #[derive(Debug, Clone)]
@@ -43,12 +81,16 @@ pub struct Capabilities {
 
 impl JdkToolsStatus {
     pub fn detect() -> Self {
-        let java_home = std::env::var("JAVA_HOME").ok().map(PathBuf::from);
+        Self::detect_with_java_home(std::env::var("JAVA_HOME").ok().map(PathBuf::from))
+    }
 
+    /// Detects tools preferring `java_home/bin` over whatever is on PATH.
+    pub fn detect_with_java_home(java_home: Option<PathBuf>) -> Self {
         Self {
             jcmd: detect_tool("jcmd", &java_home),
             jstat: detect_tool("jstat", &java_home),
             jps: detect_tool("jps", &java_home),
+            jmap: detect_tool("jmap", &java_home),
             java_home,
         }
     }
@@ -63,7 +105,7 @@ impl JdkToolsStatus {
             can_heap_info: self.jcmd.is_available(),
             can_gc_stats: self.jstat.is_available() || self.jcmd.is_available(),
             can_thread_dump: self.jcmd.is_available(),
-            can_class_histogram: self.jcmd.is_available(),
+            can_class_histogram: self.jcmd.is_available() || self.jmap.is_available(),
             can_trigger_gc: self.jcmd.is_available(),
         }
     }
@@ -224,6 +266,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_major_version() {
+        assert_eq!(parse_major_version("17.0.2"), Some(17));
+        assert_eq!(parse_major_version("1.8.0_392"), Some(8));
+        assert_eq!(
+            parse_major_version("openjdk version \"21.0.1\" 2023-10-17"),
+            Some(21)
+        );
+        assert_eq!(parse_major_version("JDK 11.0.21+9"), Some(11));
+        assert_eq!(parse_major_version("unknown"), None);
+    }
+
+    #[test]
+    fn test_version_mismatch_warning() {
+        assert!(version_mismatch_warning(21, "21.0.1+12-LTS").is_none());
+        assert!(version_mismatch_warning(21, "unknown").is_none());
+
+        let warning = version_mismatch_warning(17, "21.0.1+12-LTS").unwrap();
+        assert!(warning.starts_with("jcmd is JDK 17, target is JDK 21"));
+    }
+
     #[test]
     fn test_capabilities() {
         let status = JdkToolsStatus::detect();
@@ -10,12 +10,26 @@ pub async fn execute_command(
     tool: &str,
     args: &[&str],
     timeout_duration: Option<Duration>,
+) -> Result<Output, JdkToolsError> {
+    execute_command_with_env(tool, args, timeout_duration, &[]).await
+}
+
+/// Like `execute_command`, with extra environment variables set on top of the
+/// inherited environment.
+pub async fn execute_command_with_env(
+    tool: &str,
+    args: &[&str],
+    timeout_duration: Option<Duration>,
+    env: &[(String, String)],
 ) -> Result<Output, JdkToolsError> {
     let timeout_duration = timeout_duration.unwrap_or(DEFAULT_TIMEOUT);
 
-    let command = Command::new(tool).args(args).output();
+    let command = Command::new(tool)
+        .args(args)
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .output();
 
-    timeout(timeout_duration, command)
+    let output = timeout(timeout_duration, command)
         .await
         .map_err(|_| JdkToolsError::Timeout {
             command: format!("{} {}", tool, args.join(" ")),
@@ -23,5 +37,32 @@ pub async fn execute_command(
         .map_err(|e| JdkToolsError::ExecutionFailed {
             command: format!("{} {}", tool, args.join(" ")),
             source: e,
-        })
+        })?;
+
+    Ok(output)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute_command_with_env() {
+        let env = vec![("JAVA_HOME".to_string(), "/opt/jdk-21".to_string())];
+        let output =
+            execute_command_with_env("sh", &["-c", "printf %s \"$JAVA_HOME\""], None, &env)
+                .await
+                .unwrap();
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "/opt/jdk-21");
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_inherits_environment() {
+        let output = execute_command("sh", &["-c", "printf %s \"$PATH\""], None)
+            .await
+            .unwrap();
+
+        assert!(!output.stdout.is_empty());
+    }
 }
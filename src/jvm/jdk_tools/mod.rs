@@ -26,6 +26,10 @@ pub enum JdkToolsError {
     #[error("Command timed out: {command}")]
     Timeout { command: String },
 
+    /// The tool ran but exited unsuccessfully, e.g. because the JVM is gone.
+    #[error("{command} failed: {message}")]
+    Failed { command: String, message: String },
+
     #[error("Parse error: {0}")]
     ParseError(String),
 }
@@ -0,0 +1,147 @@
+use crate::jvm::types::GcPause;
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+// [2024-01-15T10:23:43.130+0000][2.140s][info][gc] GC(0) Pause Young (Normal) (G1 Evacuation Pause) 24M->5M(256M) 12.345ms
+static PAUSE_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\[gc\s*\]\s*GC\((\d+)\)\s+Pause\s+(.+?)\s+(?:\d+[KMGT]?->\d+[KMGT]?\(\d+[KMGT]?\)\s+)?(\d+(?:\.\d+)?)ms\s*$",
+    )
+    .unwrap()
+});
+
+static DECORATION: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[([^\]]+)\]").unwrap());
+
+/// Parses pause events from JDK 11+ unified GC logging (`-Xlog:gc`).
+///
+/// Only the `[gc]` summary lines are used; phase and `gc,start` lines are
+/// skipped so each pause is counted once.
+pub fn parse_gc_log(content: &str) -> Vec<GcPause> {
+    content.lines().filter_map(parse_pause_line).collect()
+}
+
+fn parse_pause_line(line: &str) -> Option<GcPause> {
+    let caps = PAUSE_LINE.captures(line)?;
+    let gc_id = caps[1].parse::<u64>().ok()?;
+    let (kind, cause) = split_cause(&caps[2]);
+    let duration_ms = caps[3].parse::<f64>().ok()?;
+
+    let mut timestamp = None;
+    let mut uptime_seconds = None;
+    for decoration in DECORATION.captures_iter(line) {
+        let value = &decoration[1];
+        if let Ok(time) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.3f%z") {
+            timestamp = Some(time.with_timezone(&Local));
+        } else if let Some(seconds) = value.strip_suffix('s') {
+            if let Ok(seconds) = seconds.parse::<f64>() {
+                uptime_seconds = Some(seconds);
+            }
+        }
+        if value == "gc" || value.starts_with("gc ") {
+            break;
+        }
+    }
+
+    Some(GcPause {
+        gc_id,
+        kind: kind.to_string(),
+        cause: cause.map(str::to_string),
+        duration_ms,
+        timestamp,
+        uptime_seconds,
+    })
+}
+
+/// Splits a pause description into its kind and the cause in the trailing
+/// parentheses: "Young (Normal) (G1 Evacuation Pause)" is a "Young (Normal)"
+/// pause caused by "G1 Evacuation Pause", "Full (System.gc())" a "Full" one.
+fn split_cause(description: &str) -> (&str, Option<&str>) {
+    let description = description.trim();
+    if !description.ends_with(')') {
+        return (description, None);
+    }
+
+    // Walk back to the parenthesis matching the final one; causes such as
+    // "System.gc()" contain parentheses themselves
+    let mut depth = 0;
+    for (index, c) in description.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' => {
+                depth -= 1;
+                if depth == 0 {
+                    let kind = description[..index].trim_end();
+                    if kind.is_empty() {
+                        break;
+                    }
+                    let cause = &description[index + 1..description.len() - 1];
+                    return (kind, Some(cause));
+                }
+            }
+            _ => {}
+        }
+    }
+    (description, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gc_log() {
+        let content = include_str!("../../../../assets/sample_outputs/gc_unified.log");
+        let pauses = parse_gc_log(content);
+
+        assert_eq!(pauses.len(), 5);
+
+        assert_eq!(pauses[0].gc_id, 0);
+        assert_eq!(pauses[0].kind, "Young (Normal)");
+        assert_eq!(pauses[0].cause.as_deref(), Some("G1 Evacuation Pause"));
+        assert!((pauses[0].duration_ms - 12.345).abs() < 1e-9);
+        assert_eq!(pauses[0].uptime_seconds, Some(2.140));
+        assert!(pauses[0].timestamp.is_some());
+
+        assert_eq!(pauses[1].cause.as_deref(), Some("G1 Humongous Allocation"));
+        assert_eq!(pauses[2].kind, "Remark");
+        assert_eq!(pauses[2].cause, None);
+        assert_eq!(pauses[3].kind, "Cleanup");
+
+        assert_eq!(pauses[4].gc_id, 3);
+        assert_eq!(pauses[4].kind, "Full");
+        assert_eq!(pauses[4].cause.as_deref(), Some("System.gc()"));
+        assert_eq!(pauses[4].description(), "Full (System.gc())");
+        assert!((pauses[4].duration_ms - 154.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_parse_gc_log_uptime_only() {
+        let line = "[12.503s][info][gc] GC(41) Pause Young (Normal) (G1 Evacuation Pause) 12.3ms";
+        let pauses = parse_gc_log(line);
+
+        assert_eq!(pauses.len(), 1);
+        assert_eq!(pauses[0].gc_id, 41);
+        assert!((pauses[0].duration_ms - 12.3).abs() < 1e-9);
+        assert_eq!(pauses[0].uptime_seconds, Some(12.503));
+        assert!(pauses[0].timestamp.is_none());
+    }
+
+    #[test]
+    fn test_split_cause() {
+        assert_eq!(
+            split_cause("Young (Allocation Failure)"),
+            ("Young", Some("Allocation Failure"))
+        );
+        assert_eq!(
+            split_cause("Full (Metadata GC Threshold)"),
+            ("Full", Some("Metadata GC Threshold"))
+        );
+        assert_eq!(split_cause("Init Mark"), ("Init Mark", None));
+        assert_eq!(split_cause("(odd)"), ("(odd)", None));
+        assert_eq!(
+            split_cause("Full (unbalanced))"),
+            ("Full (unbalanced))", None)
+        );
+    }
+}
@@ -1,8 +1,13 @@
+use super::parse_decimal;
 use crate::jvm::types::{
-    ClassInfo, HeapInfo, MemoryPool, PoolType, StackFrame, ThreadInfo, ThreadState,
+    ClassInfo, HeapInfo, HumongousStats, LockInfo, LockKind, MemoryPool, NativeMemory,
+    NativeMemoryCategory, NativeMemoryReport, PoolType, StackFrame, ThreadInfo, ThreadState,
+    VmVendor,
 };
+use chrono::Local;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 
 static HEAP_TOTAL_USED: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"total\s+(\d+)K,\s+used\s+(\d+)K").unwrap());
@@ -15,12 +20,22 @@ static CLASS_SPACE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"class space\s+used\s+(\d+)K,\s+committed\s+(\d+)K,\s+reserved\s+(\d+)K").unwrap()
 });
 
-static UPTIME: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+\.\d+)\s+s").unwrap());
+static UPTIME: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+[.,]\d+)\s+s").unwrap());
 
 static THREAD_HEADER: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#""([^"]+)"\s+#(\d+).*tid=0x[0-9a-f]+\s+nid=\d+\s+(.*)\s+\["#).unwrap()
+    Regex::new(r#""([^"]+)"\s+#(\d+).*tid=0x[0-9a-f]+\s+nid=(?:0x[0-9a-f]+|\d+)\s+(.*)\s+\["#)
+        .unwrap()
+});
+
+static LOCK_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s+- (locked|waiting to lock|waiting on|waiting to re-lock in wait\(\)|parking to wait for|)\s*<([^>]+)>(?:\s+\(a ([^)]+)\))?",
+    )
+    .unwrap()
 });
 
+static THREAD_CPU: Lazy<Regex> = Lazy::new(|| Regex::new(r"\scpu=(\d+(?:[.,]\d+)?)ms\s").unwrap());
+
 static THREAD_STATE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"java\.lang\.Thread\.State:\s+(\w+)").unwrap());
 
@@ -29,8 +44,31 @@ static STACK_FRAME: Lazy<Regex> = Lazy::new(|| {
         .unwrap()
 });
 
+static DEADLOCK_THREAD: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^"([^"]+)":$"#).unwrap());
+
+/// The object a deadlocked thread waits for: a monitor's `(object 0x...` or
+/// an `ownable synchronizer 0x...`.
+static DEADLOCK_WAIT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s+waiting .*(?:\(object|ownable synchronizer) (0x[0-9a-fA-F]+)").unwrap()
+});
+
+static REGION_SIZE: Lazy<Regex> = Lazy::new(|| Regex::new(r"region size (\d+)K").unwrap());
+
+// Matches both segmented "CodeHeap '...':" and unsegmented "CodeCache:" lines
+static CODE_HEAP: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:CodeHeap '[^']+'|CodeCache):\s+size=(\d+)Kb\s+used=(\d+)Kb").unwrap()
+});
+
+// "-    Java Heap (reserved=131072KB, committed=96256KB)"
+static NMT_CATEGORY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^-\s+(.+?)\s+\(reserved=(\d+)KB,\s+committed=(\d+)KB\)").unwrap());
+
+static NMT_TOTAL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^Total:\s+reserved=(\d+)KB,\s+committed=(\d+)KB").unwrap());
+
+// OpenJ9 prints the rank without HotSpot's trailing colon
 static CLASS_HISTOGRAM_LINE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r"^\s*(\d+):\s+(\d+)\s+(\d+)\s+(.+?)\s*(?:\(.*\))?$").unwrap());
+    Lazy::new(|| Regex::new(r"^\s*(\d+):?\s+(\d+)\s+(\d+)\s+(.+?)\s*(?:\(.*\))?$").unwrap());
 
 pub fn parse_heap_info(output: &str) -> Result<HeapInfo, String> {
     let mut used_bytes = 0u64;
@@ -83,6 +121,7 @@ pub fn parse_heap_info(output: &str) -> Result<HeapInfo, String> {
         max_bytes,
         committed_bytes,
         pools,
+        timestamp: Local::now(),
     })
 }
 
@@ -95,22 +134,82 @@ pub fn parse_jvm_version(output: &str) -> Result<String, String> {
                 return Ok(parts[1].to_string());
             }
         }
+        // OpenJ9: "JCL      - 8b5b8fcbc1 based on jdk-17.0.8.1+1)"
+        if let Some((_, based_on)) = trimmed.split_once("based on ") {
+            let version = based_on.trim_end_matches(')').trim_start_matches("jdk-");
+            if !version.is_empty() {
+                return Ok(version.to_string());
+            }
+        }
     }
     Err("Failed to parse JVM version".to_string())
 }
 
+/// Which JVM answered `VM.version`; anything not naming J9 is HotSpot.
+pub fn parse_vm_vendor(output: &str) -> VmVendor {
+    if output.contains("OpenJ9") || output.contains("IBM J9") {
+        VmVendor::OpenJ9
+    } else {
+        VmVendor::HotSpot
+    }
+}
+
 pub fn parse_vm_uptime(output: &str) -> Result<u64, String> {
     for line in output.lines() {
         if let Some(caps) = UPTIME.captures(line) {
-            let seconds = caps[1]
-                .parse::<f64>()
-                .map_err(|e| format!("Failed to parse uptime: {}", e))?;
+            let seconds =
+                parse_decimal(&caps[1]).map_err(|e| format!("Failed to parse uptime: {}", e))?;
             return Ok(seconds as u64);
         }
     }
     Err("Failed to parse VM uptime".to_string())
 }
 
+/// Humongous region counts from the G1 region table `VM.info` prints.
+/// `None` for other collectors, which print no such table.
+pub fn parse_humongous_regions(output: &str) -> Option<HumongousStats> {
+    let table_start = output.find("Heap Regions:")?;
+    let region_size_bytes = REGION_SIZE
+        .captures(output)
+        .and_then(|caps| caps[1].parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+        .unwrap_or(0);
+
+    let mut stats = HumongousStats {
+        regions: 0,
+        objects: 0,
+        total_regions: 0,
+        region_size_bytes,
+    };
+
+    // |  3|0x..., 0x..., 0x...|100%|HS|  |TAMS ...
+    for line in output[table_start..].lines().skip(1) {
+        if !line.starts_with('|') {
+            break;
+        }
+        stats.total_regions += 1;
+        match line.split('|').nth(4).map(str::trim) {
+            Some("HS") => {
+                stats.regions += 1;
+                stats.objects += 1;
+            }
+            Some("HC") => stats.regions += 1,
+            _ => {}
+        }
+    }
+
+    Some(stats)
+}
+
+/// Main class (or jar) from `VM.command_line`, as `jps -l` reports it.
+pub fn parse_main_class(output: &str) -> Option<String> {
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("java_command:"))
+        .and_then(|command| command.split_whitespace().next())
+        .map(str::to_string)
+}
+
 pub fn parse_vm_flags(output: &str) -> Result<Vec<String>, String> {
     let mut flags = Vec::new();
 
@@ -145,10 +244,19 @@ pub fn parse_thread_dump(output: &str) -> Result<Vec<ThreadInfo>, String> {
             let id = caps[2]
                 .parse::<u64>()
                 .map_err(|e| format!("Failed to parse thread id: {}", e))?;
+            let cpu_time_ns = THREAD_CPU
+                .captures(line)
+                .and_then(|caps| parse_decimal(&caps[1]).ok())
+                .map(|ms| (ms * 1_000_000.0).round() as u64);
+            // Looked for after the quoted name, which may contain the word
+            let daemon = line[caps.get(2).map_or(0, |id| id.end())..]
+                .split_whitespace()
+                .any(|token| token == "daemon");
 
             // Parse thread state from next few lines
             let mut state = ThreadState::Runnable;
             let mut stack_trace = Vec::new();
+            let mut locks = Vec::new();
 
             // Look ahead for thread state and stack frames
             let mut j = i + 1;
@@ -156,8 +264,9 @@ pub fn parse_thread_dump(output: &str) -> Result<Vec<ThreadInfo>, String> {
                 // Limit lookahead
                 let next_line = lines[j];
 
-                // Check if we hit the next thread
-                if THREAD_HEADER.is_match(next_line) {
+                // The next thread, or the VM threads and deadlock report
+                // after the last one
+                if !next_line.is_empty() && !next_line.starts_with(char::is_whitespace) {
                     break;
                 }
 
@@ -196,6 +305,23 @@ pub fn parse_thread_dump(output: &str) -> Result<Vec<ThreadInfo>, String> {
                     });
                 }
 
+                if let Some(lock_caps) = LOCK_LINE.captures(next_line) {
+                    let kind = match &lock_caps[1] {
+                        "locked" => LockKind::Locked,
+                        "waiting to lock" => LockKind::WaitingToLock,
+                        "waiting on" => LockKind::WaitingOn,
+                        "parking to wait for" => LockKind::ParkingToWaitFor,
+                        "" => LockKind::OwnableSynchronizer,
+                        _ => LockKind::WaitingToRelock,
+                    };
+                    let address = &lock_caps[2];
+                    locks.push(LockInfo {
+                        kind,
+                        address: address.starts_with("0x").then(|| address.to_string()),
+                        class_name: lock_caps.get(3).map(|m| m.as_str().to_string()),
+                    });
+                }
+
                 j += 1;
             }
 
@@ -204,6 +330,9 @@ pub fn parse_thread_dump(output: &str) -> Result<Vec<ThreadInfo>, String> {
                 name,
                 state,
                 stack_trace,
+                cpu_time_ns,
+                daemon: Some(daemon),
+                locks,
             });
 
             i = j;
@@ -219,6 +348,66 @@ pub fn parse_thread_dump(output: &str) -> Result<Vec<ThreadInfo>, String> {
     Ok(threads)
 }
 
+/// Parses a `Thread.print` dump into its threads and the IDs of those in
+/// its deadlock report, so one dump serves both.
+pub fn parse_thread_print(output: &str) -> Result<(Vec<ThreadInfo>, Vec<u64>), String> {
+    let threads = parse_thread_dump(output)?;
+    let deadlocked = parse_deadlocked_threads(output, &threads);
+    Ok((threads, deadlocked))
+}
+
+/// Maps the threads named in the "Found one Java-level deadlock" sections of a
+/// `Thread.print` dump back to their thread IDs. The report names threads
+/// only, so each is matched on the object it waits for as well, which tells
+/// apart threads sharing a name.
+pub fn parse_deadlocked_threads(output: &str, threads: &[ThreadInfo]) -> Vec<u64> {
+    // (name, address of the awaited object)
+    let mut waiters: Vec<(&str, Option<&str>)> = Vec::new();
+    let mut in_deadlock_section = false;
+
+    for line in output.lines() {
+        let line = line.trim_end();
+
+        if line.starts_with("Found ") && line.contains("Java-level deadlock") {
+            in_deadlock_section = true;
+            continue;
+        }
+
+        if line.starts_with("Java stack information for the threads listed above") {
+            in_deadlock_section = false;
+            continue;
+        }
+
+        if !in_deadlock_section {
+            continue;
+        }
+
+        if let Some(caps) = DEADLOCK_THREAD.captures(line) {
+            waiters.push((caps.get(1).unwrap().as_str(), None));
+        } else if let Some(caps) = DEADLOCK_WAIT.captures(line) {
+            if let Some(waiter) = waiters.last_mut() {
+                waiter.1.get_or_insert(caps.get(1).unwrap().as_str());
+            }
+        }
+    }
+
+    threads
+        .iter()
+        .filter(|thread| {
+            waiters.iter().any(|(name, address)| {
+                thread.name == *name
+                    && address.map_or(true, |address| {
+                        thread
+                            .awaited_lock()
+                            .and_then(|lock| lock.address.as_deref())
+                            .is_some_and(|awaited| awaited.eq_ignore_ascii_case(address))
+                    })
+            })
+        })
+        .map(|thread| thread.id)
+        .collect()
+}
+
 pub fn parse_class_histogram(output: &str) -> Result<Vec<ClassInfo>, String> {
     let mut classes = Vec::new();
 
@@ -251,9 +440,205 @@ pub fn parse_class_histogram(output: &str) -> Result<Vec<ClassInfo>, String> {
     Ok(classes)
 }
 
+/// Whether `GC.heap_dump` (or OpenJ9's `Dump.heap`) wrote the dump. jcmd
+/// exits 0 either way, so a refused path only shows in its last line, e.g.
+/// `Unable to create /tmp/x.hprof: File exists`.
+pub fn parse_heap_dump_result(output: &str) -> Result<(), String> {
+    if output.contains("Heap dump file created") || output.contains("Dump written to") {
+        return Ok(());
+    }
+
+    Err(output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("no output")
+        .to_string())
+}
+
+/// Code cache `(used, max)` in bytes from `Compiler.codecache`, summed over
+/// all code heaps, plus whether compilation is still enabled.
+pub fn parse_code_cache(output: &str) -> Result<(u64, u64, bool), String> {
+    let mut used_bytes = 0u64;
+    let mut max_bytes = 0u64;
+    let mut found = false;
+
+    for line in output.lines() {
+        if let Some(caps) = CODE_HEAP.captures(line) {
+            let size = caps[1]
+                .parse::<u64>()
+                .map_err(|e| format!("Failed to parse code heap size: {}", e))?;
+            let used = caps[2]
+                .parse::<u64>()
+                .map_err(|e| format!("Failed to parse code heap usage: {}", e))?;
+            max_bytes += size * 1024;
+            used_bytes += used * 1024;
+            found = true;
+        }
+    }
+
+    if !found {
+        return Err("No code heaps found in output".to_string());
+    }
+
+    let enabled = !output
+        .lines()
+        .any(|line| line.trim().starts_with("compilation: disabled"));
+
+    Ok((used_bytes, max_bytes, enabled))
+}
+
+/// The command line from `VM.command_line`: the JVM arguments followed by
+/// the main class or jar and its arguments.
+pub fn parse_command_line(output: &str) -> Result<String, String> {
+    let field = |name: &str| {
+        output
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(str::trim)
+            // JVMs started through JNI report their command as "<unknown>"
+            .filter(|value| !value.is_empty() && *value != "<unknown>")
+    };
+
+    let parts: Vec<&str> = [field("jvm_args:"), field("java_command:")]
+        .into_iter()
+        .flatten()
+        .collect();
+    if parts.is_empty() {
+        return Err("No command line in VM.command_line output".to_string());
+    }
+    Ok(parts.join(" "))
+}
+
+/// `key=value` pairs from `VM.system_properties`, sorted by key. The output
+/// is in `java.util.Properties` format: `\` escapes, and a trailing `\`
+/// continues the value on the next line.
+pub fn parse_system_properties(output: &str) -> Vec<(String, String)> {
+    let mut properties = Vec::new();
+    let mut lines = output.lines();
+
+    while let Some(line) = lines.next() {
+        let mut logical = line.trim_start().to_string();
+        if logical.is_empty() || logical.starts_with('#') || logical.starts_with('!') {
+            continue;
+        }
+        // The "12345:" header jcmd prints before the properties
+        if logical.ends_with(':') && logical[..logical.len() - 1].parse::<u32>().is_ok() {
+            continue;
+        }
+
+        while ends_with_continuation(&logical) {
+            logical.pop();
+            match lines.next() {
+                Some(next) => logical.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let (key, value) = split_property(&logical);
+        properties.push((unescape_property(key), unescape_property(value)));
+    }
+
+    properties.sort_by(|a, b| a.0.cmp(&b.0));
+    properties
+}
+
+/// An odd number of trailing backslashes means the last one escapes the newline.
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1
+}
+
+/// Splits at the first unescaped `=` or `:`.
+fn split_property(line: &str) -> (&str, &str) {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '=' | ':' if !escaped => return (&line[..i], &line[i + 1..]),
+            _ => escaped = false,
+        }
+    }
+    (line, "")
+}
+
+fn unescape_property(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some('t') => result.push('\t'),
+            Some('f') => result.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) => result.push(decoded),
+                    None => {
+                        result.push_str("\\u");
+                        result.push_str(&hex);
+                    }
+                }
+            }
+            Some(other) => result.push(other),
+            None => {}
+        }
+    }
+
+    result
+}
+
+/// Numeric hsperfdata counters from `PerfCounter.print`, keyed by name.
+/// String counters (quoted values such as `sun.gc.cause`) are skipped.
+pub fn parse_perf_counters(output: &str) -> HashMap<String, i64> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().split_once('='))
+        .filter_map(|(name, value)| Some((name.to_string(), value.parse::<i64>().ok()?)))
+        .collect()
+}
+
+/// Per-category usage from `VM.native_memory summary`, or
+/// [`NativeMemory::Disabled`] when the JVM runs without NMT.
+pub fn parse_native_memory(output: &str) -> Result<NativeMemory, String> {
+    if output.contains("Native memory tracking is not enabled") {
+        return Ok(NativeMemory::Disabled);
+    }
+
+    let mut report = NativeMemoryReport::default();
+    let mut found_total = false;
+
+    for line in output.lines() {
+        if let Some(caps) = NMT_TOTAL.captures(line) {
+            report.total_reserved_bytes = caps[1].parse::<u64>().unwrap() * 1024;
+            report.total_committed_bytes = caps[2].parse::<u64>().unwrap() * 1024;
+            found_total = true;
+        } else if let Some(caps) = NMT_CATEGORY.captures(line) {
+            report.categories.push(NativeMemoryCategory {
+                name: caps[1].to_string(),
+                reserved_bytes: caps[2].parse::<u64>().unwrap() * 1024,
+                committed_bytes: caps[3].parse::<u64>().unwrap() * 1024,
+            });
+        }
+    }
+
+    if !found_total {
+        return Err("No native memory summary found in output".to_string());
+    }
+
+    Ok(NativeMemory::Tracked(report))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::jvm::types::{CompilerStats, RuntimeCounters};
 
     #[test]
     fn test_parse_heap_info() {
@@ -274,6 +659,14 @@ mod tests {
         let output = include_str!("../../../../assets/sample_outputs/jcmd_vm_version.txt");
         let version = parse_jvm_version(output).unwrap();
         assert_eq!(version, "21.0.8");
+        assert_eq!(parse_vm_vendor(output), VmVendor::HotSpot);
+    }
+
+    #[test]
+    fn test_parse_openj9_version() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_vm_version_openj9.txt");
+        assert_eq!(parse_jvm_version(output).unwrap(), "17.0.8.1+1");
+        assert_eq!(parse_vm_vendor(output), VmVendor::OpenJ9);
     }
 
     #[test]
@@ -281,6 +674,9 @@ mod tests {
         let output = include_str!("../../../../assets/sample_outputs/jcmd_vm_uptime.txt");
         let uptime = parse_vm_uptime(output).unwrap();
         assert_eq!(uptime, 390327);
+
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_vm_uptime_de.txt");
+        assert_eq!(parse_vm_uptime(output).unwrap(), 390327);
     }
 
     #[test]
@@ -310,6 +706,16 @@ mod tests {
             ThreadState::TimedWaiting | ThreadState::Waiting
         ));
         assert!(!main.stack_trace.is_empty());
+        assert_eq!(main.cpu_time_ns, Some(19_052_160_000));
+        assert_eq!(main.daemon, Some(false));
+        let finalizer = threads.iter().find(|t| t.name == "Finalizer").unwrap();
+        assert_eq!(finalizer.daemon, Some(true));
+
+        // Target JVM running under a decimal-comma locale
+        let output = output.replace("cpu=19052.16ms", "cpu=19052,16ms");
+        let threads = parse_thread_dump(&output).unwrap();
+        let main = threads.iter().find(|t| t.name == "main").unwrap();
+        assert_eq!(main.cpu_time_ns, Some(19_052_160_000));
 
         let first_frame = &main.stack_trace[0];
         assert!(
@@ -317,6 +723,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_deadlocked_threads() {
+        let output =
+            include_str!("../../../../assets/sample_outputs/jcmd_thread_print_deadlock.txt");
+        let threads = parse_thread_dump(output).unwrap();
+        let deadlocked = parse_deadlocked_threads(output, &threads);
+
+        assert_eq!(deadlocked, vec![21, 22]);
+
+        // A second thread named "worker-a" that isn't waiting on anything
+        let idle = "\"worker-a\" #30 [7470] prio=5 os_prio=0 cpu=0.10ms elapsed=1.00s \
+                    tid=0x00007f3c6c1f5000 nid=7470 waiting on condition  [0x00007f3c4c0fb000]
+   java.lang.Thread.State: TIMED_WAITING (sleeping)
+\tat java.lang.Thread.sleep0(java.base@21.0.8/Native Method)
+
+\"Attach Listener\"";
+        let output = output.replacen("\"Attach Listener\"", idle, 1);
+        let (threads, deadlocked) = parse_thread_print(&output).unwrap();
+        assert!(threads.iter().any(|t| t.id == 30 && t.name == "worker-a"));
+        assert_eq!(deadlocked, vec![21, 22]);
+    }
+
+    #[test]
+    fn test_parse_deadlocked_threads_none() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_thread_print.txt");
+        let threads = parse_thread_dump(output).unwrap();
+        assert!(parse_deadlocked_threads(output, &threads).is_empty());
+    }
+
     #[test]
     fn test_parse_class_histogram() {
         let output = include_str!("../../../../assets/sample_outputs/jcmd_class_histogram.txt");
@@ -334,4 +769,281 @@ mod tests {
         let byte_array = classes.iter().find(|c| c.name.contains("[B"));
         assert!(byte_array.is_some());
     }
+
+    #[test]
+    fn test_parse_openj9_class_histogram() {
+        let output =
+            include_str!("../../../../assets/sample_outputs/jcmd_class_histogram_openj9.txt");
+        let classes = parse_class_histogram(output).unwrap();
+
+        assert_eq!(classes.len(), 5);
+        assert_eq!(classes[0].name, "[C");
+        assert_eq!(classes[2].instances, 21421);
+        assert_eq!(classes[2].bytes, 514104);
+        assert_eq!(classes[4].name, "[Ljava.lang.Object;");
+    }
+
+    #[test]
+    fn test_parse_jmap_histogram() {
+        let output = " num     #instances         #bytes  class name (module)
+-------------------------------------------------------
+   1:         52018        4993728  [B (java.base@17.0.9)
+   2:         48210        1157040  java.lang.String (java.base@17.0.9)
+   3:          9120         802560  java.lang.Class (java.base@17.0.9)
+Total        109348        6953328";
+        let classes = parse_class_histogram(output).unwrap();
+
+        assert_eq!(classes.len(), 3);
+        assert_eq!(classes[1].rank, 2);
+        assert_eq!(classes[1].instances, 48210);
+        assert_eq!(classes[1].name, "java.lang.String");
+    }
+
+    #[test]
+    fn test_parse_humongous_regions() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_vm_info_g1.txt");
+        let stats = parse_humongous_regions(output).unwrap();
+
+        assert_eq!(stats.objects, 6);
+        assert_eq!(stats.regions, 24);
+        assert_eq!(stats.total_regions, 96);
+        assert_eq!(stats.region_size_bytes, 1024 * 1024);
+        assert!(stats.is_significant());
+
+        let serial = "Heap:\n def new generation   total 4928K, used 2380K\n";
+        assert!(parse_humongous_regions(serial).is_none());
+    }
+
+    #[test]
+    fn test_parse_command_line() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_vm_command_line.txt");
+        assert_eq!(
+            parse_command_line(output).unwrap(),
+            "--add-modules=ALL-DEFAULT jdk.compiler/com.sun.tools.javac.launcher.Main Sleep.java"
+        );
+
+        let embedded = "1:\nVM Arguments:\njava_command: <unknown>\nLauncher Type: generic\n";
+        assert!(parse_command_line(embedded).is_err());
+    }
+
+    #[test]
+    fn test_parse_thread_dump_locks() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_thread_print_locks.txt");
+        let threads = parse_thread_dump(output).unwrap();
+        assert_eq!(threads.len(), 4);
+
+        let lock = |kind, address: &str, class_name: &str| LockInfo {
+            kind,
+            address: Some(address.to_string()),
+            class_name: Some(class_name.to_string()),
+        };
+
+        let writer = &threads[0];
+        assert_eq!(
+            writer.locks,
+            vec![
+                lock(LockKind::Locked, "0x00000000c6a11f40", "java.util.HashMap"),
+                lock(
+                    LockKind::OwnableSynchronizer,
+                    "0x00000000c6a12088",
+                    "java.util.concurrent.locks.ReentrantLock$NonfairSync"
+                ),
+            ]
+        );
+        assert!(writer.awaited_lock().is_none());
+
+        let reader = &threads[1];
+        assert_eq!(
+            reader.awaited_lock(),
+            Some(&lock(
+                LockKind::WaitingToLock,
+                "0x00000000c6a11f40",
+                "java.util.HashMap"
+            ))
+        );
+        assert_eq!(reader.held_locks().count(), 0);
+
+        let consumer = &threads[2];
+        assert_eq!(consumer.locks.len(), 1);
+        assert_eq!(consumer.locks[0].kind, LockKind::ParkingToWaitFor);
+        assert_eq!(
+            consumer.locks[0].short_class_name(),
+            "AbstractQueuedSynchronizer$ConditionObject"
+        );
+
+        let finalizer = &threads[3];
+        assert_eq!(finalizer.locks[0].kind, LockKind::WaitingOn);
+        assert_eq!(finalizer.locks[0].address, None);
+        assert_eq!(finalizer.locks[1].kind, LockKind::Locked);
+
+        // The deadlock report after the threads isn't read as a stack
+        let output =
+            include_str!("../../../../assets/sample_outputs/jcmd_thread_print_deadlock.txt");
+        let threads = parse_thread_dump(output).unwrap();
+        let attach = threads
+            .iter()
+            .find(|t| t.name == "Attach Listener")
+            .unwrap();
+        assert!(attach.locks.is_empty());
+        assert!(attach.stack_trace.is_empty());
+    }
+
+    #[test]
+    fn test_parse_thread_dump_hex_nid() {
+        // JDK 8 to 16 print the native id in hex
+        let output = "\"not a daemon\" #12 prio=5 os_prio=0 tid=0x00007f3c48123800 nid=0x1a2b waiting on condition [0x00007f3c2d6fe000]\n   \
+                      java.lang.Thread.State: WAITING (parking)\n\n\
+                      \"pool-1-thread-1\" #13 daemon prio=5 os_prio=0 tid=0x00007f3c48125000 nid=0x1a2c runnable [0x00007f3c2d5fd000]\n   \
+                      java.lang.Thread.State: RUNNABLE\n";
+        let threads = parse_thread_dump(output).unwrap();
+
+        assert_eq!(threads.len(), 2);
+        assert_eq!(threads[0].id, 12);
+        assert_eq!(threads[0].state, ThreadState::Waiting);
+        assert_eq!(threads[0].daemon, Some(false));
+        assert_eq!(threads[1].daemon, Some(true));
+    }
+
+    #[test]
+    fn test_parse_system_properties() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_system_properties.txt");
+        let properties = parse_system_properties(output);
+        let get = |key: &str| {
+            properties
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        };
+
+        assert_eq!(properties.len(), 49);
+        assert!(properties.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_eq!(get("java.specification.version"), Some("17"));
+        assert_eq!(
+            get("java.vendor.url"),
+            Some("https://tracker.debian.org/openjdk-17")
+        );
+        assert_eq!(get("line.separator"), Some("\n"));
+        assert_eq!(get("path.separator"), Some(":"));
+
+        let tricky = "1:\nkey\\=with\\:seps=a\\\n    b\\u00e9\nempty=\n";
+        assert_eq!(
+            parse_system_properties(tricky),
+            vec![
+                ("empty".to_string(), String::new()),
+                ("key=with:seps".to_string(), "abé".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_perf_counters() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_perf_counter.txt");
+        let counters = parse_perf_counters(output);
+
+        assert_eq!(counters.get("sun.rt.safepoints"), Some(&51));
+        assert_eq!(counters.get("java.ci.totalTime"), Some(&940409868));
+        assert_eq!(counters.get("sun.gc.collector.1.invocations"), Some(&6));
+        assert_eq!(counters.get("sun.os.hrt.frequency"), Some(&1_000_000_000));
+        assert!(!counters.contains_key("sun.gc.collector.0.name"));
+        assert!(!counters.contains_key("46168:"));
+        assert!(parse_perf_counters("46168:\n").is_empty());
+
+        let runtime = RuntimeCounters::from_perf_counters(&counters);
+        assert_eq!(runtime.safepoints, 51);
+        assert!((runtime.safepoint_time_ms - 194.534819).abs() < 1e-6);
+        assert!((runtime.jit_time_ms - 940.409868).abs() < 1e-6);
+        assert_eq!(runtime.classes_loaded, 1566);
+        assert_eq!(runtime.gc_invocations, 9);
+        assert!((runtime.gc_time_ms - 153.624386).abs() < 1e-6);
+
+        let compiler = CompilerStats::from_perf_counters(&counters, 240 * 1024 * 1024).unwrap();
+        assert_eq!(compiler.compiled_methods, 1051);
+        assert_eq!(compiler.failed_compilations, 0);
+        assert!((compiler.compile_time_seconds - 0.940409868).abs() < 1e-9);
+        assert_eq!(compiler.code_cache_used_bytes, 1683520);
+        assert_eq!(compiler.code_cache_max_bytes, 240 * 1024 * 1024);
+
+        // -Xint: no JIT counters at all
+        let interpreted = parse_perf_counters("sun.rt.safepoints=3\n");
+        assert!(CompilerStats::from_perf_counters(&interpreted, 0).is_none());
+    }
+
+    #[test]
+    fn test_parse_native_memory() {
+        let output =
+            include_str!("../../../../assets/sample_outputs/jcmd_native_memory_summary.txt");
+        let NativeMemory::Tracked(report) = parse_native_memory(output).unwrap() else {
+            panic!("expected an NMT report");
+        };
+
+        assert_eq!(report.total_reserved_bytes, 1529253 * 1024);
+        assert_eq!(report.total_committed_bytes, 131817 * 1024);
+        assert_eq!(report.categories[0].name, "Java Heap");
+        assert_eq!(report.categories[0].committed_bytes, 96256 * 1024);
+
+        let nmt = report
+            .categories
+            .iter()
+            .find(|c| c.name == "Native Memory Tracking")
+            .unwrap();
+        assert_eq!(nmt.reserved_bytes, 1018 * 1024);
+
+        let disabled =
+            include_str!("../../../../assets/sample_outputs/jcmd_native_memory_disabled.txt");
+        assert!(matches!(
+            parse_native_memory(disabled),
+            Ok(NativeMemory::Disabled)
+        ));
+        assert!(parse_native_memory("12345:\nUnknown diagnostic command").is_err());
+    }
+
+    #[test]
+    fn test_parse_main_class() {
+        let output = "12345:\n\
+                      VM Arguments:\n\
+                      jvm_args: -Xmx512m\n\
+                      java_command: /opt/app/service.jar --port 8080\n\
+                      java_class_path (initial): /opt/app/service.jar\n";
+        assert_eq!(
+            parse_main_class(output).as_deref(),
+            Some("/opt/app/service.jar")
+        );
+        assert_eq!(
+            parse_main_class("java_command: <unknown>\n").as_deref(),
+            Some("<unknown>")
+        );
+        assert_eq!(parse_main_class("12345:\nVM Arguments:\n"), None);
+    }
+
+    #[test]
+    fn test_parse_heap_dump_result() {
+        let written = "14561:\nDumping heap to /tmp/h.hprof ...\n\
+                       Heap dump file created [6775143 bytes in 0.038 secs]\n";
+        assert!(parse_heap_dump_result(written).is_ok());
+
+        let refused = "14561:\nDumping heap to /tmp/h.hprof ...\n\
+                       Unable to create /tmp/h.hprof: File exists\n";
+        assert_eq!(
+            parse_heap_dump_result(refused).unwrap_err(),
+            "Unable to create /tmp/h.hprof: File exists"
+        );
+    }
+
+    #[test]
+    fn test_parse_code_cache() {
+        let output = include_str!("../../../../assets/sample_outputs/jcmd_compiler_codecache.txt");
+        let (used, max, enabled) = parse_code_cache(output).unwrap();
+
+        assert_eq!(used, (338 + 1789 + 1133) * 1024);
+        assert_eq!(max, (120036 + 120032 + 5692) * 1024);
+        assert!(enabled);
+
+        let unsegmented = "CodeCache: size=49152Kb used=1200Kb max_used=1210Kb free=47952Kb\n\
+                           compilation: disabled (not enough contiguous free space left)\n";
+        assert_eq!(
+            parse_code_cache(unsegmented).unwrap(),
+            (1200 * 1024, 49152 * 1024, false)
+        );
+        assert!(parse_code_cache("nothing here").is_err());
+    }
 }
@@ -1,4 +1,7 @@
-use crate::jvm::types::GcStats;
+use super::parse_decimal;
+use crate::jvm::types::{
+    CompilerStats, GcCapacity, GcCategory, GcCollectorStat, GcStats, HeapInfo, MemoryPool, PoolType,
+};
 use chrono::Local;
 
 pub fn parse_gc_stats(output: &str) -> Result<GcStats, String> {
@@ -18,23 +21,180 @@ pub fn parse_gc_stats(output: &str) -> Result<GcStats, String> {
         .parse::<u64>()
         .map_err(|e| format!("Failed to parse YGC: {}", e))?;
 
-    let young_gc_time = values[7]
-        .parse::<f64>()
-        .map_err(|e| format!("Failed to parse YGCT: {}", e))?;
+    let young_gc_time =
+        parse_decimal(values[7]).map_err(|e| format!("Failed to parse YGCT: {}", e))?;
 
     let full_gc_count = values[8]
         .parse::<u64>()
         .map_err(|e| format!("Failed to parse FGC: {}", e))?;
 
-    let full_gc_time = values[9]
-        .parse::<f64>()
-        .map_err(|e| format!("Failed to parse FGCT: {}", e))?;
+    let full_gc_time =
+        parse_decimal(values[9]).map_err(|e| format!("Failed to parse FGCT: {}", e))?;
 
-    Ok(GcStats {
+    let mut stats = GcStats::from_generations(
         young_gc_count,
-        young_gc_time_ms: (young_gc_time * 1000.0) as u64,
-        old_gc_count: full_gc_count,
-        old_gc_time_ms: (full_gc_time * 1000.0) as u64,
+        (young_gc_time * 1000.0) as u64,
+        full_gc_count,
+        (full_gc_time * 1000.0) as u64,
+        Local::now(),
+    );
+
+    // CGC/CGCT read "-" for collectors without concurrent cycles
+    if let (Ok(count), Ok(time)) = (values[10].parse::<u64>(), parse_decimal(values[11])) {
+        stats.collectors.push(GcCollectorStat {
+            name: "Concurrent GC".to_string(),
+            count,
+            time_ms: (time * 1000.0) as u64,
+            category: GcCategory::Concurrent,
+        });
+    }
+
+    Ok(stats)
+}
+
+/// Parses `jstat -compiler`. Code cache fields are left for jcmd to fill in.
+pub fn parse_compiler_stats(output: &str) -> Result<CompilerStats, String> {
+    let lines: Vec<&str> = output.lines().collect();
+    if lines.len() < 2 {
+        return Err("Invalid jstat output format".to_string());
+    }
+
+    let values: Vec<&str> = lines[1].split_whitespace().collect();
+    if values.len() < 4 {
+        return Err(format!("Expected at least 4 values, got {}", values.len()));
+    }
+
+    let compiled_methods = values[0]
+        .parse::<u64>()
+        .map_err(|e| format!("Failed to parse Compiled: {}", e))?;
+
+    let failed_compilations = values[1]
+        .parse::<u64>()
+        .map_err(|e| format!("Failed to parse Failed: {}", e))?;
+
+    let invalidated_compilations = values[2]
+        .parse::<u64>()
+        .map_err(|e| format!("Failed to parse Invalid: {}", e))?;
+
+    let compile_time_seconds =
+        parse_decimal(values[3]).map_err(|e| format!("Failed to parse Time: {}", e))?;
+
+    Ok(CompilerStats {
+        compiled_methods,
+        failed_compilations,
+        invalidated_compilations,
+        compile_time_seconds,
+        compilation_enabled: true,
+        ..CompilerStats::default()
+    })
+}
+
+/// The data row of a `jstat` sample, addressed by column header since the
+/// layout varies between JDK versions.
+struct JstatRow<'a> {
+    headers: Vec<&'a str>,
+    values: Vec<&'a str>,
+}
+
+impl<'a> JstatRow<'a> {
+    fn parse(output: &'a str) -> Result<Self, String> {
+        let lines: Vec<&str> = output.lines().collect();
+        if lines.len() < 2 {
+            return Err("Invalid jstat output format".to_string());
+        }
+
+        Ok(Self {
+            headers: lines[0].split_whitespace().collect(),
+            values: lines[1].split_whitespace().collect(),
+        })
+    }
+
+    /// Sizes are printed in KB, with a fractional part.
+    fn bytes(&self, name: &str) -> Result<u64, String> {
+        let index = self
+            .headers
+            .iter()
+            .position(|header| *header == name)
+            .ok_or_else(|| format!("Missing {} column", name))?;
+        let value = self
+            .values
+            .get(index)
+            .ok_or_else(|| format!("Missing {} value", name))?;
+        let kb = parse_decimal(value).map_err(|e| format!("Failed to parse {}: {}", name, e))?;
+        Ok((kb * 1024.0) as u64)
+    }
+}
+
+/// Parses `jstat -gccapacity`.
+pub fn parse_gc_capacity(output: &str) -> Result<GcCapacity, String> {
+    let row = JstatRow::parse(output)?;
+
+    Ok(GcCapacity {
+        young_max_bytes: row.bytes("NGCMX")?,
+        young_committed_bytes: row.bytes("NGC")?,
+        old_max_bytes: row.bytes("OGCMX")?,
+        old_committed_bytes: row.bytes("OGC")?,
+    })
+}
+
+/// Parses `jstat -gc` into Eden, Survivor and Old pools, the two survivor
+/// spaces summed. jstat reports no maximum per space, so it is left at 0.
+pub fn parse_gc_generations(output: &str) -> Result<Vec<MemoryPool>, String> {
+    generations(&JstatRow::parse(output)?)
+}
+
+fn generations(row: &JstatRow) -> Result<Vec<MemoryPool>, String> {
+    Ok(vec![
+        space("Eden", PoolType::Eden, row.bytes("EU")?, row.bytes("EC")?),
+        space(
+            "Survivor",
+            PoolType::Survivor,
+            row.bytes("S0U")? + row.bytes("S1U")?,
+            row.bytes("S0C")? + row.bytes("S1C")?,
+        ),
+        space("Old", PoolType::Old, row.bytes("OU")?, row.bytes("OC")?),
+    ])
+}
+
+fn space(name: &str, pool_type: PoolType, used_bytes: u64, committed_bytes: u64) -> MemoryPool {
+    MemoryPool {
+        name: name.to_string(),
+        pool_type,
+        used_bytes,
+        max_bytes: 0,
+        committed_bytes,
+    }
+}
+
+/// Builds heap usage from the space sizes in `jstat -gc`, for when
+/// `GC.heap_info` has no line we can parse. The maximum is left at the
+/// committed size; `jstat -gccapacity` knows better.
+pub fn parse_gc_heap(output: &str) -> Result<HeapInfo, String> {
+    let row = JstatRow::parse(output)?;
+
+    let mut pools = generations(&row)?;
+
+    let used_bytes = pools.iter().map(|pool| pool.used_bytes).sum();
+    let committed_bytes = pools.iter().map(|pool| pool.committed_bytes).sum();
+
+    // Metaspace and class space carry no maximum here either
+    for (name, used, committed) in [("Metaspace", "MU", "MC"), ("Class Space", "CCSU", "CCSC")] {
+        if let (Ok(used), Ok(committed)) = (row.bytes(used), row.bytes(committed)) {
+            pools.push(MemoryPool {
+                name: name.to_string(),
+                pool_type: PoolType::Metaspace,
+                used_bytes: used,
+                max_bytes: 0,
+                committed_bytes: committed,
+            });
+        }
+    }
+
+    Ok(HeapInfo {
+        used_bytes,
+        max_bytes: committed_bytes,
+        committed_bytes,
+        pools,
         timestamp: Local::now(),
     })
 }
@@ -48,10 +208,110 @@ mod tests {
         let output = include_str!("../../../../assets/sample_outputs/jstat_gcutil.txt");
         let stats = parse_gc_stats(output).unwrap();
 
-        assert_eq!(stats.young_gc_count, 125387);
-        assert_eq!(stats.young_gc_time_ms, 497699);
-        assert_eq!(stats.old_gc_count, 37);
-        assert_eq!(stats.old_gc_time_ms, 9222);
+        assert_eq!(stats.young_gc_count(), 125387);
+        assert_eq!(stats.young_gc_time_ms(), 497699);
+        assert_eq!(stats.old_gc_count(), 37);
+        assert_eq!(stats.old_gc_time_ms(), 9222);
+
+        let concurrent = &stats.collectors[2];
+        assert_eq!(concurrent.category, GcCategory::Concurrent);
+        assert_eq!(concurrent.count, 118290);
+        assert_eq!(concurrent.time_ms, 285225);
+    }
+
+    #[test]
+    fn test_parse_gc_stats_with_decimal_commas() {
+        let output = include_str!("../../../../assets/sample_outputs/jstat_gcutil_de.txt");
+        let stats = parse_gc_stats(output).unwrap();
+
+        assert_eq!(stats.young_gc_time_ms(), 497699);
+        assert_eq!(stats.old_gc_time_ms(), 9222);
+        assert_eq!(stats.collectors[2].time_ms, 285225);
+
+        let capacity = "NGCMN NGCMX NGC OGCMN OGCMX OGC\n0,0 43648,0 32128,0 0,0 87424,0 64192,0\n";
+        assert_eq!(
+            parse_gc_capacity(capacity).unwrap().old_committed_bytes,
+            64192 * 1024
+        );
+    }
+
+    #[test]
+    fn test_parse_gc_stats_without_concurrent_cycles() {
+        // Serial GC
+        let output = "  S0     S1     E      O      M     CCS    YGC     YGCT     FGC    FGCT     CGC    CGCT       GCT   \n  \
+                      0.00   0.00   0.00  32.31  97.34  88.43      1     0.011    33     0.488     -         -     0.500\n";
+        let stats = parse_gc_stats(output).unwrap();
+
+        assert_eq!(stats.collectors.len(), 2);
+        assert_eq!(stats.old_gc_count(), 33);
+    }
+
+    #[test]
+    fn test_parse_compiler_stats() {
+        let output = include_str!("../../../../assets/sample_outputs/jstat_compiler.txt");
+        let stats = parse_compiler_stats(output).unwrap();
+
+        assert_eq!(stats.compiled_methods, 983);
+        assert_eq!(stats.failed_compilations, 0);
+        assert_eq!(stats.invalidated_compilations, 0);
+        assert!((stats.compile_time_seconds - 0.88).abs() < f64::EPSILON);
+        assert!(parse_compiler_stats("Compiled Failed").is_err());
+    }
+
+    #[test]
+    fn test_parse_gc_capacity() {
+        let output = include_str!("../../../../assets/sample_outputs/jstat_gccapacity.txt");
+        let capacity = parse_gc_capacity(output).unwrap();
+
+        assert_eq!(capacity.young_max_bytes, 43648 * 1024);
+        assert_eq!(capacity.young_committed_bytes, 32128 * 1024);
+        assert_eq!(capacity.old_max_bytes, 87424 * 1024);
+        assert_eq!(capacity.old_committed_bytes, 64192 * 1024);
+        // -Xmx128m
+        assert_eq!(capacity.max_heap_bytes(), 128 * 1024 * 1024);
+
+        assert!(parse_gc_capacity("NGCMN NGC\n0.0 1.0").is_err());
+    }
+
+    #[test]
+    fn test_parse_gc_heap() {
+        let output = include_str!("../../../../assets/sample_outputs/jstat_gc.txt");
+        let heap = parse_gc_heap(output).unwrap();
+
+        // Eden 0 + survivors 0 + old 20741.2 KB
+        assert_eq!(heap.used_bytes, (20741.2f64 * 1024.0) as u64);
+        // 3200 + 3200 + 25728 + 64192 KB
+        assert_eq!(heap.committed_bytes, 96320 * 1024);
+        assert_eq!(heap.max_bytes, heap.committed_bytes);
+
+        let names: Vec<&str> = heap.pools.iter().map(|pool| pool.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["Eden", "Survivor", "Old", "Metaspace", "Class Space"]
+        );
+        assert_eq!(heap.pools[1].committed_bytes, 6400 * 1024);
+        assert_eq!(heap.pools[3].used_bytes, (9344.8f64 * 1024.0) as u64);
+
+        assert!(parse_gc_heap("S0C S1C\n1.0 2.0").is_err());
+    }
+
+    #[test]
+    fn test_parse_gc_generations() {
+        let output = include_str!("../../../../assets/sample_outputs/jstat_gc.txt");
+        let pools = parse_gc_generations(output).unwrap();
+
+        assert_eq!(pools.len(), 3);
+        assert_eq!(pools[0].pool_type, PoolType::Eden);
+        assert_eq!(pools[0].committed_bytes, 25728 * 1024);
+        assert_eq!(pools[1].pool_type, PoolType::Survivor);
+        // S0C + S1C
+        assert_eq!(pools[1].committed_bytes, 6400 * 1024);
+        assert_eq!(pools[1].used_bytes, 0);
+        assert_eq!(pools[2].pool_type, PoolType::Old);
+        assert_eq!(pools[2].used_bytes, (20741.2f64 * 1024.0) as u64);
+        assert_eq!(pools[2].committed_bytes, 64192 * 1024);
+
+        assert!(parse_gc_generations("MC MU\n1.0 2.0").is_err());
     }
 
     #[test]
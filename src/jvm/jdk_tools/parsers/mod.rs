@@ -1,3 +1,29 @@
+pub mod gc_log;
 pub mod jcmd;
 pub mod jps;
 pub mod jstat;
+
+use std::num::ParseFloatError;
+
+/// Parses a decimal printed by a JDK tool. The target JVM (for jcmd) and
+/// jstat both format numbers in their own locale, so a JVM started under
+/// e.g. `de_DE` prints `390327,060`. Forcing `LC_ALL=C` on the tools we spawn
+/// wouldn't cover the jcmd side and would mangle non-ASCII thread names, so
+/// both separators are accepted here instead.
+pub(crate) fn parse_decimal(s: &str) -> Result<f64, ParseFloatError> {
+    s.replacen(',', ".", 1).parse::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("497.699"), Ok(497.699));
+        assert_eq!(parse_decimal("497,699"), Ok(497.699));
+        assert_eq!(parse_decimal("12"), Ok(12.0));
+        assert!(parse_decimal("-").is_err());
+        assert!(parse_decimal("1,2,3").is_err());
+    }
+}
@@ -1,29 +1,53 @@
 use crate::error::{AppError, Result};
-use crate::jvm::connector::JvmConnector;
-use crate::jvm::jolokia::types::{JolokiaRequest, JolokiaResponse};
+use crate::jvm::connector::{java_command, JvmConnector};
+use crate::jvm::jolokia::types::{JolokiaRequest, JolokiaResponse, JolokiaTarget};
+use crate::jvm::raw_output::{RawOutput, RawOutputCapture};
 use crate::jvm::types::{
-    ClassInfo, GcStats, HeapInfo, JvmInfo, MemoryPool, PoolType, ThreadInfo, ThreadState,
+    ClassInfo, GcCategory, GcCollectorStat, GcStats, HeapInfo, JvmInfo, MemoryPool, PoolType,
+    ThreadInfo, ThreadState,
 };
 use async_trait::async_trait;
 use chrono::Local;
-use reqwest::Client;
+use reqwest::{Certificate, Client, Identity, RequestBuilder};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::collections::HashMap;
 use std::time::Duration;
 
+/// TLS settings for HTTPS agents behind self-signed or corporate CAs.
+#[derive(Debug, Clone, Default)]
+pub struct JolokiaTls {
+    pub insecure_skip_verify: bool,
+    /// PEM file with extra CA certificates to trust.
+    pub ca_cert_path: Option<String>,
+    /// PEM client certificate and its PKCS#8 PEM key, for mutual TLS.
+    pub client_cert_path: Option<String>,
+    pub client_key_path: Option<String>,
+}
+
+impl JolokiaTls {
+    fn is_default(&self) -> bool {
+        !self.insecure_skip_verify
+            && self.ca_cert_path.is_none()
+            && self.client_cert_path.is_none()
+            && self.client_key_path.is_none()
+    }
+}
+
 pub struct JolokiaConnector {
     url: String,
     client: Client,
     connected: bool,
     username: Option<String>,
     password: Option<String>,
+    target: Option<JolokiaTarget>,
+    use_get: bool,
+    raw_output: RawOutputCapture,
 }
 
 impl JolokiaConnector {
     pub fn new(url: String, username: Option<String>, password: Option<String>) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .unwrap_or_else(|_| Client::new());
+        let client = build_client(&JolokiaTls::default()).unwrap_or_else(|_| Client::new());
 
         Self {
             url,
@@ -31,36 +55,128 @@ impl JolokiaConnector {
             connected: false,
             username,
             password,
+            target: None,
+            use_get: false,
+            raw_output: RawOutputCapture::default(),
         }
     }
 
-    async fn execute_request(&self, request: JolokiaRequest) -> Result<JolokiaResponse> {
-        let mut req_builder = self.client.post(&self.url).json(&request);
+    /// Route requests through a Jolokia agent in proxy mode to the given JMX
+    /// service URL.
+    pub fn with_target(mut self, target: JolokiaTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Rebuilds the HTTP client with `tls`; fails if a certificate or key
+    /// can't be read or parsed.
+    pub fn with_tls(mut self, tls: &JolokiaTls) -> Result<Self> {
+        if !tls.is_default() {
+            self.client = build_client(tls)?;
+        }
+        Ok(self)
+    }
+
+    /// Use the GET protocol for agents that don't accept POST.
+    pub fn with_get_requests(mut self) -> Self {
+        self.use_get = true;
+        self
+    }
+
+    fn request_builder(&self, request: JolokiaRequest) -> RequestBuilder {
+        let builder = if self.use_get {
+            let url = format!("{}/{}", self.url.trim_end_matches('/'), request.get_path());
+            self.client.get(url)
+        } else {
+            let request = request.with_target(self.target.clone());
+            self.client.post(&self.url).json(&request)
+        };
+        self.authorize(builder)
+    }
 
-        if let (Some(username), Some(password)) = (&self.username, &self.password) {
-            req_builder = req_builder.basic_auth(username, Some(password));
+    fn authorize(&self, builder: RequestBuilder) -> RequestBuilder {
+        match (&self.username, &self.password) {
+            (Some(username), Some(password)) => builder.basic_auth(username, Some(password)),
+            _ => builder,
         }
+    }
 
-        let response = req_builder
+    /// `label` names the request in the raw output log, and is only built
+    /// when that log is on.
+    async fn send<T: DeserializeOwned>(
+        &self,
+        builder: RequestBuilder,
+        label: Option<String>,
+    ) -> Result<T> {
+        let body = builder
             .send()
             .await
+            .map_err(|e| AppError::Connection(format!("Jolokia HTTP error: {}", e)))?
+            .text()
+            .await
             .map_err(|e| AppError::Connection(format!("Jolokia HTTP error: {}", e)))?;
 
-        let jolokia_resp: JolokiaResponse = response
-            .json()
-            .await
-            .map_err(|e| AppError::Parse(format!("Failed to parse Jolokia response: {}", e)))?;
-
-        if jolokia_resp.status != 200 {
-            return Err(AppError::Connection(format!(
-                "Jolokia error: {}",
-                jolokia_resp
-                    .error
-                    .unwrap_or_else(|| "Unknown error".to_string())
-            )));
+        if let Some(label) = label {
+            self.raw_output.record(&label, &body);
         }
 
-        Ok(jolokia_resp)
+        serde_json::from_str(&body)
+            .map_err(|e| AppError::Parse(format!("Failed to parse Jolokia response: {}", e)))
+    }
+
+    /// "jolokia read/java.lang:type=Memory/HeapMemoryUsage, ..." when raw
+    /// output is being kept.
+    fn raw_output_label(&self, requests: &[JolokiaRequest]) -> Option<String> {
+        self.raw_output.is_enabled().then(|| {
+            let paths: Vec<String> = requests.iter().map(JolokiaRequest::get_path).collect();
+            format!("jolokia {}", paths.join(", "))
+        })
+    }
+
+    async fn execute_request(&self, request: JolokiaRequest) -> Result<JolokiaResponse> {
+        let label = self.raw_output_label(std::slice::from_ref(&request));
+        let response = self.send(self.request_builder(request), label).await?;
+        check_status(response)
+    }
+
+    /// Sends all `requests` in one round trip and returns the responses in
+    /// the same order. Each response carries its own status, so callers
+    /// decide which failures matter.
+    pub async fn execute_batch(
+        &self,
+        requests: Vec<JolokiaRequest>,
+    ) -> Result<Vec<JolokiaResponse>> {
+        // The GET protocol has no batch form
+        if self.use_get {
+            let mut responses = Vec::with_capacity(requests.len());
+            for request in requests {
+                let label = self.raw_output_label(std::slice::from_ref(&request));
+                responses.push(self.send(self.request_builder(request), label).await?);
+            }
+            return Ok(responses);
+        }
+
+        let count = requests.len();
+        let label = self.raw_output_label(&requests);
+        let requests: Vec<JolokiaRequest> = requests
+            .into_iter()
+            .map(|request| request.with_target(self.target.clone()))
+            .collect();
+        let responses: Vec<JolokiaResponse> = self
+            .send(
+                self.authorize(self.client.post(&self.url).json(&requests)),
+                label,
+            )
+            .await?;
+
+        if responses.len() != count {
+            return Err(AppError::Parse(format!(
+                "Jolokia answered {} of {} batched requests",
+                responses.len(),
+                count
+            )));
+        }
+        Ok(responses)
     }
 
     async fn read_attribute(&self, mbean: &str, attribute: &str) -> Result<Value> {
@@ -69,6 +185,16 @@ impl JolokiaConnector {
         Ok(response.value)
     }
 
+    async fn all_thread_ids(&self) -> Result<Vec<u64>> {
+        let ids = self
+            .read_attribute("java.lang:type=Threading", "AllThreadIds")
+            .await?;
+        Ok(ids
+            .as_array()
+            .map(|ids| ids.iter().filter_map(|id| id.as_u64()).collect())
+            .unwrap_or_default())
+    }
+
     async fn exec_operation(
         &self,
         mbean: &str,
@@ -105,15 +231,21 @@ impl JvmConnector for JolokiaConnector {
     }
 
     async fn get_jvm_info(&self) -> Result<JvmInfo> {
-        let runtime_name = self
-            .read_attribute("java.lang:type=Runtime", "Name")
-            .await?;
-        let vm_version = self
-            .read_attribute("java.lang:type=Runtime", "VmVersion")
-            .await?;
-        let uptime_ms = self
-            .read_attribute("java.lang:type=Runtime", "Uptime")
+        let responses = self
+            .execute_batch(vec![
+                JolokiaRequest::read("java.lang:type=Runtime", "Name"),
+                JolokiaRequest::read("java.lang:type=Runtime", "VmVersion"),
+                JolokiaRequest::read("java.lang:type=Runtime", "Uptime"),
+            ])
             .await?;
+        let mut values = responses
+            .into_iter()
+            .map(|response| check_status(response).map(|response| response.value));
+        let (runtime_name, vm_version, uptime_ms) = (
+            values.next().unwrap()?,
+            values.next().unwrap()?,
+            values.next().unwrap()?,
+        );
 
         let runtime_str = runtime_name.as_str().unwrap_or("");
         let pid = runtime_str
@@ -128,73 +260,99 @@ impl JvmConnector for JolokiaConnector {
             version: vm_version.as_str().unwrap_or("Unknown").to_string(),
             uptime_seconds: uptime_ms.as_u64().unwrap_or(0) / 1000,
             vm_flags: vec![],
+            container_memory_limit: None,
         })
     }
 
     async fn get_heap_info(&self) -> Result<HeapInfo> {
-        let heap_mem = self
-            .read_attribute("java.lang:type=Memory", "HeapMemoryUsage")
-            .await?;
+        let mut responses = self
+            .execute_batch(vec![
+                JolokiaRequest::read("java.lang:type=Memory", "HeapMemoryUsage"),
+                JolokiaRequest::read("java.lang:type=MemoryPool,name=*", "Usage"),
+            ])
+            .await?
+            .into_iter();
+        let heap_mem = check_status(responses.next().unwrap())?.value;
 
         let used = heap_mem["used"].as_u64().unwrap_or(0);
         let max = heap_mem["max"].as_u64().unwrap_or(0);
         let committed = heap_mem["committed"].as_u64().unwrap_or(0);
 
-        let pools = vec![MemoryPool {
-            name: "Remote Heap".to_string(),
-            pool_type: PoolType::Old,
-            used_bytes: used,
-            max_bytes: max,
-            committed_bytes: committed,
-        }];
+        // Agents that restrict MBean access may refuse the pool pattern
+        let mut pools = responses
+            .next()
+            .and_then(|response| check_status(response).ok())
+            .map(|response| parse_memory_pools(&response.value))
+            .unwrap_or_default();
+        if pools.is_empty() {
+            pools.push(MemoryPool {
+                name: "Remote Heap".to_string(),
+                pool_type: PoolType::Old,
+                used_bytes: used,
+                max_bytes: max,
+                committed_bytes: committed,
+            });
+        }
 
         Ok(HeapInfo {
             used_bytes: used,
             max_bytes: max,
             committed_bytes: committed,
             pools,
+            timestamp: Local::now(),
         })
     }
 
     async fn get_gc_stats(&self) -> Result<GcStats> {
-        let young_count = self
-            .read_attribute("java.lang:type=GarbageCollector,name=*", "CollectionCount")
-            .await
-            .unwrap_or(Value::from(0))
-            .as_u64()
-            .unwrap_or(0);
-
-        let young_time = self
-            .read_attribute("java.lang:type=GarbageCollector,name=*", "CollectionTime")
-            .await
-            .unwrap_or(Value::from(0))
-            .as_u64()
-            .unwrap_or(0);
+        let collectors = "java.lang:type=GarbageCollector,name=*";
+        let mut responses = self
+            .execute_batch(vec![
+                JolokiaRequest::read(collectors, "CollectionCount"),
+                JolokiaRequest::read(collectors, "CollectionTime"),
+            ])
+            .await?
+            .into_iter()
+            .map(|response| check_status(response).map(|response| response.value));
+        let (counts, times) = (responses.next().unwrap()?, responses.next().unwrap()?);
 
         Ok(GcStats {
-            young_gc_count: young_count,
-            young_gc_time_ms: young_time,
-            old_gc_count: 0,
-            old_gc_time_ms: 0,
+            collectors: parse_gc_collectors(&counts, &times),
             timestamp: Local::now(),
         })
     }
 
     async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
-        let thread_count = self
-            .read_attribute("java.lang:type=Threading", "ThreadCount")
-            .await?
-            .as_u64()
-            .unwrap_or(0);
+        let ids = self.all_thread_ids().await?;
+        let infos = self
+            .exec_operation(
+                "java.lang:type=Threading",
+                "getThreadInfo([J)",
+                vec![Value::from(ids)],
+            )
+            .await?;
 
-        let threads = (0..thread_count.min(50))
-            .map(|i| ThreadInfo {
-                id: i,
-                name: format!("Thread-{}", i),
-                state: ThreadState::Runnable,
-                stack_trace: vec![],
+        // Threads that died since AllThreadIds was read come back as null
+        let threads = infos
+            .as_array()
+            .map(|infos| {
+                infos
+                    .iter()
+                    .filter_map(|info| {
+                        Some(ThreadInfo {
+                            id: info.get("threadId")?.as_u64()?,
+                            name: info.get("threadName")?.as_str()?.to_string(),
+                            state: parse_thread_state(info.get("threadState")?.as_str()?),
+                            stack_trace: vec![],
+                            cpu_time_ns: None,
+                            // Part of ThreadInfo since Java 9
+                            daemon: info.get("daemon").and_then(Value::as_bool),
+                            // This overload leaves out locked monitors
+                            locks: Vec::new(),
+                        })
+                    })
+                    .collect()
             })
-            .collect();
+            .unwrap_or_default();
 
         Ok(threads)
     }
@@ -214,9 +372,389 @@ impl JvmConnector for JolokiaConnector {
         }])
     }
 
+    async fn get_deadlocked_threads(&self) -> Result<Vec<u64>> {
+        let ids = self
+            .exec_operation("java.lang:type=Threading", "findDeadlockedThreads", vec![])
+            .await?;
+
+        // findDeadlockedThreads returns null when there is no deadlock
+        Ok(ids
+            .as_array()
+            .map(|ids| ids.iter().filter_map(|id| id.as_u64()).collect())
+            .unwrap_or_default())
+    }
+
+    async fn get_thread_cpu_times(&self) -> Result<HashMap<u64, u64>> {
+        let ids = self.all_thread_ids().await?;
+        let times = self
+            .exec_operation(
+                "java.lang:type=Threading",
+                "getThreadCpuTime([J)",
+                vec![Value::from(ids.clone())],
+            )
+            .await?;
+
+        // -1 marks threads that are gone or have CPU time measurement disabled
+        Ok(times
+            .as_array()
+            .map(|times| {
+                ids.into_iter()
+                    .zip(times)
+                    .filter_map(|(id, time)| {
+                        time.as_i64().filter(|t| *t >= 0).map(|t| (id, t as u64))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn get_system_properties(&self) -> Result<Vec<(String, String)>> {
+        let properties = self
+            .read_attribute("java.lang:type=Runtime", "SystemProperties")
+            .await?;
+        Ok(parse_system_properties(&properties))
+    }
+
+    async fn get_process_command_line(&self) -> Result<String> {
+        let arguments = self
+            .read_attribute("java.lang:type=Runtime", "InputArguments")
+            .await?;
+        let properties = self.get_system_properties().await?;
+
+        let mut parts = parse_input_arguments(&arguments);
+        parts.extend(java_command(&properties).map(str::to_string));
+        if parts.is_empty() {
+            return Err(AppError::Parse(
+                "Runtime MBean reported no input arguments".to_string(),
+            ));
+        }
+        Ok(parts.join(" "))
+    }
+
     async fn trigger_gc(&self) -> Result<()> {
         self.exec_operation("java.lang:type=Memory", "gc", vec![])
             .await?;
         Ok(())
     }
+
+    fn capture_raw_output(&mut self) {
+        self.raw_output.enable();
+    }
+
+    fn last_raw_output(&self) -> Option<Vec<RawOutput>> {
+        self.raw_output.latest()
+    }
+}
+
+fn build_client(tls: &JolokiaTls) -> Result<Client> {
+    let mut builder = Client::builder()
+        .timeout(Duration::from_secs(10))
+        .danger_accept_invalid_certs(tls.insecure_skip_verify);
+
+    if let Some(path) = &tls.ca_cert_path {
+        let certificates = Certificate::from_pem_bundle(&read_pem(path, "CA certificate")?)
+            .map_err(|e| AppError::Config(format!("Invalid CA certificate {}: {}", path, e)))?;
+        for certificate in certificates {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+
+    if let (Some(cert_path), Some(key_path)) = (&tls.client_cert_path, &tls.client_key_path) {
+        let cert = read_pem(cert_path, "client certificate")?;
+        let key = read_pem(key_path, "client key")?;
+        let identity = Identity::from_pkcs8_pem(&cert, &key).map_err(|e| {
+            AppError::Config(format!(
+                "Invalid client certificate {} or key {} (the key must be PKCS#8 PEM): {}",
+                cert_path, key_path, e
+            ))
+        })?;
+        builder = builder.identity(identity);
+    }
+
+    builder
+        .build()
+        .map_err(|e| AppError::Connection(format!("Failed to create HTTP client: {}", e)))
+}
+
+fn read_pem(path: &str, what: &str) -> Result<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| AppError::Config(format!("Failed to read {} {}: {}", what, path, e)))
+}
+
+fn check_status(response: JolokiaResponse) -> Result<JolokiaResponse> {
+    if response.status != 200 {
+        return Err(AppError::Connection(format!(
+            "Jolokia error: {}",
+            response
+                .error
+                .unwrap_or_else(|| "Unknown error".to_string())
+        )));
+    }
+    Ok(response)
+}
+
+/// A pattern read of `MemoryPool` `Usage` comes back keyed by MBean name,
+/// e.g. `java.lang:name=G1 Eden Space,type=MemoryPool`.
+fn parse_memory_pools(value: &Value) -> Vec<MemoryPool> {
+    let mut pools: Vec<MemoryPool> = value
+        .as_object()
+        .map(|mbeans| {
+            mbeans
+                .iter()
+                .filter_map(|(mbean, attributes)| {
+                    let name = mbean_name(mbean)?;
+                    let usage = attributes.get("Usage")?;
+                    Some(MemoryPool {
+                        name: name.to_string(),
+                        pool_type: pool_type(name),
+                        used_bytes: usage["used"].as_u64().unwrap_or(0),
+                        // -1 marks an undefined maximum
+                        max_bytes: usage["max"].as_u64().unwrap_or(0),
+                        committed_bytes: usage["committed"].as_u64().unwrap_or(0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    pools.sort_by(|a, b| a.name.cmp(&b.name));
+    pools
+}
+
+fn pool_type(name: &str) -> PoolType {
+    if name.contains("Eden") {
+        PoolType::Eden
+    } else if name.contains("Survivor") {
+        PoolType::Survivor
+    } else if name.contains("Old") || name.contains("Tenured") {
+        PoolType::Old
+    } else if name.contains("Metaspace") || name.contains("Class Space") {
+        PoolType::Metaspace
+    } else if name.contains("Code") {
+        PoolType::CodeCache
+    } else {
+        PoolType::Other
+    }
+}
+
+/// Pattern reads of `CollectionCount` and `CollectionTime`, each keyed by
+/// MBean name, e.g. `java.lang:name=G1 Young Generation,type=GarbageCollector`.
+fn parse_gc_collectors(counts: &Value, times: &Value) -> Vec<GcCollectorStat> {
+    let mut collectors: Vec<GcCollectorStat> = counts
+        .as_object()
+        .map(|mbeans| {
+            mbeans
+                .iter()
+                .filter_map(|(mbean, attributes)| {
+                    let name = mbean_name(mbean)?;
+                    Some(GcCollectorStat {
+                        name: name.to_string(),
+                        count: attributes["CollectionCount"].as_u64().unwrap_or(0),
+                        time_ms: times[mbean]["CollectionTime"].as_u64().unwrap_or(0),
+                        category: GcCategory::from_collector_name(name),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    collectors.sort_by(|a, b| a.name.cmp(&b.name));
+    collectors
+}
+
+fn mbean_name(mbean: &str) -> Option<&str> {
+    mbean
+        .split_once(':')?
+        .1
+        .split(',')
+        .find_map(|property| property.strip_prefix("name="))
+}
+
+fn parse_thread_state(state: &str) -> ThreadState {
+    match state {
+        "BLOCKED" => ThreadState::Blocked,
+        "WAITING" => ThreadState::Waiting,
+        "TIMED_WAITING" => ThreadState::TimedWaiting,
+        "TERMINATED" => ThreadState::Terminated,
+        "NEW" => ThreadState::New,
+        _ => ThreadState::Runnable,
+    }
+}
+
+/// `InputArguments` is a plain string array: the JVM's own arguments,
+/// without the main class or program arguments.
+fn parse_input_arguments(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|arguments| {
+            arguments
+                .iter()
+                .filter_map(|argument| argument.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `SystemProperties` is TabularData, which Jolokia renders as an object keyed
+/// by property name whose entries hold `key` and `value`.
+fn parse_system_properties(value: &Value) -> Vec<(String, String)> {
+    let mut properties: Vec<(String, String)> = value
+        .as_object()
+        .map(|entries| {
+            entries
+                .iter()
+                .map(|(key, entry)| {
+                    let value = entry
+                        .get("value")
+                        .unwrap_or(entry)
+                        .as_str()
+                        .unwrap_or_default()
+                        .to_string();
+                    (key.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    properties.sort_by(|a, b| a.0.cmp(&b.0));
+    properties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tls_reports_unreadable_certificates() {
+        let tls = JolokiaTls {
+            ca_cert_path: Some("/nonexistent/corp-ca.pem".to_string()),
+            ..JolokiaTls::default()
+        };
+        let err = build_client(&tls).unwrap_err().to_string();
+        assert!(
+            err.contains("CA certificate /nonexistent/corp-ca.pem"),
+            "{}",
+            err
+        );
+
+        let insecure = JolokiaTls {
+            insecure_skip_verify: true,
+            ..JolokiaTls::default()
+        };
+        assert!(build_client(&insecure).is_ok());
+    }
+
+    #[test]
+    fn test_parse_system_properties() {
+        let value = serde_json::json!({
+            "user.timezone": {"key": "user.timezone", "value": "UTC"},
+            "file.encoding": {"key": "file.encoding", "value": "UTF-8"},
+            "flat": "value",
+        });
+
+        assert_eq!(
+            parse_system_properties(&value),
+            vec![
+                ("file.encoding".to_string(), "UTF-8".to_string()),
+                ("flat".to_string(), "value".to_string()),
+                ("user.timezone".to_string(), "UTC".to_string()),
+            ]
+        );
+        assert!(parse_system_properties(&Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_parse_input_arguments() {
+        let value = serde_json::json!(["-Xmx512m", "-Dapp.env=prod"]);
+        assert_eq!(
+            parse_input_arguments(&value),
+            vec!["-Xmx512m".to_string(), "-Dapp.env=prod".to_string()]
+        );
+        assert!(parse_input_arguments(&Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_parse_memory_pools() {
+        let value = serde_json::json!({
+            "java.lang:name=G1 Old Gen,type=MemoryPool": {
+                "Usage": {"used": 300, "max": 1000, "committed": 500, "init": 0}
+            },
+            "java.lang:name=G1 Eden Space,type=MemoryPool": {
+                "Usage": {"used": 100, "max": -1, "committed": 200, "init": 0}
+            },
+            "java.lang:type=MemoryPool,name=Metaspace": {
+                "Usage": {"used": 50, "max": -1, "committed": 60, "init": 0}
+            },
+        });
+
+        let pools = parse_memory_pools(&value);
+        let names: Vec<&str> = pools.iter().map(|pool| pool.name.as_str()).collect();
+        assert_eq!(names, vec!["G1 Eden Space", "G1 Old Gen", "Metaspace"]);
+        assert!(matches!(pools[0].pool_type, PoolType::Eden));
+        assert_eq!(pools[0].max_bytes, 0);
+        assert!(matches!(pools[1].pool_type, PoolType::Old));
+        assert_eq!(pools[1].used_bytes, 300);
+        assert!(matches!(pools[2].pool_type, PoolType::Metaspace));
+    }
+
+    #[test]
+    fn test_parse_gc_collectors() {
+        let counts = serde_json::json!({
+            "java.lang:name=G1 Young Generation,type=GarbageCollector": {"CollectionCount": 42},
+            "java.lang:name=G1 Old Generation,type=GarbageCollector": {"CollectionCount": 1},
+            "java.lang:name=G1 Concurrent GC,type=GarbageCollector": {"CollectionCount": 7},
+        });
+        let times = serde_json::json!({
+            "java.lang:name=G1 Young Generation,type=GarbageCollector": {"CollectionTime": 420},
+            "java.lang:name=G1 Old Generation,type=GarbageCollector": {"CollectionTime": 90},
+            "java.lang:name=G1 Concurrent GC,type=GarbageCollector": {"CollectionTime": 30},
+        });
+
+        let stats = GcStats {
+            collectors: parse_gc_collectors(&counts, &times),
+            timestamp: Local::now(),
+        };
+        let names: Vec<&str> = stats.collectors.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "G1 Concurrent GC",
+                "G1 Old Generation",
+                "G1 Young Generation"
+            ]
+        );
+        assert_eq!(stats.collectors[0].category, GcCategory::Concurrent);
+        assert_eq!(stats.young_gc_count(), 42);
+        assert_eq!(stats.young_gc_time_ms(), 420);
+        assert_eq!(stats.old_gc_count(), 1);
+        assert_eq!(stats.old_gc_time_ms(), 90);
+
+        for (name, category) in [
+            ("PS Scavenge", GcCategory::Young),
+            ("PS MarkSweep", GcCategory::Old),
+            ("ZGC Cycles", GcCategory::Concurrent),
+            ("ZGC Pauses", GcCategory::Young),
+            ("ZGC Major Pauses", GcCategory::Old),
+            ("Shenandoah Cycles", GcCategory::Concurrent),
+        ] {
+            assert_eq!(GcCategory::from_collector_name(name), category, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_batch_response_with_error_entry() {
+        let responses: Vec<JolokiaResponse> = serde_json::from_value(serde_json::json!([
+            {"status": 200, "timestamp": 1, "request": {}, "value": "12@host"},
+            {"status": 404, "error_type": "javax.management.InstanceNotFoundException",
+             "error": "No MBean found"},
+        ]))
+        .unwrap();
+
+        let mut responses = responses.into_iter();
+        assert_eq!(
+            check_status(responses.next().unwrap()).unwrap().value,
+            "12@host"
+        );
+        assert!(check_status(responses.next().unwrap()).is_err());
+    }
 }
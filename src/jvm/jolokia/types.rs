@@ -12,12 +12,27 @@ pub struct JolokiaRequest {
     pub operation: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub arguments: Option<Vec<Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<JolokiaTarget>,
+}
+
+/// JMX service a Jolokia agent in proxy mode should forward the request to.
+#[derive(Debug, Clone, Serialize)]
+pub struct JolokiaTarget {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct JolokiaResponse {
     pub status: u32,
+    // Error responses carry neither
+    #[serde(default)]
     pub timestamp: u64,
+    #[serde(default)]
     pub request: Value,
     #[serde(default)]
     pub value: Value,
@@ -35,6 +50,7 @@ impl JolokiaRequest {
             attribute: Some(attribute.to_string()),
             operation: None,
             arguments: None,
+            target: None,
         }
     }
 
@@ -45,6 +61,108 @@ impl JolokiaRequest {
             attribute: None,
             operation: Some(operation.to_string()),
             arguments: Some(arguments),
+            target: None,
+        }
+    }
+
+    pub fn with_target(mut self, target: Option<JolokiaTarget>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Path for the GET protocol, e.g. `read/java.lang:type=Memory/HeapMemoryUsage`,
+    /// with each part escaped the way the Jolokia agent expects.
+    pub fn get_path(&self) -> String {
+        let mut parts = vec![self.request_type.clone(), self.mbean.clone()];
+        if let Some(ref attribute) = self.attribute {
+            parts.push(attribute.clone());
+        }
+        if let Some(ref operation) = self.operation {
+            parts.push(operation.clone());
+        }
+        for argument in self.arguments.iter().flatten() {
+            parts.push(match argument {
+                Value::String(s) => s.clone(),
+                Value::Null => "[null]".to_string(),
+                other => other.to_string(),
+            });
         }
+
+        parts
+            .iter()
+            .map(|part| escape_path_part(part))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Jolokia escapes `!` and `/` with a leading `!`; everything outside a
+/// conservative safe set is then percent-encoded.
+fn escape_path_part(part: &str) -> String {
+    let escaped = part.replace('!', "!!").replace('/', "!/");
+    let mut encoded = String::with_capacity(escaped.len());
+    for byte in escaped.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~'
+            | b':'
+            | b'='
+            | b','
+            | b'!'
+            | b'/'
+            | b'*'
+            | b'@' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_request_serializes_target() {
+        let request = JolokiaRequest::read("java.lang:type=Memory", "HeapMemoryUsage").with_target(
+            Some(JolokiaTarget {
+                url: "service:jmx:rmi:///jndi/rmi://app:9010/jmxrmi".to_string(),
+                user: Some("monitor".to_string()),
+                password: None,
+            }),
+        );
+
+        let json = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            json["target"]["url"],
+            "service:jmx:rmi:///jndi/rmi://app:9010/jmxrmi"
+        );
+        assert_eq!(json["target"]["user"], "monitor");
+        assert!(json["target"].get("password").is_none());
+
+        let plain = serde_json::to_value(JolokiaRequest::read("a:b=c", "D")).unwrap();
+        assert!(plain.get("target").is_none());
+    }
+
+    #[test]
+    fn test_get_path() {
+        assert_eq!(
+            JolokiaRequest::read("java.lang:type=Memory", "HeapMemoryUsage").get_path(),
+            "read/java.lang:type=Memory/HeapMemoryUsage"
+        );
+        assert_eq!(
+            JolokiaRequest::exec("java.lang:type=Memory", "gc", vec![]).get_path(),
+            "exec/java.lang:type=Memory/gc"
+        );
+        assert_eq!(
+            JolokiaRequest::read("java.lang:type=MemoryPool,name=PS Eden/Space!", "Usage")
+                .get_path(),
+            "read/java.lang:type=MemoryPool,name=PS%20Eden!/Space!!/Usage"
+        );
     }
 }
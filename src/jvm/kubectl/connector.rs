@@ -0,0 +1,120 @@
+use crate::error::{AppError, Result};
+use crate::jvm::jdk_tools::executor::execute_command;
+use crate::jvm::jdk_tools::JdkToolsError;
+use crate::jvm::remote_jdk::connector::{RemoteJdkConnector, ToolTransport};
+use async_trait::async_trait;
+use std::time::Duration;
+
+const KUBECTL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Runs jcmd/jstat inside a Kubernetes pod via `kubectl exec`.
+pub type KubectlConnector = RemoteJdkConnector<KubectlTransport>;
+
+pub struct KubectlTransport {
+    pod: String,
+    container: Option<String>,
+    namespace: Option<String>,
+}
+
+impl KubectlConnector {
+    pub fn new(
+        pod: String,
+        container: Option<String>,
+        namespace: Option<String>,
+        pid: u32,
+    ) -> Self {
+        let transport = KubectlTransport {
+            pod,
+            container,
+            namespace,
+        };
+        Self::from_transport(transport, pid)
+    }
+}
+
+impl KubectlTransport {
+    fn exec_args<'a>(&'a self, command: &[&'a str]) -> Vec<&'a str> {
+        let mut args = Vec::new();
+        if let Some(ref namespace) = self.namespace {
+            args.extend(["-n", namespace.as_str()]);
+        }
+        args.extend(["exec", self.pod.as_str()]);
+        if let Some(ref container) = self.container {
+            args.extend(["-c", container.as_str()]);
+        }
+        args.push("--");
+        args.extend_from_slice(command);
+        args
+    }
+}
+
+#[async_trait]
+impl ToolTransport for KubectlTransport {
+    async fn run(&self, command: &[&str]) -> Result<String> {
+        let args = self.exec_args(command);
+        let output = execute_command("kubectl", &args, Some(KUBECTL_TIMEOUT))
+            .await
+            .map_err(|e| match e {
+                JdkToolsError::ExecutionFailed { ref source, .. }
+                    if source.kind() == std::io::ErrorKind::NotFound =>
+                {
+                    AppError::Connection("kubectl is not available in PATH".to_string())
+                }
+                other => AppError::JdkTools(other),
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::Connection(format!(
+                "kubectl exec failed: {}",
+                stderr.trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn label(&self) -> String {
+        format!("Pod JVM ({})", self.pod)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exec_args() {
+        let transport = KubectlTransport {
+            pod: "orders-7d9f".to_string(),
+            container: Some("app".to_string()),
+            namespace: Some("prod".to_string()),
+        };
+
+        assert_eq!(
+            transport.exec_args(&["jcmd", "1", "GC.heap_info"]),
+            vec![
+                "-n",
+                "prod",
+                "exec",
+                "orders-7d9f",
+                "-c",
+                "app",
+                "--",
+                "jcmd",
+                "1",
+                "GC.heap_info"
+            ]
+        );
+
+        let transport = KubectlTransport {
+            pod: "orders-7d9f".to_string(),
+            container: None,
+            namespace: None,
+        };
+        assert_eq!(
+            transport.exec_args(&["jstat", "-gc", "1"]),
+            vec!["exec", "orders-7d9f", "--", "jstat", "-gc", "1"]
+        );
+    }
+}
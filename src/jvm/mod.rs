@@ -1,6 +1,12 @@
+pub mod cgroup;
 pub mod connector;
 pub mod discovery;
+pub mod docker;
 pub mod jdk_tools;
 pub mod jolokia;
+pub mod kubectl;
+pub mod raw_output;
+pub mod remote_jdk;
+pub mod replay;
 pub mod ssh_jdk;
 pub mod types;
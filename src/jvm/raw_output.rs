@@ -0,0 +1,142 @@
+use chrono::{DateTime, Local};
+use std::process::Output;
+use std::sync::Mutex;
+
+/// Distinct commands kept; polling only runs a handful, the rest are
+/// one-off diagnostic commands.
+const MAX_ENTRIES: usize = 16;
+
+/// What one jcmd/jstat run or Jolokia request returned, before parsing.
+#[derive(Debug, Clone)]
+pub struct RawOutput {
+    pub command: String,
+    pub output: String,
+    pub captured_at: DateTime<Local>,
+}
+
+/// Latest output per command, most recent first.
+#[derive(Debug, Default)]
+pub struct RawOutputLog {
+    entries: Vec<RawOutput>,
+}
+
+impl RawOutputLog {
+    pub fn record(&mut self, command: String, output: String) {
+        self.entries.retain(|entry| entry.command != command);
+        self.entries.insert(
+            0,
+            RawOutput {
+                command,
+                output,
+                captured_at: Local::now(),
+            },
+        );
+        self.entries.truncate(MAX_ENTRIES);
+    }
+
+    pub fn entries(&self) -> &[RawOutput] {
+        &self.entries
+    }
+}
+
+/// A connector's raw output log. Off until [`Self::enable`] (`--debug-raw`)
+/// so polling doesn't copy every tool's stdout.
+#[derive(Debug, Default)]
+pub struct RawOutputCapture {
+    log: Option<Mutex<RawOutputLog>>,
+}
+
+impl RawOutputCapture {
+    pub fn enable(&mut self) {
+        if self.log.is_none() {
+            self.log = Some(Mutex::default());
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.log.is_some()
+    }
+
+    /// Stores `output` as the latest for `command`, when capture is on.
+    pub fn record(&self, command: &str, output: &str) {
+        if let Some(Ok(mut log)) = self.log.as_ref().map(Mutex::lock) {
+            log.record(command.to_string(), output.to_string());
+        }
+    }
+
+    /// Records a tool run's stdout, followed by its stderr if it wrote any.
+    pub fn record_process(&self, command: &str, output: &Output) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.stderr.is_empty() {
+            text.push_str("\n[stderr]\n");
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+        }
+        self.record(command, &text);
+    }
+
+    /// Latest output per command, most recent first; `None` while capture is off.
+    pub fn latest(&self) -> Option<Vec<RawOutput>> {
+        let log = self.log.as_ref()?.lock().ok()?;
+        Some(log.entries().to_vec())
+    }
+}
+
+/// One block per command for the pager.
+pub fn format_outputs(outputs: &[RawOutput]) -> String {
+    if outputs.is_empty() {
+        return "No tool output captured yet".to_string();
+    }
+    outputs
+        .iter()
+        .map(|entry| {
+            format!(
+                "=== {} ({}) ===\n{}",
+                entry.command,
+                entry.captured_at.format("%H:%M:%S"),
+                entry.output.trim_end()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_keeps_latest_per_command() {
+        let mut log = RawOutputLog::default();
+        log.record("jstat -gc 1".to_string(), "old".to_string());
+        log.record("jcmd 1 VM.uptime".to_string(), "1.0 s".to_string());
+        log.record("jstat -gc 1".to_string(), "new".to_string());
+
+        let commands: Vec<&str> = log.entries().iter().map(|e| e.command.as_str()).collect();
+        assert_eq!(commands, vec!["jstat -gc 1", "jcmd 1 VM.uptime"]);
+        assert_eq!(log.entries()[0].output, "new");
+
+        for i in 0..MAX_ENTRIES + 4 {
+            log.record(format!("jcmd 1 cmd{}", i), String::new());
+        }
+        assert_eq!(log.entries().len(), MAX_ENTRIES);
+
+        let text = format_outputs(&log.entries()[..1]);
+        assert!(text.starts_with(&format!("=== jcmd 1 cmd{} (", MAX_ENTRIES + 3)));
+    }
+
+    #[test]
+    fn test_capture_is_off_until_enabled() {
+        let mut capture = RawOutputCapture::default();
+        capture.record("jstat -gc 1", "ignored");
+        assert!(capture.latest().is_none());
+
+        capture.enable();
+        capture.record("jstat -gc 1", "kept");
+        let latest = capture.latest().unwrap();
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].output, "kept");
+    }
+}
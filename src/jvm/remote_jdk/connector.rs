@@ -0,0 +1,285 @@
+use crate::error::{AppError, Result};
+use crate::jvm::connector::{java_command, non_empty_counters, JvmConnector};
+use crate::jvm::jdk_tools::parsers::{jcmd, jstat};
+use crate::jvm::raw_output::{RawOutput, RawOutputCapture};
+use crate::jvm::types::{ClassInfo, GcStats, HeapInfo, JvmInfo, ThreadInfo};
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+/// Where jcmd and jstat run when they can't run locally: over SSH, or
+/// inside a pod or container.
+#[async_trait]
+pub trait ToolTransport: Send + Sync {
+    /// Opens the session, for transports that keep one.
+    async fn open(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) {}
+
+    /// Runs `command`, tool name first, next to the JVM and returns its stdout.
+    async fn run(&self, command: &[&str]) -> Result<String>;
+
+    /// Shown in place of the main class, e.g. `Pod JVM (orders-7d9f)`.
+    fn label(&self) -> String;
+}
+
+/// Collects metrics by running jcmd and jstat through a [`ToolTransport`].
+pub struct RemoteJdkConnector<T> {
+    transport: T,
+    pid: u32,
+    connected: bool,
+    raw_output: RawOutputCapture,
+}
+
+impl<T: ToolTransport> RemoteJdkConnector<T> {
+    pub fn from_transport(transport: T, pid: u32) -> Self {
+        Self {
+            transport,
+            pid,
+            connected: false,
+            raw_output: RawOutputCapture::default(),
+        }
+    }
+
+    async fn run(&self, command: &[&str]) -> Result<String> {
+        let output = self.transport.run(command).await?;
+        self.raw_output.record(&command.join(" "), &output);
+        Ok(output)
+    }
+
+    async fn execute_jcmd(&self, command: &str) -> Result<String> {
+        let pid = self.pid.to_string();
+        let mut args = vec!["jcmd", pid.as_str()];
+        args.extend(command.split_whitespace());
+        self.run(&args).await
+    }
+
+    async fn execute_jstat(&self, option: &str) -> Result<String> {
+        self.run(&["jstat", option, &self.pid.to_string()]).await
+    }
+}
+
+#[async_trait]
+impl<T: ToolTransport> JvmConnector for RemoteJdkConnector<T> {
+    async fn connect(&mut self, pid: u32) -> Result<()> {
+        self.transport.open().await?;
+        self.run(&["jcmd", &pid.to_string(), "VM.version"]).await?;
+        self.pid = pid;
+        self.connected = true;
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        self.transport.close().await;
+        self.connected = false;
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.connected
+    }
+
+    async fn reconnect(&mut self) -> Result<()> {
+        self.disconnect().await?;
+        self.connect(self.pid).await
+    }
+
+    async fn get_jvm_info(&self) -> Result<JvmInfo> {
+        let vm_version_output = self.execute_jcmd("VM.version").await?;
+        let uptime_output = self.execute_jcmd("VM.uptime").await?;
+        let flags_output = self.execute_jcmd("VM.flags").await?;
+
+        let version = jcmd::parse_jvm_version(&vm_version_output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse VM version: {}", e)))?;
+        let uptime_seconds = jcmd::parse_vm_uptime(&uptime_output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse uptime: {}", e)))?;
+        let vm_flags = jcmd::parse_vm_flags(&flags_output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse VM flags: {}", e)))?;
+
+        Ok(JvmInfo {
+            pid: self.pid,
+            main_class: self.transport.label(),
+            version,
+            uptime_seconds,
+            vm_flags,
+            container_memory_limit: None,
+        })
+    }
+
+    async fn get_heap_info(&self) -> Result<HeapInfo> {
+        let output = self.execute_jcmd("GC.heap_info").await?;
+        jcmd::parse_heap_info(&output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse heap info: {}", e)))
+    }
+
+    async fn get_gc_stats(&self) -> Result<GcStats> {
+        let output = self.execute_jstat("-gcutil").await?;
+        jstat::parse_gc_stats(&output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse GC stats: {}", e)))
+    }
+
+    async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
+        Ok(self.get_threads_and_deadlocks().await?.0)
+    }
+
+    async fn get_class_histogram(&self) -> Result<Vec<ClassInfo>> {
+        let output = self.execute_jcmd("GC.class_histogram").await?;
+        jcmd::parse_class_histogram(&output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse class histogram: {}", e)))
+    }
+
+    async fn get_deadlocked_threads(&self) -> Result<Vec<u64>> {
+        Ok(self.get_threads_and_deadlocks().await?.1)
+    }
+
+    async fn get_threads_and_deadlocks(&self) -> Result<(Vec<ThreadInfo>, Vec<u64>)> {
+        let output = self.execute_jcmd("Thread.print").await?;
+        jcmd::parse_thread_print(&output)
+            .map_err(|e| AppError::Parse(format!("Failed to parse thread dump: {}", e)))
+    }
+
+    async fn get_process_command_line(&self) -> Result<String> {
+        let output = self.execute_jcmd("VM.command_line").await?;
+        match jcmd::parse_command_line(&output) {
+            Ok(command_line) => Ok(command_line),
+            Err(e) => java_command(&self.get_system_properties().await?)
+                .map(str::to_string)
+                .ok_or(AppError::Parse(e)),
+        }
+    }
+
+    async fn get_system_properties(&self) -> Result<Vec<(String, String)>> {
+        let output = self.execute_jcmd("VM.system_properties").await?;
+        Ok(jcmd::parse_system_properties(&output))
+    }
+
+    async fn run_diagnostic(&self, command: &str) -> Result<String> {
+        self.execute_jcmd(command).await
+    }
+
+    async fn get_perf_counters(&self) -> Result<HashMap<String, i64>> {
+        let output = self.execute_jcmd("PerfCounter.print").await?;
+        non_empty_counters(jcmd::parse_perf_counters(&output))
+    }
+
+    async fn trigger_gc(&self) -> Result<()> {
+        self.execute_jcmd("GC.run").await?;
+        Ok(())
+    }
+
+    fn capture_raw_output(&mut self) {
+        self.raw_output.enable();
+    }
+
+    fn last_raw_output(&self) -> Option<Vec<RawOutput>> {
+        self.raw_output.latest()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Answers with the sample outputs and remembers what it was asked to run.
+    #[derive(Default)]
+    struct SampleTransport {
+        commands: Mutex<Vec<String>>,
+    }
+
+    impl SampleTransport {
+        fn commands(&self) -> Vec<String> {
+            self.commands.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ToolTransport for SampleTransport {
+        async fn run(&self, command: &[&str]) -> Result<String> {
+            self.commands.lock().unwrap().push(command.join(" "));
+            let output = match command {
+                ["jcmd", _, "VM.version"] => {
+                    include_str!("../../../assets/sample_outputs/jcmd_vm_version.txt")
+                }
+                ["jcmd", _, "VM.uptime"] => {
+                    include_str!("../../../assets/sample_outputs/jcmd_vm_uptime.txt")
+                }
+                ["jcmd", _, "VM.flags"] => {
+                    include_str!("../../../assets/sample_outputs/jcmd_vm_flags.txt")
+                }
+                ["jcmd", _, "Thread.print"] => {
+                    include_str!("../../../assets/sample_outputs/jcmd_thread_print_deadlock.txt")
+                }
+                ["jstat", "-gcutil", _] => {
+                    include_str!("../../../assets/sample_outputs/jstat_gcutil.txt")
+                }
+                _ => "",
+            };
+            Ok(output.to_string())
+        }
+
+        fn label(&self) -> String {
+            "Sample JVM".to_string()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_commands_target_the_connected_pid() {
+        let mut connector = RemoteJdkConnector::from_transport(SampleTransport::default(), 1);
+        connector.connect(51234).await.unwrap();
+        assert!(connector.is_connected().await);
+
+        connector.get_gc_stats().await.unwrap();
+        connector
+            .run_diagnostic("VM.native_memory summary")
+            .await
+            .unwrap();
+        assert_eq!(
+            connector.transport.commands(),
+            vec![
+                "jcmd 51234 VM.version",
+                "jstat -gcutil 51234",
+                "jcmd 51234 VM.native_memory summary"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parses_tool_output() {
+        let mut connector = RemoteJdkConnector::from_transport(SampleTransport::default(), 1);
+        connector.connect(51234).await.unwrap();
+
+        let jvm_info = connector.get_jvm_info().await.unwrap();
+        assert_eq!(jvm_info.pid, 51234);
+        assert_eq!(jvm_info.main_class, "Sample JVM");
+        assert!(!jvm_info.vm_flags.is_empty());
+
+        let (threads, deadlocked) = connector.get_threads_and_deadlocks().await.unwrap();
+        assert!(threads.iter().any(|thread| thread.name == "worker-a"));
+        assert_eq!(deadlocked, vec![21, 22]);
+        let thread_dumps = connector
+            .transport
+            .commands()
+            .iter()
+            .filter(|command| command.ends_with("Thread.print"))
+            .count();
+        assert_eq!(thread_dumps, 1);
+
+        assert_eq!(connector.get_gc_stats().await.unwrap().old_gc_count(), 37);
+    }
+
+    #[tokio::test]
+    async fn test_keeps_raw_output_once_enabled() {
+        let mut connector = RemoteJdkConnector::from_transport(SampleTransport::default(), 1);
+        connector.connect(51234).await.unwrap();
+        assert!(connector.last_raw_output().is_none());
+
+        connector.capture_raw_output();
+        connector.get_gc_stats().await.unwrap();
+        let outputs = connector.last_raw_output().unwrap();
+        assert_eq!(outputs.len(), 1);
+        assert_eq!(outputs[0].command, "jstat -gcutil 51234");
+        assert!(outputs[0].output.contains("YGC"));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::error::{AppError, Result};
+use crate::jvm::connector::JvmConnector;
+use crate::jvm::replay::recording::Recording;
+use crate::jvm::types::{
+    ClassInfo, CompilerStats, GcPause, GcStats, HeapInfo, HumongousStats, JvmInfo, NativeMemory,
+    ThreadInfo,
+};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Plays a `--record` file back as if it were a live JVM: each poll returns
+/// the next recorded heap and GC sample, and the connection ends once the
+/// heap samples run out, leaving the last state on screen.
+pub struct ReplayConnector {
+    recording: Recording,
+    heap_cursor: AtomicUsize,
+    gc_cursor: AtomicUsize,
+}
+
+impl ReplayConnector {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            heap_cursor: AtomicUsize::new(0),
+            gc_cursor: AtomicUsize::new(0),
+        }
+    }
+
+    fn unavailable(what: &str) -> AppError {
+        AppError::Connection(format!("The recording has no {}", what))
+    }
+
+    /// GC sample the last `get_gc_stats` call returned.
+    fn current_gc(&self) -> Option<&GcStats> {
+        let shown = self.gc_cursor.load(Ordering::Relaxed);
+        self.recording.gc_history.get(
+            shown
+                .checked_sub(1)?
+                .min(self.recording.gc_history.len().checked_sub(1)?),
+        )
+    }
+}
+
+#[async_trait]
+impl JvmConnector for ReplayConnector {
+    async fn connect(&mut self, _pid: u32) -> Result<()> {
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        self.heap_cursor.load(Ordering::Relaxed) < self.recording.heap_history.len()
+    }
+
+    async fn reconnect(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_jvm_info(&self) -> Result<JvmInfo> {
+        Ok(self.recording.jvm_info.clone().unwrap_or_else(|| JvmInfo {
+            pid: 0,
+            main_class: "Recording".to_string(),
+            version: "Unknown".to_string(),
+            uptime_seconds: 0,
+            vm_flags: Vec::new(),
+            container_memory_limit: None,
+        }))
+    }
+
+    async fn get_heap_info(&self) -> Result<HeapInfo> {
+        let next = self.heap_cursor.fetch_add(1, Ordering::Relaxed);
+        self.recording
+            .heap_history
+            .get(next)
+            .cloned()
+            .ok_or_else(|| Self::unavailable("more heap samples"))
+    }
+
+    async fn get_gc_stats(&self) -> Result<GcStats> {
+        let next = self.gc_cursor.fetch_add(1, Ordering::Relaxed);
+        let history = &self.recording.gc_history;
+        history
+            .get(next.min(history.len().saturating_sub(1)))
+            .cloned()
+            .ok_or_else(|| Self::unavailable("GC samples"))
+    }
+
+    async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
+        Ok(self.recording.thread_snapshot.clone())
+    }
+
+    async fn get_class_histogram(&self) -> Result<Vec<ClassInfo>> {
+        Ok(self.recording.class_histogram.clone())
+    }
+
+    async fn get_deadlocked_threads(&self) -> Result<Vec<u64>> {
+        Ok(self.recording.deadlocked_threads.clone())
+    }
+
+    /// Only the pauses that had happened by the GC sample being shown.
+    async fn get_recent_gc_pauses(&self) -> Result<Vec<GcPause>> {
+        let until = self.current_gc().map(|gc| gc.timestamp);
+        Ok(self
+            .recording
+            .gc_pauses
+            .iter()
+            .filter(|pause| match (pause.timestamp, until) {
+                (Some(at), Some(until)) => at <= until,
+                _ => true,
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_humongous_stats(&self) -> Result<Option<HumongousStats>> {
+        Ok(self.recording.humongous.clone())
+    }
+
+    async fn get_native_memory(&self) -> Result<NativeMemory> {
+        self.recording
+            .native_memory
+            .clone()
+            .ok_or_else(|| Self::unavailable("native memory summary"))
+    }
+
+    async fn get_compiler_stats(&self) -> Result<CompilerStats> {
+        self.recording
+            .compiler_stats
+            .clone()
+            .ok_or_else(|| Self::unavailable("compiler stats"))
+    }
+
+    async fn trigger_gc(&self) -> Result<()> {
+        Err(AppError::Connection(
+            "Cannot trigger a GC while replaying a recording".to_string(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Local};
+
+    fn heap(used_bytes: u64) -> HeapInfo {
+        HeapInfo {
+            used_bytes,
+            max_bytes: 1000,
+            committed_bytes: 500,
+            pools: Vec::new(),
+            timestamp: Local::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_returns_samples_in_order() {
+        let start = Local::now();
+        let pause = |gc_id, seconds| GcPause {
+            gc_id,
+            kind: "Young (Normal)".to_string(),
+            cause: None,
+            duration_ms: 5.0,
+            timestamp: Some(start + Duration::seconds(seconds)),
+            uptime_seconds: None,
+        };
+        let connector = ReplayConnector::new(Recording {
+            heap_history: vec![heap(100), heap(200)],
+            gc_history: vec![
+                GcStats::from_generations(1, 5, 0, 0, start),
+                GcStats::from_generations(2, 10, 0, 0, start + Duration::seconds(1)),
+            ],
+            gc_pauses: vec![pause(1, 0), pause(2, 1)],
+            ..Default::default()
+        });
+
+        assert!(connector.is_connected().await);
+        assert_eq!(connector.get_heap_info().await.unwrap().used_bytes, 100);
+        assert_eq!(connector.get_gc_stats().await.unwrap().young_gc_count(), 1);
+        assert_eq!(connector.get_recent_gc_pauses().await.unwrap().len(), 1);
+
+        assert!(connector.is_connected().await);
+        assert_eq!(connector.get_heap_info().await.unwrap().used_bytes, 200);
+        assert_eq!(connector.get_gc_stats().await.unwrap().young_gc_count(), 2);
+        assert_eq!(connector.get_recent_gc_pauses().await.unwrap().len(), 2);
+
+        assert!(!connector.is_connected().await);
+        assert!(connector.get_heap_info().await.is_err());
+        assert_eq!(
+            connector.get_jvm_info().await.unwrap().main_class,
+            "Recording"
+        );
+    }
+}
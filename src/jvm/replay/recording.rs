@@ -0,0 +1,130 @@
+use crate::error::{AppError, Result};
+use crate::jvm::types::{
+    ClassInfo, CompilerStats, GcPause, GcStats, HeapInfo, HumongousStats, JvmInfo, NativeMemory,
+    ThreadInfo,
+};
+use crate::metrics::store::MetricsStore;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::path::Path;
+
+/// A session saved with `--record`: the JSON export plus the attached JVM,
+/// so `--replay` can show it again. Plain JSON exports load too.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Recording {
+    pub jvm_info: Option<JvmInfo>,
+    #[serde(deserialize_with = "samples")]
+    pub heap_history: Vec<HeapInfo>,
+    #[serde(deserialize_with = "samples")]
+    pub gc_history: Vec<GcStats>,
+    pub thread_snapshot: Vec<ThreadInfo>,
+    pub deadlocked_threads: Vec<u64>,
+    pub gc_pauses: Vec<GcPause>,
+    pub class_histogram: Vec<ClassInfo>,
+    pub compiler_stats: Option<CompilerStats>,
+    pub humongous: Option<HumongousStats>,
+    pub native_memory: Option<NativeMemory>,
+}
+
+#[derive(Serialize)]
+struct RecordingRef<'a> {
+    jvm_info: Option<&'a JvmInfo>,
+    #[serde(flatten)]
+    store: &'a MetricsStore,
+}
+
+/// Ring buffers are exported with their capacity; only the samples matter here.
+fn samples<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<T>, D::Error> {
+    #[derive(Deserialize)]
+    struct Samples<T> {
+        buffer: Vec<T>,
+    }
+    Ok(Samples::deserialize(deserializer)?.buffer)
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            AppError::Config(format!("cannot read recording {}: {}", path.display(), e))
+        })?;
+        let recording: Self = serde_json::from_str(&contents).map_err(|e| {
+            AppError::Parse(format!("{} is not a recording: {}", path.display(), e))
+        })?;
+        if recording.heap_history.is_empty() {
+            return Err(AppError::Parse(format!(
+                "{} has no heap samples to replay",
+                path.display()
+            )));
+        }
+        Ok(recording)
+    }
+}
+
+/// Writes the store to `path`, replacing the previous recording only once the
+/// new one is complete.
+pub fn write_recording(
+    path: &Path,
+    jvm_info: Option<&JvmInfo>,
+    store: &MetricsStore,
+) -> Result<()> {
+    let partial = path.with_extension("partial");
+    let file = std::fs::File::create(&partial)?;
+    serde_json::to_writer(
+        std::io::BufWriter::new(file),
+        &RecordingRef { jvm_info, store },
+    )?;
+    std::fs::rename(&partial, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    #[test]
+    fn test_recording_round_trip() {
+        let mut store = MetricsStore::new(10);
+        for used in [100, 200, 300] {
+            store.record_heap(HeapInfo {
+                used_bytes: used,
+                max_bytes: 1000,
+                committed_bytes: 500,
+                pools: Vec::new(),
+                timestamp: Local::now(),
+            });
+        }
+        store.record_gc(GcStats::from_generations(4, 40, 1, 100, Local::now()));
+        let jvm_info = JvmInfo {
+            pid: 42,
+            main_class: "com.example.App".to_string(),
+            version: "21".to_string(),
+            uptime_seconds: 60,
+            vm_flags: Vec::new(),
+            container_memory_limit: None,
+        };
+
+        let dir = std::env::temp_dir().join(format!("jvm-tui-recording-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.json");
+        write_recording(&path, Some(&jvm_info), &store).unwrap();
+        let recording = Recording::load(&path).unwrap();
+
+        assert_eq!(recording.jvm_info.unwrap().pid, 42);
+        let used: Vec<u64> = recording
+            .heap_history
+            .iter()
+            .map(|h| h.used_bytes)
+            .collect();
+        assert_eq!(used, vec![100, 200, 300]);
+        assert_eq!(recording.gc_history[0].young_gc_count(), 4);
+        assert!(!path.with_extension("partial").exists());
+
+        std::fs::write(&path, "{}").unwrap();
+        assert!(Recording::load(&path).is_err());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -1,17 +1,20 @@
 use crate::error::{AppError, Result};
-use crate::jvm::connector::JvmConnector;
-use crate::jvm::jdk_tools::parsers::{jcmd, jstat};
-use crate::jvm::types::{ClassInfo, GcStats, HeapInfo, JvmInfo, ThreadInfo};
-use async_ssh2_tokio::{client::AuthMethod, Client, ServerCheckMethod};
+use crate::jvm::remote_jdk::connector::{RemoteJdkConnector, ToolTransport};
+use async_ssh2_tokio::{
+    client::{AuthMethod, CommandExecutedResult},
+    Client, ServerCheckMethod,
+};
 use async_trait::async_trait;
 use std::path::PathBuf;
 
-pub struct SshJdkConnector {
+/// Runs jcmd/jstat on a remote host over SSH.
+pub type SshJdkConnector = RemoteJdkConnector<SshTransport>;
+
+pub struct SshTransport {
     host: String,
     port: u16,
     user: String,
     auth_method: AuthMethod,
-    pid: u32,
     client: Option<Client>,
 }
 
@@ -42,34 +45,20 @@ impl SshJdkConnector {
             )
         };
 
-        Self {
+        let transport = SshTransport {
             host,
             port,
             user,
             auth_method,
-            pid,
             client: None,
-        }
-    }
-
-    async fn execute_command(&self, command: &str) -> Result<String> {
-        let client = self
-            .client
-            .as_ref()
-            .ok_or_else(|| AppError::Connection("Not connected".to_string()))?;
-
-        let result = client
-            .execute(command)
-            .await
-            .map_err(|e| AppError::Connection(format!("SSH command failed: {}", e)))?;
-
-        Ok(result.stdout)
+        };
+        Self::from_transport(transport, pid)
     }
 }
 
 #[async_trait]
-impl JvmConnector for SshJdkConnector {
-    async fn connect(&mut self, _pid: u32) -> Result<()> {
+impl ToolTransport for SshTransport {
+    async fn open(&mut self) -> Result<()> {
         let client = Client::connect(
             (self.host.clone(), self.port),
             &self.user,
@@ -83,86 +72,70 @@ impl JvmConnector for SshJdkConnector {
         Ok(())
     }
 
-    async fn disconnect(&mut self) -> Result<()> {
+    async fn close(&mut self) {
         self.client = None;
-        Ok(())
     }
 
-    async fn is_connected(&self) -> bool {
-        self.client.is_some()
-    }
+    async fn run(&self, command: &[&str]) -> Result<String> {
+        let client = self
+            .client
+            .as_ref()
+            .ok_or_else(|| AppError::Connection("Not connected".to_string()))?;
 
-    async fn reconnect(&mut self) -> Result<()> {
-        self.disconnect().await?;
-        self.connect(self.pid).await
-    }
+        let result = client
+            .execute(&command.join(" "))
+            .await
+            .map_err(|e| AppError::Connection(format!("SSH command failed: {}", e)))?;
 
-    async fn get_jvm_info(&self) -> Result<JvmInfo> {
-        let vm_version_output = self
-            .execute_command(&format!("jcmd {} VM.version", self.pid))
-            .await?;
-        let uptime_output = self
-            .execute_command(&format!("jcmd {} VM.uptime", self.pid))
-            .await?;
-        let flags_output = self
-            .execute_command(&format!("jcmd {} VM.flags", self.pid))
-            .await?;
-
-        let version = jcmd::parse_jvm_version(&vm_version_output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse VM version: {}", e)))?;
-        let uptime_seconds = jcmd::parse_vm_uptime(&uptime_output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse uptime: {}", e)))?;
-        let vm_flags = jcmd::parse_vm_flags(&flags_output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse VM flags: {}", e)))?;
-
-        Ok(JvmInfo {
-            pid: self.pid,
-            main_class: format!("Remote JVM ({})", self.host),
-            version,
-            uptime_seconds,
-            vm_flags,
-        })
+        command_output(result)
     }
 
-    async fn get_heap_info(&self) -> Result<HeapInfo> {
-        let output = self
-            .execute_command(&format!("jcmd {} GC.heap_info", self.pid))
-            .await?;
-
-        jcmd::parse_heap_info(&output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse heap info: {}", e)))
+    fn label(&self) -> String {
+        format!("Remote JVM ({})", self.host)
     }
+}
 
-    async fn get_gc_stats(&self) -> Result<GcStats> {
-        let output = self
-            .execute_command(&format!("jstat -gc {}", self.pid))
-            .await?;
-
-        jstat::parse_gc_stats(&output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse GC stats: {}", e)))
+/// A tool that exits non-zero (e.g. jcmd once the JVM is gone) fails the
+/// command instead of handing its partial stdout to the parsers.
+fn command_output(result: CommandExecutedResult) -> Result<String> {
+    if result.exit_status != 0 {
+        return Err(AppError::Connection(format!(
+            "SSH command exited with status {}: {}",
+            result.exit_status,
+            result.stderr.trim()
+        )));
     }
 
-    async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
-        let output = self
-            .execute_command(&format!("jcmd {} Thread.print", self.pid))
-            .await?;
-
-        jcmd::parse_thread_dump(&output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse thread dump: {}", e)))
-    }
+    Ok(result.stdout)
+}
 
-    async fn get_class_histogram(&self) -> Result<Vec<ClassInfo>> {
-        let output = self
-            .execute_command(&format!("jcmd {} GC.class_histogram", self.pid))
-            .await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        jcmd::parse_class_histogram(&output)
-            .map_err(|e| AppError::Parse(format!("Failed to parse class histogram: {}", e)))
+    fn executed(stdout: &str, stderr: &str, exit_status: u32) -> CommandExecutedResult {
+        CommandExecutedResult {
+            stdout: stdout.to_string(),
+            stderr: stderr.to_string(),
+            exit_status,
+        }
     }
 
-    async fn trigger_gc(&self) -> Result<()> {
-        self.execute_command(&format!("jcmd {} GC.run", self.pid))
-            .await?;
-        Ok(())
+    #[test]
+    fn test_command_output() {
+        assert_eq!(
+            command_output(executed("YGC\n", "", 0)).unwrap(),
+            "YGC\n".to_string()
+        );
+
+        // jcmd against a JVM that has exited
+        let err = command_output(executed(
+            "4242:\n",
+            "java.io.IOException: No such process\n",
+            1,
+        ))
+        .unwrap_err();
+        assert!(err.is_connection_error());
+        assert!(err.to_string().contains("No such process"), "{}", err);
     }
 }
@@ -1,5 +1,7 @@
 use chrono::{DateTime, Local};
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JvmInfo {
@@ -8,6 +10,19 @@ pub struct JvmInfo {
     pub version: String,
     pub uptime_seconds: u64,
     pub vm_flags: Vec<String>,
+    /// Memory limit of the container the JVM runs in, when attached locally.
+    #[serde(default)]
+    pub container_memory_limit: Option<u64>,
+}
+
+/// JVM implementation, which decides the diagnostic commands jcmd knows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VmVendor {
+    #[default]
+    HotSpot,
+    /// Eclipse OpenJ9 / IBM J9: no `GC.heap_info`, `VM.uptime` or `VM.flags`,
+    /// and a jstat that only knows `-class`.
+    OpenJ9,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +31,16 @@ pub struct HeapInfo {
     pub max_bytes: u64,
     pub committed_bytes: u64,
     pub pools: Vec<MemoryPool>,
+    /// When the sample was read; exports from before it existed load as now.
+    #[serde(default = "Local::now")]
+    pub timestamp: DateTime<Local>,
+}
+
+impl HeapInfo {
+    /// Used share of the maximum heap, or `None` before the heap is sized.
+    pub fn used_percent(&self) -> Option<f64> {
+        (self.max_bytes > 0).then(|| self.used_bytes as f64 / self.max_bytes as f64 * 100.0)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,7 +52,9 @@ pub struct MemoryPool {
     pub committed_bytes: u64,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// Declared young generation first, then old, then non-heap, which is the
+/// order the Memory tab lists pools in.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum PoolType {
     Eden,
     Survivor,
@@ -37,21 +64,440 @@ pub enum PoolType {
     Other,
 }
 
+impl PoolType {
+    pub fn all() -> &'static [PoolType] {
+        &[
+            PoolType::Eden,
+            PoolType::Survivor,
+            PoolType::Old,
+            PoolType::Metaspace,
+            PoolType::CodeCache,
+            PoolType::Other,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PoolType::Eden => "Eden",
+            PoolType::Survivor => "Survivor",
+            PoolType::Old => "Old",
+            PoolType::Metaspace => "Metaspace",
+            PoolType::CodeCache => "Code Cache",
+            PoolType::Other => "Other",
+        }
+    }
+}
+
+/// Which bucket a collector's counts roll up into. Concurrent cycles run
+/// alongside the application, so their time isn't pause time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GcCategory {
+    Young,
+    Old,
+    Concurrent,
+}
+
+impl GcCategory {
+    /// Classifies a `GarbageCollectorMXBean` name such as `G1 Young
+    /// Generation`, `PS MarkSweep` or `ZGC Major Pauses`. Pauses of the
+    /// single-generation concurrent collectors count as young, leaving Full
+    /// GC for real full collections.
+    pub fn from_collector_name(name: &str) -> Self {
+        if name.contains("Cycles") || name.contains("G1 Concurrent") {
+            GcCategory::Concurrent
+        } else if name.contains("Old")
+            || name.contains("MarkSweep")
+            || name.contains("Major")
+            || name.contains("Full")
+        {
+            GcCategory::Old
+        } else {
+            GcCategory::Young
+        }
+    }
+}
+
+/// Cumulative count and time of one collector since JVM start.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcCollectorStat {
+    pub name: String,
+    pub count: u64,
+    pub time_ms: u64,
+    pub category: GcCategory,
+}
+
+impl GcCollectorStat {
+    /// Mean time per collection in ms (per cycle for concurrent collectors).
+    pub fn avg_ms(&self) -> f64 {
+        average_ms(self.time_ms, self.count)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
 pub struct GcStats {
-    pub young_gc_count: u64,
-    pub young_gc_time_ms: u64,
-    pub old_gc_count: u64,
-    pub old_gc_time_ms: u64,
+    pub collectors: Vec<GcCollectorStat>,
     pub timestamp: DateTime<Local>,
 }
 
+/// Exports keep the young/old totals they had before the per-collector
+/// breakdown, alongside it.
+impl Serialize for GcStats {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("GcStats", 6)?;
+        state.serialize_field("young_gc_count", &self.young_gc_count())?;
+        state.serialize_field("young_gc_time_ms", &self.young_gc_time_ms())?;
+        state.serialize_field("old_gc_count", &self.old_gc_count())?;
+        state.serialize_field("old_gc_time_ms", &self.old_gc_time_ms())?;
+        state.serialize_field("collectors", &self.collectors)?;
+        state.serialize_field("timestamp", &self.timestamp)?;
+        state.end()
+    }
+}
+
+impl GcStats {
+    /// Stats for connectors that only see the young/full split, such as
+    /// `jstat -gcutil`.
+    pub fn from_generations(
+        young_gc_count: u64,
+        young_gc_time_ms: u64,
+        old_gc_count: u64,
+        old_gc_time_ms: u64,
+        timestamp: DateTime<Local>,
+    ) -> Self {
+        Self {
+            collectors: vec![
+                GcCollectorStat {
+                    name: "Young GC".to_string(),
+                    count: young_gc_count,
+                    time_ms: young_gc_time_ms,
+                    category: GcCategory::Young,
+                },
+                GcCollectorStat {
+                    name: "Full GC".to_string(),
+                    count: old_gc_count,
+                    time_ms: old_gc_time_ms,
+                    category: GcCategory::Old,
+                },
+            ],
+            timestamp,
+        }
+    }
+
+    fn sum(&self, category: GcCategory, value: impl Fn(&GcCollectorStat) -> u64) -> u64 {
+        self.collectors
+            .iter()
+            .filter(|collector| collector.category == category)
+            .map(value)
+            .sum()
+    }
+
+    pub fn young_gc_count(&self) -> u64 {
+        self.sum(GcCategory::Young, |collector| collector.count)
+    }
+
+    pub fn young_gc_time_ms(&self) -> u64 {
+        self.sum(GcCategory::Young, |collector| collector.time_ms)
+    }
+
+    pub fn old_gc_count(&self) -> u64 {
+        self.sum(GcCategory::Old, |collector| collector.count)
+    }
+
+    pub fn old_gc_time_ms(&self) -> u64 {
+        self.sum(GcCategory::Old, |collector| collector.time_ms)
+    }
+
+    /// Mean young pause in ms, 0 before the first collection.
+    pub fn avg_young_gc_ms(&self) -> f64 {
+        average_ms(self.young_gc_time_ms(), self.young_gc_count())
+    }
+
+    /// Mean full pause in ms, 0 before the first collection.
+    pub fn avg_old_gc_ms(&self) -> f64 {
+        average_ms(self.old_gc_time_ms(), self.old_gc_count())
+    }
+}
+
+fn average_ms(time_ms: u64, count: u64) -> f64 {
+    if count > 0 {
+        time_ms as f64 / count as f64
+    } else {
+        0.0
+    }
+}
+
+/// Generation sizes from `jstat -gccapacity`. The maximums reflect `-Xmx`
+/// even where `GC.heap_info` only reports the committed size.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcCapacity {
+    pub young_max_bytes: u64,
+    pub young_committed_bytes: u64,
+    pub old_max_bytes: u64,
+    pub old_committed_bytes: u64,
+}
+
+impl GcCapacity {
+    pub fn max_heap_bytes(&self) -> u64 {
+        self.young_max_bytes + self.old_max_bytes
+    }
+}
+
+/// JIT compiler activity and code cache occupancy.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompilerStats {
+    pub compiled_methods: u64,
+    pub failed_compilations: u64,
+    pub invalidated_compilations: u64,
+    pub compile_time_seconds: f64,
+    pub code_cache_used_bytes: u64,
+    pub code_cache_max_bytes: u64,
+    /// False once the JVM has stopped compiling, typically because the code
+    /// cache filled up.
+    pub compilation_enabled: bool,
+}
+
+impl CompilerStats {
+    /// The `jstat -compiler` figures, read from the counters jstat itself
+    /// uses. The code cache usage is what its nmethods take up; the counters
+    /// can't tell whether compilation has stopped. `None` without a JIT
+    /// section, e.g. under `-Xint`.
+    pub fn from_perf_counters(
+        counters: &HashMap<String, i64>,
+        code_cache_max_bytes: u64,
+    ) -> Option<Self> {
+        let count = |name: &str| counters.get(name).copied().unwrap_or(0).max(0) as u64;
+        let ticks_per_second = counters
+            .get("sun.os.hrt.frequency")
+            .filter(|frequency| **frequency > 0)
+            .map_or(1_000_000_000.0, |frequency| *frequency as f64);
+
+        counters.contains_key("sun.ci.totalCompiles").then(|| Self {
+            compiled_methods: count("sun.ci.totalCompiles"),
+            failed_compilations: count("sun.ci.totalBailouts"),
+            invalidated_compilations: count("sun.ci.totalInvalidates"),
+            compile_time_seconds: count("java.ci.totalTime") as f64 / ticks_per_second,
+            code_cache_used_bytes: count("sun.ci.nmethodSize"),
+            code_cache_max_bytes,
+            compilation_enabled: true,
+        })
+    }
+}
+
+/// Runtime totals read from the JVM's hsperfdata counters.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuntimeCounters {
+    pub safepoints: u64,
+    pub safepoint_time_ms: f64,
+    pub safepoint_sync_time_ms: f64,
+    pub jit_time_ms: f64,
+    pub jit_compiles: u64,
+    pub classes_loaded: u64,
+    pub classes_unloaded: u64,
+    /// Summed over every `sun.gc.collector.<n>`.
+    pub gc_invocations: u64,
+    pub gc_time_ms: f64,
+}
+
+impl RuntimeCounters {
+    /// Picks the counters out of `PerfCounter.print`; times there are in
+    /// high-resolution timer ticks, converted using `sun.os.hrt.frequency`.
+    pub fn from_perf_counters(counters: &HashMap<String, i64>) -> Self {
+        let count = |name: &str| counters.get(name).copied().unwrap_or(0).max(0) as u64;
+        let ticks_per_ms = counters
+            .get("sun.os.hrt.frequency")
+            .filter(|frequency| **frequency > 0)
+            .map_or(1_000_000.0, |frequency| *frequency as f64 / 1000.0);
+        let millis = |ticks: u64| ticks as f64 / ticks_per_ms;
+
+        let collector_total = |suffix: &str| -> u64 {
+            counters
+                .iter()
+                .filter(|(name, _)| {
+                    name.strip_prefix("sun.gc.collector.")
+                        .and_then(|rest| rest.split_once('.'))
+                        .is_some_and(|(_, field)| field == suffix)
+                })
+                .map(|(_, value)| (*value).max(0) as u64)
+                .sum()
+        };
+
+        Self {
+            safepoints: count("sun.rt.safepoints"),
+            safepoint_time_ms: millis(count("sun.rt.safepointTime")),
+            safepoint_sync_time_ms: millis(count("sun.rt.safepointSyncTime")),
+            jit_time_ms: millis(count("java.ci.totalTime")),
+            jit_compiles: count("sun.ci.totalCompiles"),
+            classes_loaded: count("java.cls.loadedClasses"),
+            classes_unloaded: count("java.cls.unloadedClasses"),
+            gc_invocations: collector_total("invocations"),
+            gc_time_ms: millis(collector_total("time")),
+        }
+    }
+}
+
+/// One `VM.native_memory summary` category, e.g. Thread or Code.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NativeMemoryCategory {
+    pub name: String,
+    pub reserved_bytes: u64,
+    pub committed_bytes: u64,
+}
+
+/// Native Memory Tracking summary: where the process's off-heap memory goes.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NativeMemoryReport {
+    pub categories: Vec<NativeMemoryCategory>,
+    pub total_reserved_bytes: u64,
+    pub total_committed_bytes: u64,
+}
+
+/// Result of asking for NMT data; the JVM only tracks it when started with
+/// `-XX:NativeMemoryTracking=summary` (or `detail`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NativeMemory {
+    Disabled,
+    Tracked(NativeMemoryReport),
+}
+
+/// Share of the heap's regions above which humongous allocations are flagged.
+pub const HUMONGOUS_WARN_PERCENT: f64 = 10.0;
+
+/// G1 humongous regions: objects of at least half a region get whole
+/// regions of their own, and many of them tend to trigger full GCs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HumongousStats {
+    /// Regions holding humongous objects (starts plus continuations).
+    pub regions: u64,
+    /// Humongous objects, i.e. "starts humongous" regions.
+    pub objects: u64,
+    pub total_regions: u64,
+    pub region_size_bytes: u64,
+}
+
+impl HumongousStats {
+    pub fn percent_of_heap(&self) -> f64 {
+        if self.total_regions == 0 {
+            return 0.0;
+        }
+        self.regions as f64 / self.total_regions as f64 * 100.0
+    }
+
+    pub fn is_significant(&self) -> bool {
+        self.percent_of_heap() >= HUMONGOUS_WARN_PERCENT
+    }
+}
+
+/// A single stop-the-world pause read from a GC log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GcPause {
+    pub gc_id: u64,
+    /// What kind of pause it was, e.g. "Young (Normal)", "Full" or "Remark".
+    pub kind: String,
+    /// Why the collection ran, e.g. "G1 Evacuation Pause" or "System.gc()".
+    /// `None` for pauses the log gives no cause for, such as G1's Remark.
+    pub cause: Option<String>,
+    pub duration_ms: f64,
+    pub timestamp: Option<DateTime<Local>>,
+    pub uptime_seconds: Option<f64>,
+}
+
+impl GcPause {
+    /// The pause as the GC log describes it, e.g. "Full (System.gc())".
+    pub fn description(&self) -> String {
+        match &self.cause {
+            Some(cause) => format!("{} ({})", self.kind, cause),
+            None => self.kind.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThreadInfo {
     pub id: u64,
     pub name: String,
     pub state: ThreadState,
     pub stack_trace: Vec<StackFrame>,
+    /// Total CPU time the thread has consumed, when the connector can tell.
+    #[serde(default)]
+    pub cpu_time_ns: Option<u64>,
+    /// Whether the thread is a daemon, when the connector can tell.
+    #[serde(default)]
+    pub daemon: Option<bool>,
+    /// Monitors and locks held or waited for, in stack order.
+    #[serde(default)]
+    pub locks: Vec<LockInfo>,
+}
+
+/// One "- locked <0x...> (a java.util.HashMap)" style line of a thread dump.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockInfo {
+    pub kind: LockKind,
+    /// Object address, e.g. `0x00000000c5a0d2b8`; `None` when the dump has
+    /// no object reference.
+    pub address: Option<String>,
+    pub class_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LockKind {
+    Locked,
+    WaitingToLock,
+    /// `Object.wait()`, which releases the monitor until notified.
+    WaitingOn,
+    WaitingToRelock,
+    /// `LockSupport.park`, e.g. a `ReentrantLock` or a condition.
+    ParkingToWaitFor,
+    /// A `java.util.concurrent` lock held, listed with `Thread.print -l`.
+    OwnableSynchronizer,
+}
+
+impl LockKind {
+    /// The wording `Thread.print` uses.
+    pub fn description(&self) -> &'static str {
+        match self {
+            LockKind::Locked => "locked",
+            LockKind::WaitingToLock => "waiting to lock",
+            LockKind::WaitingOn => "waiting on",
+            LockKind::WaitingToRelock => "waiting to re-lock in wait()",
+            LockKind::ParkingToWaitFor => "parking to wait for",
+            LockKind::OwnableSynchronizer => "holds",
+        }
+    }
+
+    /// Shorter wording for table cells.
+    pub fn short_description(&self) -> &'static str {
+        match self {
+            LockKind::Locked | LockKind::OwnableSynchronizer => "holds",
+            LockKind::WaitingToLock => "blocked on",
+            LockKind::WaitingOn => "waiting on",
+            LockKind::WaitingToRelock => "re-locking",
+            LockKind::ParkingToWaitFor => "parked on",
+        }
+    }
+
+    pub fn is_held(&self) -> bool {
+        matches!(self, LockKind::Locked | LockKind::OwnableSynchronizer)
+    }
+}
+
+impl LockInfo {
+    /// Class name without its package.
+    pub fn short_class_name(&self) -> &str {
+        let name = self.class_name.as_deref().unwrap_or("monitor");
+        name.rsplit('.').next().unwrap_or(name)
+    }
+}
+
+impl ThreadInfo {
+    /// The lock the thread is blocked or parked on, if any.
+    pub fn awaited_lock(&self) -> Option<&LockInfo> {
+        self.locks.iter().find(|lock| !lock.kind.is_held())
+    }
+
+    pub fn held_locks(&self) -> impl Iterator<Item = &LockInfo> {
+        self.locks.iter().filter(|lock| lock.kind.is_held())
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -64,6 +510,36 @@ pub enum ThreadState {
     New,
 }
 
+/// Number of threads in each state at one collection.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ThreadStateCounts {
+    pub runnable: usize,
+    pub blocked: usize,
+    pub waiting: usize,
+    pub timed_waiting: usize,
+    pub other: usize,
+}
+
+impl ThreadStateCounts {
+    pub fn from_threads(threads: &[ThreadInfo]) -> Self {
+        let mut counts = Self::default();
+        for thread in threads {
+            match thread.state {
+                ThreadState::Runnable => counts.runnable += 1,
+                ThreadState::Blocked => counts.blocked += 1,
+                ThreadState::Waiting => counts.waiting += 1,
+                ThreadState::TimedWaiting => counts.timed_waiting += 1,
+                ThreadState::Terminated | ThreadState::New => counts.other += 1,
+            }
+        }
+        counts
+    }
+
+    pub fn total(&self) -> usize {
+        self.runnable + self.blocked + self.waiting + self.timed_waiting + self.other
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StackFrame {
     pub class_name: String,
@@ -1,9 +1,11 @@
 pub mod app;
 pub mod cli;
+pub mod clipboard;
 pub mod config;
 pub mod error;
 pub mod export;
 pub mod jvm;
 pub mod metrics;
+pub mod server;
 pub mod theme;
 pub mod tui;
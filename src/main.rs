@@ -2,26 +2,57 @@ use clap::Parser;
 use color_eyre::Result;
 use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyModifiers};
 use jvm_tui::{
-    app::{App, AppMode, ExportFormat, Tab},
-    cli::Cli,
-    config::{Config, ConnectionProfile},
-    export,
+    app::{App, AppMode, ProcessInfo, Tab},
+    cli::{Cli, Command, ConfigCommand},
+    clipboard,
+    config::{AlertAction, Config, ConnectionProfile, ExportFormat},
+    error::{exit_code, AppError},
+    export::{self, ExportTarget},
     jvm::{
-        connector::JvmConnector,
-        discovery::{discover_local_jvms, DiscoveredJvm},
+        connector::{sanitize_diagnostic_command, JvmConnector},
+        discovery::{discover_local_jvms, filter_by_main_class, DiscoveredJvm},
+        docker::connector::DockerJdkConnector,
         jdk_tools::connector::JdkToolsConnector,
-        jolokia::connector::JolokiaConnector,
+        jolokia::{
+            connector::{JolokiaConnector, JolokiaTls},
+            types::JolokiaTarget,
+        },
+        kubectl::connector::KubectlConnector,
+        raw_output,
+        replay::{
+            connector::ReplayConnector,
+            recording::{write_recording, Recording},
+        },
         ssh_jdk::connector::SshJdkConnector,
+        types::JvmInfo,
+    },
+    metrics::{
+        alerts::AlertEngine,
+        collector::{self, MetricSet, MetricsCollector},
+        store::MetricsStore,
     },
-    metrics::{collector::MetricsCollector, store::MetricsStore},
     theme::Theme,
-    tui::screens::{jvm_picker::JvmPickerScreen, monitoring::MonitoringScreen},
+    tui::keybindings::{Action, KeyMap},
+    tui::screens::{
+        jvm_picker::{JvmPickerScreen, PickerStatus},
+        monitoring::MonitoringScreen,
+    },
     tui::terminal,
-    tui::views::threads::ThreadsView,
+    tui::views::{classes::ClassesView, threads::ThreadsView},
 };
+use std::io::Write;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, RwLock};
+
+/// How long a saved connection picked in the picker gets to answer.
+const CONNECTION_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How often `--record` rewrites its file while the TUI runs.
+const RECORD_INTERVAL: Duration = Duration::from_secs(10);
 
 enum SelectedConnection {
     LocalJvm(DiscoveredJvm),
@@ -29,6 +60,16 @@ enum SelectedConnection {
         url: String,
         username: Option<String>,
         password: Option<String>,
+        target: Option<JolokiaTarget>,
+        use_get: bool,
+        tls: JolokiaTls,
+    },
+    Docker(DockerJdkConnector),
+    Kubectl {
+        pod: String,
+        container: Option<String>,
+        namespace: Option<String>,
+        pid: u32,
     },
     SshJdk {
         host: String,
@@ -38,177 +79,871 @@ enum SelectedConnection {
         password: Option<String>,
         pid: u32,
     },
+    Replay(ReplayConnector),
+}
+
+impl SelectedConnection {
+    /// Resolves a saved connection; local profiles must name a PID among
+    /// `jvms`.
+    fn from_profile(
+        profile: &ConnectionProfile,
+        jvms: &[DiscoveredJvm],
+    ) -> jvm_tui::error::Result<Self> {
+        match profile {
+            ConnectionProfile::Local { pid: Some(pid), .. } => jvms
+                .iter()
+                .find(|jvm| jvm.pid == *pid)
+                .map(|jvm| SelectedConnection::LocalJvm(jvm.clone()))
+                .ok_or_else(|| {
+                    AppError::NoJvm(format!(
+                        "saved connection references PID {} which is not running",
+                        pid
+                    ))
+                }),
+            ConnectionProfile::Local { pid: None, .. } => Err(AppError::Config(
+                "local connection must specify a PID".to_string(),
+            )),
+            ConnectionProfile::Jolokia {
+                url,
+                username,
+                password,
+                target_url,
+                target_username,
+                target_password,
+                use_get,
+                insecure_skip_verify,
+                ca_cert_path,
+                client_cert_path,
+                client_key_path,
+                ..
+            } => Ok(SelectedConnection::Jolokia {
+                url: url.clone(),
+                username: username.clone(),
+                password: password.clone(),
+                target: target_url.clone().map(|url| JolokiaTarget {
+                    url,
+                    user: target_username.clone(),
+                    password: target_password.clone(),
+                }),
+                use_get: *use_get,
+                tls: JolokiaTls {
+                    insecure_skip_verify: *insecure_skip_verify,
+                    ca_cert_path: ca_cert_path.clone(),
+                    client_cert_path: client_cert_path.clone(),
+                    client_key_path: client_key_path.clone(),
+                },
+            }),
+            ConnectionProfile::SshJdk {
+                ssh_host,
+                ssh_user,
+                ssh_port,
+                ssh_key,
+                ssh_password,
+                pid,
+                ..
+            } => Ok(SelectedConnection::SshJdk {
+                host: ssh_host.clone(),
+                user: ssh_user.clone(),
+                port: *ssh_port,
+                key: ssh_key.clone(),
+                password: ssh_password.clone(),
+                pid: *pid,
+            }),
+            ConnectionProfile::Kubectl {
+                pod,
+                container,
+                namespace,
+                pid,
+                ..
+            } => Ok(SelectedConnection::Kubectl {
+                pod: pod.clone(),
+                container: container.clone(),
+                namespace: namespace.clone(),
+                pid: *pid,
+            }),
+            ConnectionProfile::SshJolokia { .. } => Err(AppError::Config(
+                "SSH+Jolokia connections are not supported yet; use a jolokia, ssh-jdk or \
+                 local connection instead"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Shown in the header next to the poll latency.
+    fn label(&self) -> &'static str {
+        match self {
+            SelectedConnection::LocalJvm(_) => "Local",
+            SelectedConnection::Jolokia { .. } => "Jolokia",
+            SelectedConnection::Docker(_) => "Docker",
+            SelectedConnection::Kubectl { .. } => "kubectl",
+            SelectedConnection::SshJdk { .. } => "SSH",
+            SelectedConnection::Replay(_) => "Replay",
+        }
+    }
+
+    /// Attaches and reads the JVM's info, so a connection that can't be
+    /// used fails here rather than on the first poll.
+    async fn connect(self, config: &Config) -> jvm_tui::error::Result<Connected> {
+        let label = self.label();
+        let mut tool_warning = None;
+        let (connector, jvm_info): (Arc<RwLock<dyn JvmConnector>>, JvmInfo) = match self {
+            SelectedConnection::LocalJvm(jvm) => {
+                let mut connector = local_connector(config);
+                if let Err(e) = connector.connect(jvm.pid).await {
+                    let hint = match connector.jcmd_major_version().await {
+                        Some(major) => format!(
+                            "\nHint: jcmd is from JDK {}; attach usually fails when the target \
+                             JVM runs a different JDK version. Point JAVA_HOME at a matching JDK.",
+                            major
+                        ),
+                        None => String::new(),
+                    };
+                    return Err(AppError::Connection(format!(
+                        "failed to attach to PID {}: {}{}",
+                        jvm.pid, e, hint
+                    )));
+                }
+                let jvm_info = connector.get_jvm_info().await?;
+                tool_warning = match connector.vendor_warning().await {
+                    Some(warning) => Some(warning),
+                    None => connector.tool_version_warning(&jvm_info.version).await,
+                };
+                (Arc::new(RwLock::new(connector)), jvm_info)
+            }
+            SelectedConnection::Jolokia {
+                url,
+                username,
+                password,
+                target,
+                use_get,
+                tls,
+            } => {
+                let mut connector =
+                    JolokiaConnector::new(url, username, password).with_tls(&tls)?;
+                if let Some(target) = target {
+                    connector = connector.with_target(target);
+                }
+                if use_get {
+                    connector = connector.with_get_requests();
+                }
+                connector.connect(0).await?;
+                let jvm_info = connector.get_jvm_info().await?;
+                (Arc::new(RwLock::new(connector)), jvm_info)
+            }
+            SelectedConnection::Docker(connector) => {
+                let jvm_info = connector.get_jvm_info().await?;
+                (Arc::new(RwLock::new(connector)), jvm_info)
+            }
+            SelectedConnection::Kubectl {
+                pod,
+                container,
+                namespace,
+                pid,
+            } => {
+                let mut connector = KubectlConnector::new(pod, container, namespace, pid);
+                connector.connect(pid).await?;
+                let jvm_info = connector.get_jvm_info().await?;
+                (Arc::new(RwLock::new(connector)), jvm_info)
+            }
+            SelectedConnection::SshJdk {
+                host,
+                user,
+                port,
+                key,
+                password,
+                pid,
+            } => {
+                let mut connector = SshJdkConnector::new(host, port, user, key, password, pid);
+                connector.connect(pid).await?;
+                let jvm_info = connector.get_jvm_info().await?;
+                (Arc::new(RwLock::new(connector)), jvm_info)
+            }
+            SelectedConnection::Replay(connector) => {
+                let jvm_info = connector.get_jvm_info().await?;
+                (Arc::new(RwLock::new(connector)), jvm_info)
+            }
+        };
+
+        Ok(Connected {
+            connector,
+            jvm_info,
+            tool_warning,
+            label,
+        })
+    }
+}
+
+/// A connection that attached and answered, ready to be polled.
+struct Connected {
+    connector: Arc<RwLock<dyn JvmConnector>>,
+    jvm_info: JvmInfo,
+    /// Shown as a banner, e.g. when local JDK tools don't match the target.
+    tool_warning: Option<String>,
+    label: &'static str,
+}
+
+fn check_config(path: Option<PathBuf>) {
+    let resolved = path.or_else(Config::find_config_file);
+
+    println!("Config search paths:");
+    for candidate in Config::config_search_paths() {
+        let marker = if Some(&candidate) == resolved.as_ref() {
+            "*"
+        } else if candidate.exists() {
+            "+"
+        } else {
+            " "
+        };
+        println!("  {} {}", marker, candidate.display());
+    }
+    println!();
+
+    let path = match resolved {
+        Some(path) => path,
+        None => {
+            println!("No config file found, using built-in defaults.");
+            return;
+        }
+    };
+
+    println!("Using: {}", path.display());
+    match Config::load_from_file(&path).and_then(|config| config.to_redacted_toml()) {
+        Ok(effective) => {
+            println!("Config is valid.\n");
+            print!("{}", effective);
+        }
+        Err(e) => {
+            eprintln!("Config is invalid: {}", e);
+            std::process::exit(e.exit_code());
+        }
+    }
+}
+
+/// JDK tools connector honouring `advanced.java_home` and
+/// `preferences.gc_log_path` from the config.
+fn local_connector(config: &Config) -> JdkToolsConnector {
+    let connector = match config.advanced.java_home {
+        Some(ref java_home) => JdkToolsConnector::with_java_home(PathBuf::from(java_home)),
+        None => JdkToolsConnector::new(),
+    };
+    match config.preferences.gc_log_path {
+        Some(ref gc_log) => connector.with_gc_log(PathBuf::from(gc_log)),
+        None => connector,
+    }
+}
+
+/// Export location and file naming from the preferences, with `jvm_info`
+/// filling the `{pid}`/`{main_class}` placeholders.
+fn export_target<'a>(config: &'a Config, jvm_info: Option<&'a JvmInfo>) -> ExportTarget<'a> {
+    ExportTarget::new(config.preferences.export_directory.as_deref())
+        .with_filename_template(config.preferences.export_filename_template.as_deref())
+        .with_jvm_info(jvm_info)
+        .with_sample_limit(config.preferences.export_sample_limit())
+}
+
+/// Re-runs the search for the current tab after the query changed. On the
+/// Classes tab the query also filters the table.
+fn refresh_search_results(app: &mut App, store: &MetricsStore) {
+    let results = match app.current_tab {
+        Tab::Threads => ThreadsView::search_threads(
+            store,
+            app.thread_sort,
+            app.thread_limit(),
+            &app.search_query,
+        ),
+        Tab::Classes => {
+            app.apply_class_filter();
+            ClassesView::search_classes(store, app.class_sort, &app.search_query)
+        }
+        _ => return,
+    };
+    app.update_search_results(results);
+}
+
+/// Local JVMs, narrowed to those matching `--match` when it's given.
+async fn discover_matching(cli: &Cli) -> jvm_tui::error::Result<Vec<DiscoveredJvm>> {
+    let jvms = discover_local_jvms().await?;
+    Ok(match cli.match_pattern {
+        Some(ref pattern) => filter_by_main_class(jvms, pattern),
+        None => jvms,
+    })
+}
+
+/// The single JVM `--match` selects; none or several is an error.
+fn single_match(cli: &Cli, jvms: &[DiscoveredJvm]) -> jvm_tui::error::Result<DiscoveredJvm> {
+    let pattern = cli.match_pattern.as_ref().map_or("", |p| p.as_str());
+    match jvms {
+        [jvm] => Ok(jvm.clone()),
+        [] => Err(AppError::NoJvm(format!(
+            "no running JVM's main class matches '{}'",
+            pattern
+        ))),
+        _ => Err(AppError::NoJvm(format!(
+            "'{}' matches {} JVMs ({}); narrow the pattern or use --pid",
+            pattern,
+            jvms.len(),
+            jvms.iter()
+                .map(|jvm| jvm.pid.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+/// Reports the outcome of a triggered GC as a toast.
+fn log_gc_result(app: &mut App, result: jvm_tui::error::Result<()>) {
+    match result {
+        Ok(()) => app.show_toast("GC triggered".to_string()),
+        Err(e) => app.show_error_toast(format!("GC trigger failed: {}", e)),
+    }
+}
+
+/// Connects to the JVM named by `--pid` or `--docker` for the modes that run
+/// without the TUI and its picker.
+async fn attach_headless(
+    cli: &Cli,
+    config: &Config,
+    mode: &str,
+) -> Result<Arc<RwLock<dyn JvmConnector>>> {
+    if let Some(ref container) = cli.docker {
+        Ok(Arc::new(RwLock::new(
+            DockerJdkConnector::attach(container).await?,
+        )))
+    } else if let Some(pid) = cli.pid {
+        let mut connector = local_connector(config);
+        connector.connect(pid).await?;
+        Ok(Arc::new(RwLock::new(connector)))
+    } else if cli.match_pattern.is_some() {
+        let jvm = single_match(cli, &discover_matching(cli).await?)?;
+        let mut connector = local_connector(config);
+        connector.connect(jvm.pid).await?;
+        Ok(Arc::new(RwLock::new(connector)))
+    } else {
+        Err(color_eyre::eyre::eyre!(
+            "{} requires --pid, --match or --docker",
+            mode
+        ))
+    }
+}
+
+/// Collects a single sample, exports it and prints the file path (or the
+/// export itself with `--export-stdout`), without touching the terminal.
+async fn run_snapshot(cli: &Cli, config: &Config) -> Result<()> {
+    let connector = attach_headless(cli, config, "--snapshot").await?;
+
+    let store = Arc::new(RwLock::new(MetricsStore::new(1)));
+    let interval = cli.interval.unwrap_or(config.preferences.default_interval);
+    MetricsCollector::new(connector.clone(), store.clone(), interval)
+        .collect_once()
+        .await?;
+
+    let store = store.read().await;
+    let jvm_info = connector.read().await.get_jvm_info().await.ok();
+    let target = export_target(config, jvm_info.as_ref());
+    let format = cli
+        .format
+        .unwrap_or(config.preferences.default_export_format);
+
+    if cli.export_stdout {
+        let store = target.limit(&store);
+        let mut stdout = std::io::stdout().lock();
+        match format {
+            ExportFormat::Json => export::write_metrics_json(&store, &mut stdout)?,
+            ExportFormat::Prometheus => export::write_metrics_prometheus(&store, &mut stdout)?,
+            ExportFormat::Csv => export::write_metrics_csv(&store, &mut stdout)?,
+            ExportFormat::Markdown => export::write_report_markdown(&store, &mut stdout)?,
+        }
+        stdout.flush()?;
+    } else {
+        let path = export::export_metrics(&store, &target, format)?;
+        println!("{}", path.display());
+    }
+
+    connector.write().await.disconnect().await?;
+    Ok(())
+}
+
+/// Reads one heap sample and prints a monitoring-plugin style status line,
+/// returning the exit code for `--health`.
+async fn run_health(cli: &Cli, config: &Config) -> i32 {
+    let sample = async {
+        let connector = attach_headless(cli, config, "--health").await?;
+        let store = Arc::new(RwLock::new(MetricsStore::new(1)));
+        let interval = cli.interval.unwrap_or(config.preferences.default_interval);
+        let collected = MetricsCollector::new(connector.clone(), store.clone(), interval)
+            .collect_once()
+            .await;
+        let _ = connector.write().await.disconnect().await;
+        collected?;
+
+        let heap = store.read().await.heap_history.latest().cloned();
+        heap.ok_or_else(|| color_eyre::eyre::eyre!("no heap sample collected"))
+    };
+
+    let heap = match sample.await {
+        Ok(heap) => heap,
+        Err(e) => {
+            println!("HEAP UNKNOWN - {}", e);
+            return exit_code::FAILURE;
+        }
+    };
+    let Some(percent) = heap.used_percent() else {
+        println!("HEAP UNKNOWN - maximum heap size not reported");
+        return exit_code::FAILURE;
+    };
+
+    let (status, code) = if percent >= cli.heap_max {
+        ("CRITICAL", exit_code::HEALTH_CRITICAL)
+    } else {
+        ("OK", 0)
+    };
+    println!(
+        "HEAP {} - {:.1}% used ({} / {} MB, threshold {}%)",
+        status,
+        percent,
+        heap.used_bytes / 1024 / 1024,
+        heap.max_bytes / 1024 / 1024,
+        cli.heap_max
+    );
+    code
+}
+
+/// Polls like the TUI would and prints a JSON line per poll until Ctrl-C,
+/// the JVM going away or the reader closing the pipe.
+async fn run_stream(cli: &Cli, config: &Config) -> Result<()> {
+    let connector = attach_headless(cli, config, "--stream").await?;
+
+    let store = Arc::new(RwLock::new(MetricsStore::new(2)));
+    let interval = cli.interval.unwrap_or(config.preferences.default_interval);
+    let collector = MetricsCollector::new(connector.clone(), store.clone(), interval)
+        .with_class_histogram_interval(config.preferences.class_histogram_interval);
+    let mut polls = collector.subscribe();
+    let unreachable_handle = collector.unreachable_handle();
+    let collector_handle = tokio::spawn(async move {
+        let _ = collector.run().await;
+    });
+
+    let mut unreachable_check = tokio::time::interval(Duration::from_secs(1));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let result = loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break Ok(()),
+            changed = polls.changed() => {
+                if changed.is_err() {
+                    break Ok(());
+                }
+                let store = store.read().await;
+                match export::write_stream_sample(&store, &mut std::io::stdout().lock()) {
+                    Ok(()) => {}
+                    Err(AppError::Io(e)) if e.kind() == std::io::ErrorKind::BrokenPipe => {
+                        break Ok(());
+                    }
+                    Err(e) => break Err(e.into()),
+                }
+            }
+            _ = unreachable_check.tick() => {
+                if unreachable_handle.load(Ordering::Relaxed) {
+                    break Err(AppError::Connection(
+                        "JVM process exited or became unreachable".to_string(),
+                    )
+                    .into());
+                }
+            }
+        }
+    };
+
+    collector_handle.abort();
+    let _ = connector.write().await.disconnect().await;
+    result
+}
+
+/// Keeps collecting in the background and serves the latest sample at
+/// `/metrics` for Prometheus to scrape, until Ctrl-C or the JVM going away.
+async fn run_serve(cli: &Cli, config: &Config, addr: SocketAddr) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| AppError::Config(format!("cannot listen on {}: {}", addr, e)))?;
+    let connector = attach_headless(cli, config, "--serve").await?;
+
+    let store = Arc::new(RwLock::new(MetricsStore::new(2)));
+    let interval = cli.interval.unwrap_or(config.preferences.default_interval);
+    let collector = MetricsCollector::new(connector.clone(), store.clone(), interval)
+        .with_class_histogram_interval(config.preferences.class_histogram_interval);
+    let unreachable_handle = collector.unreachable_handle();
+    let collector_handle = tokio::spawn(async move {
+        let _ = collector.run().await;
+    });
+
+    if !cli.quiet {
+        eprintln!("Serving metrics at http://{}/metrics", addr);
+    }
+    let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+    let mut server = tokio::spawn(jvm_tui::server::serve(listener, store, async {
+        let _ = stopped.await;
+    }));
+
+    let mut unreachable_check = tokio::time::interval(Duration::from_secs(1));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let result = loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break Ok(()),
+            served = &mut server => {
+                break match served {
+                    Ok(served) => served.map_err(Into::into),
+                    Err(e) => Err(e.into()),
+                };
+            }
+            _ = unreachable_check.tick() => {
+                if unreachable_handle.load(Ordering::Relaxed) {
+                    break Err(AppError::Connection(
+                        "JVM process exited or became unreachable".to_string(),
+                    )
+                    .into());
+                }
+            }
+        }
+    };
+
+    let _ = stop.send(());
+    collector_handle.abort();
+    let _ = connector.write().await.disconnect().await;
+    result
 }
 
 #[tokio::main]
-async fn main() -> Result<()> {
+async fn main() {
+    if let Err(report) = run().await {
+        let code = report
+            .downcast_ref::<AppError>()
+            .map_or(exit_code::FAILURE, AppError::exit_code);
+        eprintln!("Error: {:?}", report);
+        std::process::exit(code);
+    }
+}
+
+async fn run() -> Result<()> {
     color_eyre::install()?;
 
     let cli = Cli::parse();
+    if let Some(Command::Config {
+        action: ConfigCommand::Check { path },
+    }) = &cli.command
+    {
+        check_config(path.clone().or_else(|| cli.config.clone()));
+        return Ok(());
+    }
 
-    let config = if let Some(ref config_path) = cli.config {
+    let mut config = if let Some(ref config_path) = cli.config {
         Config::load_from_file(config_path)?
     } else {
         Config::load()?
     };
+    if let Some(ref gc_log) = cli.gc_log {
+        config.preferences.gc_log_path = Some(gc_log.display().to_string());
+    }
+    if let Some(max_samples) = cli.export_max_samples {
+        config.preferences.export_max_samples = Some(max_samples as usize);
+    }
 
-    let jvms = discover_local_jvms().await?;
+    if cli.health {
+        std::process::exit(run_health(&cli, &config).await);
+    }
+    if cli.snapshot {
+        return run_snapshot(&cli, &config).await;
+    }
+    if cli.stream.is_some() {
+        return run_stream(&cli, &config).await;
+    }
+    if let Some(addr) = cli.serve {
+        return run_serve(&cli, &config, addr).await;
+    }
 
-    if jvms.is_empty() && config.connections.is_empty() {
-        println!("No JVM processes or saved connections found.");
-        println!("Make sure you have running Java applications, or");
-        println!("add saved connections to your config file.");
-        return Ok(());
+    let replay = match cli.replay {
+        Some(ref path) => match Recording::load(path) {
+            Ok(recording) => Some(ReplayConnector::new(recording)),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(e.exit_code());
+            }
+        },
+        None => None,
+    };
+
+    let docker_connector = match cli.docker {
+        Some(ref container) => match DockerJdkConnector::attach(container).await {
+            Ok(connector) => Some(connector),
+            Err(e) => {
+                eprintln!("Error: failed to attach to container {}: {}", container, e);
+                std::process::exit(e.exit_code());
+            }
+        },
+        None => None,
+    };
+
+    let profile = match cli.profile {
+        Some(ref name) => Some(config.get_connection(name).ok_or_else(|| {
+            let names: Vec<&str> = config.connections.iter().map(|c| c.name()).collect();
+            AppError::Config(format!(
+                "no saved connection named '{}' (available: {})",
+                name,
+                if names.is_empty() {
+                    "none".to_string()
+                } else {
+                    names.join(", ")
+                }
+            ))
+        })?),
+        None => None,
+    };
+
+    // Only local profiles need the discovered JVMs; remote ones shouldn't
+    // depend on a local JDK
+    let jvms = if replay.is_some()
+        || docker_connector.is_some()
+        || profile.is_some_and(|p| !matches!(p, ConnectionProfile::Local { .. }))
+    {
+        Vec::new()
+    } else {
+        discover_matching(&cli).await?
+    };
+
+    // With --match the picker only offers the matching JVMs, not saved
+    // connections, and a single match is attached to straight away
+    let matched = match cli.match_pattern {
+        Some(_) if jvms.len() > 1 => None,
+        Some(_) => match single_match(&cli, &jvms) {
+            Ok(jvm) => Some(jvm),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(e.exit_code());
+            }
+        },
+        None => None,
+    };
+    let saved_connections = if cli.match_pattern.is_some() {
+        Vec::new()
+    } else {
+        config.connections.clone()
+    };
+
+    let preselected = match (replay, docker_connector, profile) {
+        (Some(connector), _, _) => Some(SelectedConnection::Replay(connector)),
+        (None, Some(connector), _) => Some(SelectedConnection::Docker(connector)),
+        (None, None, Some(profile)) => Some(SelectedConnection::from_profile(profile, &jvms)?),
+        (None, None, None) => matched.map(SelectedConnection::LocalJvm),
+    };
+
+    if preselected.is_none() && jvms.is_empty() && config.connections.is_empty() {
+        if cli.quiet {
+            eprintln!("Error: no JVM processes or saved connections found");
+        } else {
+            println!("No JVM processes or saved connections found.");
+            println!("Make sure you have running Java applications, or");
+            println!("add saved connections to your config file.");
+        }
+        std::process::exit(exit_code::NO_JVM);
     }
 
     let mut terminal = terminal::setup_terminal()?;
-    let mut picker = JvmPickerScreen::new(jvms.clone(), config.connections.clone());
 
-    let selected_connection = loop {
-        terminal.draw(|frame| {
-            picker.render(frame, &Theme);
-        })?;
+    let connected = if let Some(selected) = preselected {
+        match selected.connect(&config).await {
+            Ok(connected) => connected,
+            Err(e) => {
+                terminal::restore_terminal(&mut terminal)?;
+                eprintln!("Error: {}", e);
+                std::process::exit(e.exit_code());
+            }
+        }
+    } else {
+        let mut picker = JvmPickerScreen::new(jvms.clone(), saved_connections.clone());
 
-        if event::poll(Duration::from_millis(100))? {
-            if let CrosstermEvent::Key(key) = event::read()? {
-                match (key.code, key.modifiers) {
-                    (KeyCode::Char('q'), _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                        terminal::restore_terminal(&mut terminal)?;
-                        return Ok(());
-                    }
-                    (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
-                        picker.next();
-                    }
-                    (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
-                        picker.previous();
-                    }
-                    (KeyCode::Enter, _) => {
-                        // Handle saved connection selection
-                        if let Some(conn) = picker.selected_connection() {
-                            match conn {
-                                ConnectionProfile::Local { pid: Some(pid), .. } => {
-                                    // Find the JVM with this PID
-                                    if let Some(jvm) = jvms.iter().find(|j| j.pid == *pid) {
-                                        break SelectedConnection::LocalJvm(jvm.clone());
-                                    } else {
-                                        // PID not found, show error and continue
-                                        terminal::restore_terminal(&mut terminal)?;
-                                        eprintln!("Error: Saved connection references PID {} which is not running", pid);
-                                        return Ok(());
+        loop {
+            terminal.draw(|frame| {
+                picker.render(frame, &Theme);
+            })?;
+
+            if event::poll(Duration::from_millis(100))? {
+                if let CrosstermEvent::Key(key) = event::read()? {
+                    match (key.code, key.modifiers) {
+                        (KeyCode::Char('q'), _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                            terminal::restore_terminal(&mut terminal)?;
+                            return Ok(());
+                        }
+                        (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
+                            picker.next();
+                        }
+                        (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
+                            picker.previous();
+                        }
+                        (KeyCode::Enter, _) => {
+                            // Saved connections are tested before leaving the
+                            // picker, so a broken one can't end the session
+                            if let Some(conn) = picker.selected_connection().cloned() {
+                                picker.status =
+                                    Some(PickerStatus::Testing(conn.name().to_string()));
+                                terminal.draw(|frame| {
+                                    picker.render(frame, &Theme);
+                                })?;
+                                let tested = match SelectedConnection::from_profile(&conn, &jvms) {
+                                    Ok(selected) => tokio::time::timeout(
+                                        CONNECTION_TEST_TIMEOUT,
+                                        selected.connect(&config),
+                                    )
+                                    .await
+                                    .unwrap_or_else(|_| {
+                                        Err(AppError::Connection(format!(
+                                            "no answer within {}s",
+                                            CONNECTION_TEST_TIMEOUT.as_secs()
+                                        )))
+                                    }),
+                                    Err(e) => Err(e),
+                                };
+                                match tested {
+                                    Ok(connected) => break connected,
+                                    Err(e) => {
+                                        picker.status = Some(PickerStatus::Failed(format!(
+                                            "{}: {}",
+                                            conn.name(),
+                                            e
+                                        )));
                                     }
                                 }
-                                ConnectionProfile::Local { pid: None, .. } => {
-                                    // Local connection without PID - shouldn't happen in valid config
-                                    terminal::restore_terminal(&mut terminal)?;
-                                    eprintln!("Error: Local connection must specify a PID");
-                                    return Ok(());
-                                }
-                                ConnectionProfile::Jolokia {
-                                    url,
-                                    username,
-                                    password,
-                                    ..
-                                } => {
-                                    break SelectedConnection::Jolokia {
-                                        url: url.clone(),
-                                        username: username.clone(),
-                                        password: password.clone(),
-                                    };
-                                }
-                                ConnectionProfile::SshJdk {
-                                    ssh_host,
-                                    ssh_user,
-                                    ssh_port,
-                                    ssh_key,
-                                    ssh_password,
-                                    pid,
-                                    ..
-                                } => {
-                                    break SelectedConnection::SshJdk {
-                                        host: ssh_host.clone(),
-                                        user: ssh_user.clone(),
-                                        port: *ssh_port,
-                                        key: ssh_key.clone(),
-                                        password: ssh_password.clone(),
-                                        pid: *pid,
-                                    };
-                                }
-                                ConnectionProfile::SshJolokia { .. } => {
-                                    terminal::restore_terminal(&mut terminal)?;
-                                    println!("SSH+Jolokia tunnel connections coming soon");
-                                    println!("For now, use:");
-                                    println!("  - Direct Jolokia HTTP");
-                                    println!("  - SSH+JDK (jcmd/jstat over SSH)");
-                                    println!("  - Local JVMs");
-                                    return Ok(());
+                            } else if let Some(jvm) = picker.selected_jvm().cloned() {
+                                match SelectedConnection::LocalJvm(jvm).connect(&config).await {
+                                    Ok(connected) => break connected,
+                                    Err(e) => {
+                                        picker.status = Some(PickerStatus::Failed(e.to_string()));
+                                    }
                                 }
                             }
                         }
-                        // Handle discovered JVM selection
-                        else if let Some(jvm) = picker.selected_jvm() {
-                            break SelectedConnection::LocalJvm(jvm.clone());
+                        (KeyCode::Char('r'), _) => {
+                            let jvms = discover_matching(&cli).await?;
+                            picker = JvmPickerScreen::new(jvms, saved_connections.clone());
                         }
+                        _ => {}
                     }
-                    (KeyCode::Char('r'), _) => {
-                        let jvms = discover_local_jvms().await?;
-                        picker = JvmPickerScreen::new(jvms, config.connections.clone());
-                    }
-                    _ => {}
                 }
             }
         }
     };
+    let Connected {
+        connector: connector_arc,
+        jvm_info,
+        tool_warning,
+        label: connection_label,
+    } = connected;
 
-    let jvm_info;
-    let connector_arc: Arc<RwLock<dyn JvmConnector>> = match selected_connection {
-        SelectedConnection::LocalJvm(jvm) => {
-            let mut connector = JdkToolsConnector::new();
-            connector.connect(jvm.pid).await?;
-            jvm_info = connector.get_jvm_info().await?;
-            Arc::new(RwLock::new(connector))
-        }
-        SelectedConnection::Jolokia {
-            url,
-            username,
-            password,
-        } => {
-            let mut connector = JolokiaConnector::new(url, username, password);
-            connector.connect(0).await?;
-            jvm_info = connector.get_jvm_info().await?;
-            Arc::new(RwLock::new(connector))
-        }
-        SelectedConnection::SshJdk {
-            host,
-            user,
-            port,
-            key,
-            password,
-            pid,
-        } => {
-            let mut connector = SshJdkConnector::new(host, port, user, key, password, pid);
-            connector.connect(pid).await?;
-            jvm_info = connector.get_jvm_info().await?;
-            Arc::new(RwLock::new(connector))
-        }
-    };
+    if cli.debug_raw {
+        connector_arc.write().await.capture_raw_output();
+    }
 
     let interval = cli.interval.unwrap_or(config.preferences.default_interval);
     let history_size = config.preferences.max_history_samples;
     let store = Arc::new(RwLock::new(MetricsStore::new(history_size)));
     let mut app = App::new(store.clone());
     app.set_jvm_info(jvm_info);
-    let collector = MetricsCollector::new(connector_arc.clone(), store.clone(), interval);
+    app.hide_columns(
+        &config.preferences.hidden_thread_columns,
+        &config.preferences.hidden_class_columns,
+    );
+    app.max_threads_displayed = config.preferences.max_threads_displayed;
+    app.selected_export_format = config.preferences.default_export_format;
+    app.compact = config.preferences.compact_layout;
+    app.connection_label = Some(connection_label.to_string());
+    app.keymap = KeyMap::new(&config.keybindings).map_err(AppError::Config)?;
+    app.allow_trigger_gc = config.advanced.allow_trigger_gc;
+    app.confirm_destructive = config.advanced.confirm_destructive;
+    if let Some(warning) = tool_warning {
+        app.set_warning(warning);
+    }
+    let mut collector = MetricsCollector::new(connector_arc.clone(), store.clone(), interval)
+        .with_class_histogram_interval(config.preferences.class_histogram_interval);
+    if config.preferences.adaptive_interval {
+        collector = collector.with_adaptive_interval(config.preferences.max_adaptive_interval);
+    }
+    if let Some(csv_log) = cli.csv_log.clone() {
+        collector = collector.with_csv_log(csv_log);
+    }
+    let mut alert_engine = AlertEngine::new(&config.alerts);
+    // Collect only what the visible tab shows, plus threads for alert rules
+    // on thread metrics
+    let poll_active_tab_only = config.preferences.poll_active_tab_only;
+    let always_collected = if alert_engine.uses_thread_metrics() {
+        MetricSet::THREADS
+    } else {
+        MetricSet::NONE
+    };
+    let interval_handle = collector.interval_handle();
+    let unreachable_handle = collector.unreachable_handle();
+    let metrics_handle = collector.metrics_handle();
+    let poll_status_handle = collector.poll_status_handle();
+    let (event_log_tx, mut event_log_rx) = mpsc::channel(64);
+    let collector = collector.with_event_log(event_log_tx);
+    app.poll_interval = Duration::from_millis(interval_handle.load(Ordering::Relaxed));
 
     let collector_handle = tokio::spawn(async move {
         let _ = collector.run().await;
     });
 
+    let mut quit_requested = false;
+    let mut last_recorded = Instant::now();
     loop {
+        if poll_active_tab_only {
+            let wanted = app.current_tab.metrics().union(always_collected);
+            metrics_handle.store(wanted.bits(), Ordering::Relaxed);
+        }
+        app.poll_status = *poll_status_handle.borrow();
+        while let Ok(entry) = event_log_rx.try_recv() {
+            app.event_log.push(entry);
+        }
+
         let store_snapshot = {
             let store = store.read().await;
             store.clone()
         };
 
+        if let Some(ref path) = cli.record {
+            if last_recorded.elapsed() >= RECORD_INTERVAL {
+                last_recorded = Instant::now();
+                if let Err(e) = write_recording(path, app.jvm_info.as_ref(), &store_snapshot) {
+                    app.show_error_toast(format!("Recording to {} failed: {}", path.display(), e));
+                }
+            }
+        }
+
+        if !alert_engine.is_empty() {
+            for alert in alert_engine.evaluate(&store_snapshot, Instant::now()) {
+                app.record_alert(&alert);
+                match alert.action {
+                    AlertAction::Banner => {}
+                    AlertAction::Beep => {
+                        let _ = terminal::beep();
+                    }
+                    AlertAction::Export => {
+                        let target = export_target(&config, app.jvm_info.as_ref());
+                        match export::export_metrics_json(&store_snapshot, &target) {
+                            Ok(path) => app.show_toast(format!(
+                                "Alert {}: metrics exported to {}",
+                                alert.message,
+                                path.display()
+                            )),
+                            Err(e) => app.show_error_toast(format!("Alert export failed: {}", e)),
+                        }
+                    }
+                }
+            }
+            app.active_alerts = alert_engine.active();
+        }
+
+        if unreachable_handle.load(Ordering::Relaxed) && !matches!(app.mode, AppMode::Error(_)) {
+            app.show_error("JVM process exited or became unreachable".to_string());
+        }
+
+        app.expire_toasts(Instant::now());
         terminal.draw(|frame| {
             MonitoringScreen::render(frame, &app, &store_snapshot);
         })?;
@@ -231,6 +966,7 @@ async fn main() -> Result<()> {
                             let mut conn = connector_arc.write().await;
                             match conn.reconnect().await {
                                 Ok(_) => {
+                                    unreachable_handle.store(false, Ordering::Relaxed);
                                     app.clear_loading();
                                 }
                                 Err(e) => {
@@ -245,53 +981,98 @@ async fn main() -> Result<()> {
                         KeyCode::Esc => {
                             app.cancel_search();
                         }
-                        KeyCode::Enter => {
-                            if !app.search_results.is_empty() {
-                                app.mode = AppMode::Normal;
-                            }
+                        KeyCode::Enter
+                            if app.current_tab == Tab::Classes
+                                || !app.search_results.is_empty() =>
+                        {
+                            app.mode = AppMode::Normal;
                         }
-                        KeyCode::Char('n') if key.modifiers.is_empty() => {
+                        KeyCode::Char('n')
+                            if key.modifiers.is_empty() && app.current_tab != Tab::Classes =>
+                        {
                             app.next_search_result();
                         }
                         KeyCode::Char('N') | KeyCode::Char('n')
-                            if key.modifiers.contains(KeyModifiers::SHIFT) =>
+                            if key.modifiers.contains(KeyModifiers::SHIFT)
+                                && app.current_tab != Tab::Classes =>
                         {
                             app.prev_search_result();
                         }
                         KeyCode::Backspace => {
                             app.pop_search_char();
-                            if app.current_tab == Tab::Threads {
-                                let store_read = store.read().await;
-                                let results =
-                                    ThreadsView::search_threads(&store_read, &app.search_query);
-                                app.update_search_results(results);
-                            }
+                            refresh_search_results(&mut app, &*store.read().await);
                         }
                         KeyCode::Char(c) => {
                             app.push_search_char(c);
+                            refresh_search_results(&mut app, &*store.read().await);
+                            // Classes are filtered rather than jumped to
                             if app.current_tab == Tab::Threads {
-                                let store_read = store.read().await;
-                                let results =
-                                    ThreadsView::search_threads(&store_read, &app.search_query);
-                                if !results.is_empty() {
-                                    app.scroll_offset = results[0];
+                                if let Some(&first) = app.search_results.first() {
+                                    app.scroll_offset = first;
                                 }
-                                app.update_search_results(results);
                             }
                         }
                         _ => {}
                     },
+                    AppMode::ConfirmGc if app.confirm_destructive => match key.code {
+                        KeyCode::Enter => {
+                            if app.gc_confirmation_matches() {
+                                let result = connector_arc.read().await.trigger_gc().await;
+                                app.cancel_confirmation();
+                                log_gc_result(&mut app, result);
+                            } else {
+                                app.gc_confirmation_input.clear();
+                            }
+                        }
+                        KeyCode::Esc => app.cancel_confirmation(),
+                        KeyCode::Backspace => {
+                            app.gc_confirmation_input.pop();
+                        }
+                        KeyCode::Char(c) => app.gc_confirmation_input.push(c),
+                        _ => {}
+                    },
                     AppMode::ConfirmGc => match key.code {
                         KeyCode::Char('y') | KeyCode::Char('Y') => {
-                            let conn = connector_arc.read().await;
-                            let _ = conn.trigger_gc().await;
+                            let result = connector_arc.read().await.trigger_gc().await;
                             app.cancel_confirmation();
+                            log_gc_result(&mut app, result);
                         }
                         KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                             app.cancel_confirmation();
                         }
                         _ => {}
                     },
+                    AppMode::ConfirmHeapDump => match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => {
+                            if let Some(plan) = app.heap_dump_plan.take() {
+                                let path = plan.path.display().to_string();
+                                app.show_loading(format!("Writing heap dump to {}...", path));
+                                terminal.draw(|frame| {
+                                    MonitoringScreen::render(frame, &app, &store_snapshot);
+                                })?;
+
+                                let result = connector_arc.read().await.dump_heap(&plan.path).await;
+                                match result {
+                                    Ok(()) => {
+                                        let size = std::fs::metadata(&plan.path)
+                                            .map(|metadata| metadata.len())
+                                            .unwrap_or(0);
+                                        app.show_heap_dump_success(path, size);
+                                    }
+                                    Err(e) => {
+                                        app.show_error_toast(format!("Heap dump failed: {}", e));
+                                    }
+                                }
+                            } else {
+                                app.cancel_confirmation();
+                            }
+                        }
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                            app.heap_dump_plan = None;
+                            app.cancel_confirmation();
+                        }
+                        _ => {}
+                    },
                     AppMode::SelectExportFormat => match key.code {
                         KeyCode::Char('j') | KeyCode::Down => {
                             app.next_export_format();
@@ -311,23 +1092,16 @@ async fn main() -> Result<()> {
                         KeyCode::Char('y') | KeyCode::Char('Y') => {
                             app.show_loading("Exporting data...".to_string());
                             let store_read = store.read().await;
-                            let export_dir = config.preferences.export_directory.as_deref();
+                            let target = export_target(&config, app.jvm_info.as_ref());
                             let result = match app.current_tab {
-                                Tab::Threads => export::export_thread_dump(
-                                    &store_read.thread_snapshot,
-                                    export_dir,
+                                Tab::Threads => {
+                                    export::export_thread_dump(&store_read.thread_snapshot, &target)
+                                }
+                                _ => export::export_metrics(
+                                    &store_read,
+                                    &target,
+                                    app.selected_export_format,
                                 ),
-                                _ => match app.selected_export_format {
-                                    ExportFormat::Json => {
-                                        export::export_metrics_json(&store_read, export_dir)
-                                    }
-                                    ExportFormat::Prometheus => {
-                                        export::export_metrics_prometheus(&store_read, export_dir)
-                                    }
-                                    ExportFormat::Csv => {
-                                        export::export_metrics_csv(&store_read, export_dir)
-                                    }
-                                },
                             };
 
                             match result {
@@ -335,7 +1109,7 @@ async fn main() -> Result<()> {
                                     app.show_export_success(path.to_string_lossy().to_string());
                                 }
                                 Err(e) => {
-                                    app.show_error(format!("Export failed: {}", e));
+                                    app.show_error_toast(format!("Export failed: {}", e));
                                 }
                             }
                         }
@@ -344,63 +1118,402 @@ async fn main() -> Result<()> {
                         }
                         _ => {}
                     },
-                    AppMode::ExportSuccess(_) => match key.code {
-                        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
+                    AppMode::AlertHistory => match key.code {
+                        KeyCode::Char('j') | KeyCode::Down
+                            if app.alert_history_scroll + 1 < app.alert_history.len() =>
+                        {
+                            app.alert_history_scroll += 1;
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            app.alert_history_scroll = app.alert_history_scroll.saturating_sub(1);
+                        }
+                        KeyCode::Esc | KeyCode::Char('a') | KeyCode::Char('q') => {
                             app.cancel_confirmation();
                         }
                         _ => {}
                     },
-                    AppMode::Normal => match (key.code, key.modifiers) {
-                        (KeyCode::Char('q'), _) | (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                    AppMode::ShellPrompt => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_confirmation();
+                        }
+                        KeyCode::Enter => {
+                            app.cancel_confirmation();
+                            let command = app.shell_command.clone();
+                            if let Err(e) =
+                                terminal::suspend_and_run(&mut terminal, &command, &app.shell_env())
+                            {
+                                app.show_error_toast(format!("Shell command failed: {}", e));
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            app.shell_command.pop();
+                        }
+                        KeyCode::Char(c) => {
+                            app.shell_command.push(c);
+                        }
+                        _ => {}
+                    },
+                    AppMode::Command => match key.code {
+                        KeyCode::Esc => {
+                            app.cancel_confirmation();
+                        }
+                        KeyCode::Enter => {
+                            let pid = app.jvm_info.as_ref().map(|info| info.pid).unwrap_or(0);
+                            match sanitize_diagnostic_command(&app.diagnostic_command, pid) {
+                                Ok(command) => {
+                                    app.show_loading(format!("Running jcmd {}...", command));
+                                    terminal.draw(|frame| {
+                                        MonitoringScreen::render(frame, &app, &store_snapshot);
+                                    })?;
+
+                                    let result =
+                                        connector_arc.read().await.run_diagnostic(&command).await;
+                                    app.diagnostic_command = command;
+                                    match result {
+                                        Ok(output) => app.show_command_output(output),
+                                        Err(e) => app.show_error_toast(format!(
+                                            "jcmd command failed: {}",
+                                            e
+                                        )),
+                                    }
+                                }
+                                Err(e) => {
+                                    app.cancel_confirmation();
+                                    app.show_toast(e.to_string());
+                                }
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            app.diagnostic_command.pop();
+                        }
+                        KeyCode::Char(c) => {
+                            app.diagnostic_command.push(c);
+                        }
+                        _ => {}
+                    },
+                    AppMode::CommandOutput | AppMode::RawOutput => match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => app.scroll_command_output(1),
+                        KeyCode::Char('k') | KeyCode::Up => app.scroll_command_output(-1),
+                        KeyCode::PageDown | KeyCode::Char(' ') => app.scroll_command_output(20),
+                        KeyCode::PageUp => app.scroll_command_output(-20),
+                        KeyCode::Char('g') | KeyCode::Home => app.diagnostic_scroll = 0,
+                        KeyCode::Char('G') | KeyCode::End => app.scroll_command_output(isize::MAX),
+                        KeyCode::Esc | KeyCode::Char('q') => {
+                            app.cancel_confirmation();
+                        }
+                        _ => {}
+                    },
+                    AppMode::ProcessInfo => match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('i') => {
+                            app.cancel_confirmation();
+                        }
+                        _ => {}
+                    },
+                    AppMode::SystemProperties => match key.code {
+                        KeyCode::Down => app.scroll_system_properties(1),
+                        KeyCode::Up => app.scroll_system_properties(-1),
+                        KeyCode::PageDown => app.scroll_system_properties(20),
+                        KeyCode::PageUp => app.scroll_system_properties(-20),
+                        KeyCode::Esc => {
+                            app.cancel_confirmation();
+                        }
+                        KeyCode::Backspace => {
+                            app.system_properties_filter.pop();
+                            app.system_properties_scroll = 0;
+                        }
+                        KeyCode::Char(c) => {
+                            app.system_properties_filter.push(c);
+                            app.system_properties_scroll = 0;
+                        }
+                        _ => {}
+                    },
+                    AppMode::ColumnMenu => match key.code {
+                        KeyCode::Char('j') | KeyCode::Down => {
+                            app.column_menu_next();
+                        }
+                        KeyCode::Char('k') | KeyCode::Up => {
+                            app.column_menu_previous();
+                        }
+                        KeyCode::Char(' ') | KeyCode::Enter => {
+                            app.toggle_selected_column();
+                        }
+                        KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('q') => {
+                            app.cancel_confirmation();
+                        }
+                        _ => {}
+                    },
+                    AppMode::Normal => match app.keymap.action_for(&key, app.current_tab) {
+                        Some(Action::Quit) => {
+                            quit_requested = true;
                             break;
                         }
-                        (KeyCode::Char('?'), _) => {
+                        Some(Action::Help) => {
                             app.toggle_help();
                         }
-                        (KeyCode::Char('1'), _) => app.select_tab(0),
-                        (KeyCode::Char('2'), _) => app.select_tab(1),
-                        (KeyCode::Char('3'), _) => app.select_tab(2),
-                        (KeyCode::Char('4'), _) => app.select_tab(3),
-                        (KeyCode::Char('5'), _) => app.select_tab(4),
-                        (KeyCode::Char('l'), _) | (KeyCode::Tab, _) | (KeyCode::Right, _) => {
-                            app.next_tab()
+                        Some(Action::SelectTab) => {
+                            // One key per tab, in tab order
+                            if let Some(index) = app
+                                .keymap
+                                .keys_for(Action::SelectTab)
+                                .iter()
+                                .position(|code| *code == key.code)
+                            {
+                                app.select_tab(index);
+                            }
                         }
-                        (KeyCode::Char('h'), _) | (KeyCode::BackTab, _) | (KeyCode::Left, _) => {
-                            app.previous_tab()
+                        Some(Action::PanTimelineForward) => {
+                            app.gc_timeline.pan_right(store_snapshot.gc_history.len());
                         }
-                        (KeyCode::Char('j'), _) | (KeyCode::Down, _) => {
-                            app.scroll_down();
+                        Some(Action::PanTimelineBack) => {
+                            app.gc_timeline.pan_left(store_snapshot.gc_history.len());
                         }
-                        (KeyCode::Char('k'), _) | (KeyCode::Up, _) => {
+                        Some(Action::ZoomTimelineIn) => {
+                            app.gc_timeline.zoom_in(store_snapshot.gc_history.len());
+                        }
+                        Some(Action::ZoomTimelineOut) => {
+                            app.gc_timeline.zoom_out(store_snapshot.gc_history.len());
+                        }
+                        Some(Action::NextTab) => app.next_tab(),
+                        Some(Action::PreviousTab) => app.previous_tab(),
+                        Some(Action::ScrollDown) => {
+                            // Classes may be filtered; the view clamps to what's left
+                            let rows = match app.current_tab {
+                                Tab::Threads => {
+                                    let threads = store_snapshot.thread_snapshot.len();
+                                    app.thread_limit()
+                                        .map_or(threads, |limit| threads.min(limit))
+                                }
+                                Tab::Classes => store_snapshot.class_histogram.len(),
+                                _ => app.jvm_info.as_ref().map_or(0, |info| info.vm_flags.len()),
+                            };
+                            app.scroll_down(rows);
+                        }
+                        Some(Action::ScrollUp) => {
                             app.scroll_up();
                         }
-                        (KeyCode::Char('g'), _) => {
+                        Some(Action::TriggerGc) => {
                             app.show_gc_confirmation();
                         }
-                        (KeyCode::Char('e'), _) => {
+                        Some(Action::HeapDump) => {
+                            let estimated_bytes = store_snapshot
+                                .heap_history
+                                .latest()
+                                .map(|heap| heap.used_bytes)
+                                .unwrap_or(0);
+                            let target = export_target(&config, app.jvm_info.as_ref());
+                            let connector = connector_arc.read().await;
+                            if !connector.supports_heap_dump() {
+                                app.show_error_toast(
+                                    "Heap dumps are not supported for this connection".to_string(),
+                                );
+                            } else {
+                                match export::plan_heap_dump(
+                                    estimated_bytes,
+                                    &target,
+                                    connector.is_local(),
+                                ) {
+                                    Ok(plan) => app.show_heap_dump_confirmation(plan),
+                                    Err(e) => {
+                                        app.show_error_toast(format!("Heap dump failed: {}", e))
+                                    }
+                                }
+                            }
+                        }
+                        Some(Action::Export) => {
                             if app.current_tab == Tab::Threads {
                                 app.show_export_confirmation();
                             } else {
                                 app.show_export_format_selector();
                             }
                         }
-                        (KeyCode::Char('/'), _) => {
-                            if app.current_tab == Tab::Threads {
-                                app.start_search();
+                        Some(Action::SupportBundle) => {
+                            // Threads are only polled on their tab, so dump them
+                            // now; properties are best effort
+                            let connector = connector_arc.read().await;
+                            let threads = connector
+                                .get_thread_info()
+                                .await
+                                .unwrap_or_else(|_| store_snapshot.thread_snapshot.clone());
+                            let properties =
+                                connector.get_system_properties().await.unwrap_or_default();
+                            drop(connector);
+                            let target = export_target(&config, app.jvm_info.as_ref());
+                            match export::export_support_bundle(
+                                &store_snapshot,
+                                &threads,
+                                &properties,
+                                &target,
+                            ) {
+                                Ok(path) => app.show_export_success(path.display().to_string()),
+                                Err(e) => {
+                                    app.show_error_toast(format!("Support bundle failed: {}", e))
+                                }
                             }
                         }
-                        (KeyCode::Char('r'), _) => {
-                            let mut store_mut = store.write().await;
-                            *store_mut = MetricsStore::new(100);
+                        Some(Action::Baseline) => {
+                            let store_read = store.read().await;
+                            let message = if app.toggle_class_baseline(&store_read.class_histogram)
+                            {
+                                "Baseline captured; showing class growth since now"
+                            } else if store_read.class_histogram.is_empty() {
+                                "No class histogram yet to use as a baseline"
+                            } else {
+                                "Baseline dropped"
+                            };
+                            app.show_toast(message.to_string());
+                        }
+                        Some(Action::Sort) => {
+                            if app.current_tab == Tab::Classes {
+                                app.cycle_class_sort();
+                            } else {
+                                app.cycle_thread_sort();
+                                app.show_toast(format!(
+                                    "Threads sorted by {}",
+                                    app.thread_sort.display_name()
+                                ));
+                            }
+                        }
+                        Some(Action::Search) => {
+                            app.start_search();
+                        }
+                        Some(Action::ShowAllThreads) => match app.max_threads_displayed {
+                            Some(limit) => {
+                                app.toggle_show_all_threads();
+                                let message = if app.show_all_threads {
+                                    "Showing all threads".to_string()
+                                } else {
+                                    format!("Showing the first {} threads", limit)
+                                };
+                                app.show_toast(message);
+                            }
+                            None => app.show_toast(
+                                "All threads are listed; set max_threads_displayed to cap the list"
+                                    .to_string(),
+                            ),
+                        },
+                        Some(Action::Columns) => {
+                            app.show_column_menu();
+                        }
+                        Some(Action::Shell) => {
+                            app.start_shell_prompt();
+                        }
+                        Some(Action::DiagnosticCommand) => {
+                            app.start_command_prompt();
+                        }
+                        Some(Action::ToggleCompact) => {
+                            app.toggle_compact();
+                        }
+                        Some(Action::ToggleEventLog) => {
+                            app.toggle_event_log();
+                        }
+                        Some(Action::SystemProperties) => {
+                            let result = connector_arc.read().await.get_system_properties().await;
+                            match result {
+                                Ok(properties) => app.show_system_properties(properties),
+                                Err(e) => app
+                                    .show_toast(format!("Could not read system properties: {}", e)),
+                            }
+                        }
+                        Some(Action::ProcessInfo) => {
+                            let connector = connector_arc.read().await;
+                            match connector.get_process_command_line().await {
+                                Ok(command_line) => {
+                                    let working_dir =
+                                        connector.get_system_properties().await.ok().and_then(
+                                            |properties| {
+                                                properties
+                                                    .into_iter()
+                                                    .find(|(key, _)| key == "user.dir")
+                                                    .map(|(_, value)| value)
+                                            },
+                                        );
+                                    app.show_process_info(ProcessInfo {
+                                        command_line,
+                                        working_dir,
+                                    });
+                                }
+                                Err(e) => app.show_error_toast(format!(
+                                    "Could not read the command line: {}",
+                                    e
+                                )),
+                            }
+                        }
+                        Some(Action::RawOutput) => {
+                            let outputs = connector_arc.read().await.last_raw_output();
+                            if let Some(outputs) = outputs {
+                                app.show_raw_output(raw_output::format_outputs(&outputs));
+                            } else {
+                                app.show_toast(
+                                    "Start with --debug-raw to keep raw tool output".to_string(),
+                                );
+                            }
+                        }
+                        Some(Action::Alerts) => {
+                            app.show_alert_history();
+                        }
+                        Some(action @ (Action::PollSlower | Action::PollFaster)) => {
+                            let slower = action == Action::PollSlower;
+                            let next = collector::step_interval(app.poll_interval, slower);
+                            interval_handle.store(next.as_millis() as u64, Ordering::Relaxed);
+                            app.poll_interval = next;
+                            app.show_toast(format!(
+                                "Polling every {}",
+                                humantime::format_duration(next)
+                            ));
+                        }
+                        Some(action @ (Action::CopyStack | Action::CopyThreadDump)) => {
+                            let store_read = store.read().await;
+                            let threads = &store_read.thread_snapshot;
+                            let (text, what) = if action == Action::CopyThreadDump {
+                                (
+                                    export::format_thread_dump(threads),
+                                    "Thread dump".to_string(),
+                                )
+                            } else {
+                                let ordered =
+                                    ThreadsView::ordered_threads(&store_read, app.thread_sort);
+                                match ordered.get(app.scroll_offset) {
+                                    Some(thread) => (
+                                        export::format_thread(thread),
+                                        format!("Stack of \"{}\"", thread.name),
+                                    ),
+                                    None => continue,
+                                }
+                            };
+
+                            match clipboard::copy_to_clipboard(&text) {
+                                Ok(()) => app.show_toast(format!("{} copied to clipboard", what)),
+                                Err(e) => app.show_error_toast(format!("Copy failed: {}", e)),
+                            }
+                        }
+                        Some(Action::Reset) => {
+                            store.write().await.clear();
                             app.reset_scroll();
                         }
-                        _ => {}
+                        None => {}
                     },
                 }
             }
         }
     }
 
+    // Quitting from the error screen leaves nothing worth keeping
+    let auto_export = match config.preferences.auto_export_on_quit {
+        Some(format) if quit_requested => {
+            let store = store.read().await;
+            let target = export_target(&config, app.jvm_info.as_ref());
+            Some(export::export_metrics(&store, &target, format))
+        }
+        _ => None,
+    };
+    let recorded = match cli.record {
+        Some(ref path) => {
+            let store = store.read().await;
+            Some(write_recording(path, app.jvm_info.as_ref(), &store).map(|_| path))
+        }
+        None => None,
+    };
+
     {
         let mut conn = connector_arc.write().await;
         conn.disconnect().await?;
@@ -409,5 +1522,15 @@ async fn main() -> Result<()> {
     let _ = tokio::time::timeout(Duration::from_secs(1), collector_handle).await;
 
     terminal::restore_terminal(&mut terminal)?;
+    match auto_export {
+        Some(Ok(path)) if !cli.quiet => println!("Session exported to {}", path.display()),
+        Some(Err(e)) => eprintln!("Error: auto-export failed: {}", e),
+        _ => {}
+    }
+    match recorded {
+        Some(Ok(path)) if !cli.quiet => println!("Session recorded to {}", path.display()),
+        Some(Err(e)) => eprintln!("Error: recording failed: {}", e),
+        _ => {}
+    }
     Ok(())
 }
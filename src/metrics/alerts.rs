@@ -0,0 +1,389 @@
+use crate::config::{AlertAction, AlertRule};
+use crate::jvm::types::ThreadState;
+use crate::metrics::store::MetricsStore;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Metrics an alert condition can refer to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMetric {
+    HeapPercent,
+    HeapUsedMb,
+    Threads,
+    BlockedThreads,
+    DeadlockedThreads,
+    GcThroughput,
+    GcOverhead,
+    YoungGcCount,
+    FullGcCount,
+}
+
+impl AlertMetric {
+    pub fn all() -> &'static [AlertMetric] {
+        &[
+            AlertMetric::HeapPercent,
+            AlertMetric::HeapUsedMb,
+            AlertMetric::Threads,
+            AlertMetric::BlockedThreads,
+            AlertMetric::DeadlockedThreads,
+            AlertMetric::GcThroughput,
+            AlertMetric::GcOverhead,
+            AlertMetric::YoungGcCount,
+            AlertMetric::FullGcCount,
+        ]
+    }
+
+    pub fn identifier(&self) -> &'static str {
+        match self {
+            AlertMetric::HeapPercent => "heap_percent",
+            AlertMetric::HeapUsedMb => "heap_used_mb",
+            AlertMetric::Threads => "threads",
+            AlertMetric::BlockedThreads => "blocked_threads",
+            AlertMetric::DeadlockedThreads => "deadlocked_threads",
+            AlertMetric::GcThroughput => "gc_throughput",
+            AlertMetric::GcOverhead => "gc_overhead",
+            AlertMetric::YoungGcCount => "young_gc_count",
+            AlertMetric::FullGcCount => "full_gc_count",
+        }
+    }
+
+    /// Current value, or `None` while there is no data to judge it by.
+    pub fn value(&self, store: &MetricsStore) -> Option<f64> {
+        match self {
+            AlertMetric::HeapPercent => store.heap_history.latest()?.used_percent(),
+            AlertMetric::HeapUsedMb => {
+                let heap = store.heap_history.latest()?;
+                Some(heap.used_bytes as f64 / 1024.0 / 1024.0)
+            }
+            AlertMetric::Threads => {
+                store.thread_state_history.latest()?;
+                Some(store.thread_snapshot.len() as f64)
+            }
+            AlertMetric::BlockedThreads => {
+                store.thread_state_history.latest()?;
+                Some(
+                    store
+                        .thread_snapshot
+                        .iter()
+                        .filter(|t| t.state == ThreadState::Blocked)
+                        .count() as f64,
+                )
+            }
+            AlertMetric::DeadlockedThreads => Some(store.deadlocked_threads.len() as f64),
+            AlertMetric::GcThroughput => store.gc_throughput_percent(),
+            AlertMetric::GcOverhead => store.gc_throughput_percent().map(|t| 100.0 - t),
+            AlertMetric::YoungGcCount => store
+                .gc_history
+                .latest()
+                .map(|gc| gc.young_gc_count() as f64),
+            AlertMetric::FullGcCount => {
+                store.gc_history.latest().map(|gc| gc.old_gc_count() as f64)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+            Comparison::Equal => "==",
+            Comparison::NotEqual => "!=",
+        }
+    }
+
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Greater => value > threshold,
+            Comparison::GreaterOrEqual => value >= threshold,
+            Comparison::Less => value < threshold,
+            Comparison::LessOrEqual => value <= threshold,
+            Comparison::Equal => value == threshold,
+            Comparison::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// A parsed `<metric> <op> <number>` expression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlertCondition {
+    pub metric: AlertMetric,
+    pub comparison: Comparison,
+    pub threshold: f64,
+}
+
+impl AlertCondition {
+    pub fn parse(expression: &str) -> Result<Self, String> {
+        // Two-character operators first so ">=" isn't read as ">"
+        let operators = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+        ];
+
+        let (position, symbol, comparison) = operators
+            .iter()
+            .find_map(|(symbol, comparison)| {
+                expression
+                    .find(symbol)
+                    .map(|position| (position, *symbol, *comparison))
+            })
+            .ok_or_else(|| "expected a comparison (>, >=, <, <=, ==, !=)".to_string())?;
+
+        let name = expression[..position].trim();
+        let threshold = expression[position + symbol.len()..].trim();
+
+        let metric = AlertMetric::all()
+            .iter()
+            .find(|metric| metric.identifier() == name)
+            .copied()
+            .ok_or_else(|| {
+                let known: Vec<&str> = AlertMetric::all().iter().map(|m| m.identifier()).collect();
+                format!("unknown metric '{}' (known: {})", name, known.join(", "))
+            })?;
+
+        let threshold = threshold
+            .parse::<f64>()
+            .map_err(|_| format!("'{}' is not a number", threshold))?;
+
+        Ok(Self {
+            metric,
+            comparison,
+            threshold,
+        })
+    }
+
+    /// `Some(value)` when the condition holds for the current metrics.
+    pub fn check(&self, store: &MetricsStore) -> Option<f64> {
+        self.metric
+            .value(store)
+            .filter(|value| self.comparison.holds(*value, self.threshold))
+    }
+}
+
+impl fmt::Display for AlertCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.metric.identifier(),
+            self.comparison.symbol(),
+            self.threshold
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FiredAlert {
+    pub rule: String,
+    pub value: f64,
+    pub message: String,
+    pub action: AlertAction,
+}
+
+struct AlertState {
+    condition: AlertCondition,
+    for_duration: Duration,
+    action: AlertAction,
+    pending_since: Option<Instant>,
+    firing: Option<String>,
+}
+
+/// Evaluates the configured alert rules against the live metrics.
+pub struct AlertEngine {
+    states: Vec<AlertState>,
+}
+
+impl AlertEngine {
+    /// Rules that don't parse are skipped; config validation reports them.
+    pub fn new(rules: &[AlertRule]) -> Self {
+        let states = rules
+            .iter()
+            .filter_map(|rule| {
+                AlertCondition::parse(&rule.when)
+                    .ok()
+                    .map(|condition| AlertState {
+                        condition,
+                        for_duration: rule.for_duration,
+                        action: rule.action,
+                        pending_since: None,
+                        firing: None,
+                    })
+            })
+            .collect();
+        Self { states }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Whether any rule needs thread data, which is otherwise only collected
+    /// for the Threads tab when polling follows the visible tab.
+    pub fn uses_thread_metrics(&self) -> bool {
+        self.states.iter().any(|state| {
+            matches!(
+                state.condition.metric,
+                AlertMetric::Threads | AlertMetric::BlockedThreads | AlertMetric::DeadlockedThreads
+            )
+        })
+    }
+
+    /// Re-checks every rule and returns the alerts that started firing now.
+    /// A firing alert clears as soon as its condition stops holding.
+    pub fn evaluate(&mut self, store: &MetricsStore, now: Instant) -> Vec<FiredAlert> {
+        let mut fired = Vec::new();
+
+        for state in &mut self.states {
+            match state.condition.check(store) {
+                Some(value) => {
+                    let since = *state.pending_since.get_or_insert(now);
+                    if now.duration_since(since) < state.for_duration {
+                        continue;
+                    }
+
+                    let message = format!("{} (now {:.1})", state.condition, value);
+                    if state.firing.is_none() {
+                        fired.push(FiredAlert {
+                            rule: state.condition.to_string(),
+                            value,
+                            message: message.clone(),
+                            action: state.action,
+                        });
+                    }
+                    state.firing = Some(message);
+                }
+                None => {
+                    state.pending_since = None;
+                    state.firing = None;
+                }
+            }
+        }
+
+        fired
+    }
+
+    /// Messages of all alerts currently firing.
+    pub fn active(&self) -> Vec<String> {
+        self.states
+            .iter()
+            .filter_map(|state| state.firing.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jvm::types::HeapInfo;
+    use chrono::Local;
+
+    fn store_with_heap(used_mb: u64, max_mb: u64) -> MetricsStore {
+        let mut store = MetricsStore::new(10);
+        store.record_heap(HeapInfo {
+            used_bytes: used_mb * 1024 * 1024,
+            max_bytes: max_mb * 1024 * 1024,
+            committed_bytes: max_mb * 1024 * 1024,
+            pools: Vec::new(),
+            timestamp: Local::now(),
+        });
+        store
+    }
+
+    fn rule(when: &str, for_secs: u64) -> AlertRule {
+        AlertRule {
+            when: when.to_string(),
+            for_duration: Duration::from_secs(for_secs),
+            action: AlertAction::Beep,
+        }
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        let condition = AlertCondition::parse("heap_percent > 90").unwrap();
+        assert_eq!(condition.metric, AlertMetric::HeapPercent);
+        assert_eq!(condition.comparison, Comparison::Greater);
+        assert_eq!(condition.threshold, 90.0);
+
+        let condition = AlertCondition::parse("blocked_threads>=5").unwrap();
+        assert_eq!(condition.comparison, Comparison::GreaterOrEqual);
+        assert_eq!(condition.to_string(), "blocked_threads >= 5");
+
+        assert!(AlertCondition::parse("heap_percent 90").is_err());
+        assert!(AlertCondition::parse("heap_pct > 90")
+            .unwrap_err()
+            .contains("unknown metric"));
+        assert!(AlertCondition::parse("heap_percent > lots").is_err());
+    }
+
+    #[test]
+    fn test_alert_fires_after_duration() {
+        let mut engine = AlertEngine::new(&[rule("heap_percent > 90", 30)]);
+        let store = store_with_heap(950, 1000);
+        let start = Instant::now();
+
+        assert!(engine.evaluate(&store, start).is_empty());
+        assert!(engine
+            .evaluate(&store, start + Duration::from_secs(10))
+            .is_empty());
+
+        let fired = engine.evaluate(&store, start + Duration::from_secs(30));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].action, AlertAction::Beep);
+        assert!(fired[0].message.starts_with("heap_percent > 90"));
+        assert_eq!(engine.active().len(), 1);
+
+        // Still firing, but not reported again
+        assert!(engine
+            .evaluate(&store, start + Duration::from_secs(40))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_alert_resets_when_condition_clears() {
+        let mut engine = AlertEngine::new(&[rule("heap_percent > 90", 0)]);
+        let start = Instant::now();
+
+        assert_eq!(engine.evaluate(&store_with_heap(950, 1000), start).len(), 1);
+        assert!(engine
+            .evaluate(&store_with_heap(500, 1000), start)
+            .is_empty());
+        assert!(engine.active().is_empty());
+
+        assert_eq!(engine.evaluate(&store_with_heap(950, 1000), start).len(), 1);
+    }
+
+    #[test]
+    fn test_uses_thread_metrics() {
+        assert!(!AlertEngine::new(&[rule("heap_percent > 90", 0)]).uses_thread_metrics());
+        assert!(AlertEngine::new(&[
+            rule("heap_percent > 90", 0),
+            rule("blocked_threads >= 5", 0),
+        ])
+        .uses_thread_metrics());
+    }
+
+    #[test]
+    fn test_no_data_never_fires() {
+        let mut engine = AlertEngine::new(&[rule("heap_percent < 10", 0)]);
+        assert!(engine
+            .evaluate(&MetricsStore::new(10), Instant::now())
+            .is_empty());
+    }
+}
@@ -1,15 +1,109 @@
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::export;
 use crate::jvm::connector::JvmConnector;
+use crate::jvm::types::{RuntimeCounters, ThreadInfo};
+use crate::metrics::log::{LogEntry, LogLevel};
 use crate::metrics::store::MetricsStore;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
-use tokio::time::interval;
+use tokio::sync::{mpsc, watch, RwLock};
+use tokio::time::{interval, interval_at, Instant};
+
+/// Maximum relative heap change between two samples that still counts as idle.
+const IDLE_HEAP_CHANGE_RATIO: f64 = 0.05;
+
+/// Consecutive failed polls after which the JVM is reported unreachable.
+const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Shortest polling interval accepted from the CLI, config or at runtime.
+pub const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often the class histogram is collected unless configured otherwise.
+/// `GC.class_histogram` walks the whole heap, so it runs on its own clock.
+pub const DEFAULT_CLASS_HISTOGRAM_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Largest share of the time between histograms one histogram may take;
+/// slower ones stretch the wait so the monitor doesn't add GC pressure.
+const MAX_HISTOGRAM_SHARE: f64 = 0.1;
+
+/// Intervals `+`/`-` step through at runtime.
+const INTERVAL_STEPS: [Duration; 9] = [
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_secs(1),
+    Duration::from_secs(2),
+    Duration::from_secs(5),
+    Duration::from_secs(10),
+    Duration::from_secs(30),
+    Duration::from_secs(60),
+];
+
+/// How long the last poll took and whether it reached the JVM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollStatus {
+    pub latency: Duration,
+    pub ok: bool,
+}
+
+impl PollStatus {
+    /// Share of `interval` the poll used up; from 1.0 on the monitoring
+    /// itself can't keep pace.
+    pub fn load(&self, interval: Duration) -> f64 {
+        self.latency.as_secs_f64() / interval.max(MIN_INTERVAL).as_secs_f64()
+    }
+}
+
+/// The optional metrics a poll collects on top of heap and GC stats, which
+/// the Overview and alerts always need.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricSet(u8);
+
+impl MetricSet {
+    pub const NONE: Self = Self(0);
+    /// Thread dump and deadlocks.
+    pub const THREADS: Self = Self(1);
+    /// GC pauses and humongous regions.
+    pub const GC_DETAILS: Self = Self(1 << 1);
+    /// Class histogram.
+    pub const CLASSES: Self = Self(1 << 2);
+    /// JIT compiler and runtime perf counters.
+    pub const RUNTIME: Self = Self(1 << 3);
+    pub const NATIVE_MEMORY: Self = Self(1 << 4);
+    pub const ALL: Self = Self(0b1_1111);
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
 
 pub struct MetricsCollector {
     connector: Arc<RwLock<dyn JvmConnector>>,
     store: Arc<RwLock<MetricsStore>>,
-    interval: Duration,
+    interval_ms: Arc<AtomicU64>,
+    max_interval: Option<Duration>,
+    class_histogram_interval: Duration,
+    csv_log: Option<PathBuf>,
+    unreachable: Arc<AtomicBool>,
+    wanted: Arc<AtomicU8>,
+    completed_polls: watch::Sender<u64>,
+    poll_status: watch::Sender<Option<PollStatus>>,
+    event_log: Option<mpsc::Sender<LogEntry>>,
     tick_count: std::sync::Arc<std::sync::atomic::AtomicU64>,
 }
 
@@ -22,16 +116,158 @@ impl MetricsCollector {
         Self {
             connector,
             store,
-            interval,
+            interval_ms: Arc::new(AtomicU64::new(interval.max(MIN_INTERVAL).as_millis() as u64)),
+            max_interval: None,
+            class_histogram_interval: DEFAULT_CLASS_HISTOGRAM_INTERVAL,
+            csv_log: None,
+            unreachable: Arc::new(AtomicBool::new(false)),
+            wanted: Arc::new(AtomicU8::new(MetricSet::ALL.bits())),
+            completed_polls: watch::channel(0).0,
+            poll_status: watch::channel(None).0,
+            event_log: None,
             tick_count: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
         }
     }
 
+    /// Back off polling while the JVM is idle, doubling the interval up to
+    /// `max_interval` and returning to the base interval once activity resumes.
+    pub fn with_adaptive_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = Some(max_interval.max(self.interval()));
+        self
+    }
+
+    /// Collect the class histogram at most this often, whatever the polling
+    /// interval.
+    pub fn with_class_histogram_interval(mut self, interval: Duration) -> Self {
+        self.class_histogram_interval = interval;
+        self
+    }
+
+    /// Shared handle to the base polling interval in milliseconds. Storing a
+    /// new value takes effect on the next tick.
+    pub fn interval_handle(&self) -> Arc<AtomicU64> {
+        self.interval_ms.clone()
+    }
+
+    /// Shared flag set once several polls in a row have failed, which usually
+    /// means the JVM exited. Polling continues, and the first successful poll
+    /// clears it again.
+    pub fn unreachable_handle(&self) -> Arc<AtomicBool> {
+        self.unreachable.clone()
+    }
+
+    /// Count of polls that reached the JVM, bumped once each has been
+    /// recorded. Closes when the collector is dropped, e.g. after `run`
+    /// returns in a spawned task.
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.completed_polls.subscribe()
+    }
+
+    /// Latency and outcome of the most recent poll, `None` until the first
+    /// one finishes.
+    pub fn poll_status_handle(&self) -> watch::Receiver<Option<PollStatus>> {
+        self.poll_status.subscribe()
+    }
+
+    fn record_poll(&self, started: Instant, ok: bool) {
+        self.poll_status.send_replace(Some(PollStatus {
+            latency: started.elapsed(),
+            ok,
+        }));
+    }
+
+    /// Report failed collections to `sender`, once when a metric starts
+    /// failing (or fails differently) and again when it recovers. Entries
+    /// are dropped while the channel is full.
+    pub fn with_event_log(mut self, sender: mpsc::Sender<LogEntry>) -> Self {
+        self.event_log = Some(sender);
+        self
+    }
+
+    fn log(&self, level: LogLevel, message: String) {
+        if let Some(sender) = &self.event_log {
+            let _ = sender.try_send(LogEntry::new(level, message));
+        }
+    }
+
+    /// Unwraps one collection result, logging changes in its failure state.
+    fn track<T>(
+        &self,
+        what: &'static str,
+        result: Result<T>,
+        failing: &mut HashMap<&'static str, String>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => {
+                if failing.remove(what).is_some() {
+                    self.log(LogLevel::Info, format!("{} collection recovered", what));
+                }
+                Some(value)
+            }
+            Err(e) => {
+                let error = e.to_string();
+                if failing.get(what) != Some(&error) {
+                    let (level, message) = match e {
+                        AppError::Unsupported(_) => (
+                            LogLevel::Info,
+                            format!("{} collection unavailable: {}", what, error),
+                        ),
+                        _ => (
+                            LogLevel::Error,
+                            format!("{} collection failed: {}", what, error),
+                        ),
+                    };
+                    self.log(level, message);
+                    failing.insert(what, error);
+                }
+                None
+            }
+        }
+    }
+
+    /// Shared handle to the [`MetricSet::bits`] collected on each poll,
+    /// [`MetricSet::ALL`] unless changed. Storing a new set takes effect on
+    /// the next tick.
+    pub fn metrics_handle(&self) -> Arc<AtomicU8> {
+        self.wanted.clone()
+    }
+
+    fn wanted(&self) -> MetricSet {
+        MetricSet::from_bits(self.wanted.load(Ordering::Relaxed))
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms.load(Ordering::Relaxed))
+    }
+
+    /// Append a CSV row set to `path` after every poll. The first failed
+    /// write is reported to the event log and ends the logging.
+    pub fn with_csv_log(mut self, path: PathBuf) -> Self {
+        self.csv_log = Some(path);
+        self
+    }
+
     pub async fn run(&self) -> Result<()> {
-        let mut ticker = interval(self.interval);
+        let mut base_interval = self.interval();
+        let mut current_interval = base_interval;
+        let mut ticker = interval(current_interval);
+        let mut consecutive_failures = 0;
+        let mut last_wanted = MetricSet::ALL;
+        let mut failing = HashMap::new();
+        let mut csv_log = self.csv_log.as_deref();
+        // When the last histogram started and how long it took
+        let mut last_histogram: Option<(Instant, Duration)> = None;
 
         loop {
             ticker.tick().await;
+            let started = Instant::now();
+
+            let requested_interval = self.interval();
+            if requested_interval != base_interval {
+                base_interval = requested_interval;
+                current_interval = base_interval;
+                ticker = interval_at(Instant::now() + current_interval, current_interval);
+            }
 
             let tick = self
                 .tick_count
@@ -42,32 +278,141 @@ impl MetricsCollector {
                 break;
             }
 
-            if let Ok(heap_info) = connector.get_heap_info().await {
+            // Heap info is the first call of every poll, so failing to reach
+            // the JVM for it is what tells us the JVM has gone away. Output
+            // we can't parse, or a heap the JVM can't report, still means it
+            // answered.
+            let heap = connector.get_heap_info().await;
+            let lost = matches!(&heap, Err(e) if e.is_connection_error());
+            if let Some(heap_info) = self.track("Heap", heap, &mut failing) {
                 let mut store = self.store.write().await;
                 store.record_heap(heap_info);
             }
+            if lost {
+                consecutive_failures += 1;
+                if consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+                    self.unreachable.store(true, Ordering::Relaxed);
+                }
+                self.record_poll(started, false);
+                continue;
+            }
+            consecutive_failures = 0;
+            self.unreachable.store(false, Ordering::Relaxed);
 
-            if let Ok(gc_stats) = connector.get_gc_stats().await {
+            if let Some(gc_stats) = self.track("GC", connector.get_gc_stats().await, &mut failing) {
                 let mut store = self.store.write().await;
                 store.record_gc(gc_stats);
             }
 
-            if let Ok(thread_info) = connector.get_thread_info().await {
-                let mut store = self.store.write().await;
-                store.record_threads(thread_info);
+            let wanted = self.wanted();
+            // Slow-cadence data is fetched right away once it is asked for
+            let slow_tick = tick % 10 == 0 || wanted != last_wanted;
+            last_wanted = wanted;
+
+            if wanted.contains(MetricSet::THREADS) {
+                let threads = fetch_threads(&*connector).await;
+                if let Some((thread_info, deadlocked)) = self.track("Thread", threads, &mut failing)
+                {
+                    let mut store = self.store.write().await;
+                    store.record_threads(thread_info);
+                    store.record_deadlocks(deadlocked);
+                }
+            }
+
+            if wanted.contains(MetricSet::GC_DETAILS) {
+                if let Ok(pauses) = connector.get_recent_gc_pauses().await {
+                    let mut store = self.store.write().await;
+                    store.record_gc_pauses(pauses);
+                }
             }
 
-            if tick % 10 == 0 {
-                if let Ok(class_histogram) = connector.get_class_histogram().await {
+            if wanted.contains(MetricSet::CLASSES)
+                && histogram_due(last_histogram, started, self.class_histogram_interval)
+            {
+                let histogram_started = Instant::now();
+                let histogram = connector.get_class_histogram().await;
+                last_histogram = Some((histogram_started, histogram_started.elapsed()));
+                if let Some(class_histogram) =
+                    self.track("Class histogram", histogram, &mut failing)
+                {
                     let mut store = self.store.write().await;
                     store.record_class_histogram(class_histogram);
                 }
             }
+
+            if slow_tick {
+                if wanted.contains(MetricSet::RUNTIME) {
+                    self.collect_runtime(&*connector).await;
+                }
+
+                if wanted.contains(MetricSet::GC_DETAILS) {
+                    if let Ok(humongous) = connector.get_humongous_stats().await {
+                        let mut store = self.store.write().await;
+                        store.record_humongous(humongous);
+                    }
+                }
+
+                if wanted.contains(MetricSet::NATIVE_MEMORY) {
+                    if let Ok(native_memory) = connector.get_native_memory().await {
+                        let mut store = self.store.write().await;
+                        store.record_native_memory(native_memory);
+                    }
+                }
+            }
+
+            if let Some(path) = csv_log {
+                let store = self.store.read().await;
+                // A full disk or a bad path won't fix itself between polls
+                if let Err(e) = export::append_metrics_csv(&store, path) {
+                    self.log(
+                        LogLevel::Error,
+                        format!("CSV log {} stopped: {}", path.display(), e),
+                    );
+                    csv_log = None;
+                }
+            }
+
+            self.record_poll(started, true);
+            self.completed_polls.send_modify(|polls| *polls += 1);
+
+            if let Some(max_interval) = self.max_interval {
+                let next_interval = {
+                    let store = self.store.read().await;
+                    next_adaptive_interval(&store, current_interval, base_interval, max_interval)
+                };
+
+                if next_interval != current_interval {
+                    current_interval = next_interval;
+                    ticker = interval_at(Instant::now() + current_interval, current_interval);
+                }
+            }
         }
 
         Ok(())
     }
 
+    /// Runtime counters and compiler stats, both from one `PerfCounter.print`
+    /// where the connector can derive them from it.
+    async fn collect_runtime(&self, connector: &dyn JvmConnector) {
+        let counters = connector.get_perf_counters().await.ok();
+        let compiler_stats = match &counters {
+            Some(counters) => connector.compiler_stats_from_counters(counters).await,
+            None => None,
+        };
+        let compiler_stats = match compiler_stats {
+            Some(stats) => Some(stats),
+            None => connector.get_compiler_stats().await.ok(),
+        };
+
+        let mut store = self.store.write().await;
+        if let Some(counters) = counters {
+            store.record_runtime_counters(RuntimeCounters::from_perf_counters(&counters));
+        }
+        if let Some(compiler_stats) = compiler_stats {
+            store.record_compiler_stats(compiler_stats);
+        }
+    }
+
     pub async fn collect_once(&self) -> Result<()> {
         let connector = self.connector.read().await;
         if !connector.is_connected().await {
@@ -86,19 +431,488 @@ impl MetricsCollector {
             store.record_gc(gc_stats);
         }
 
-        if let Ok(thread_info) = connector.get_thread_info().await {
+        if let Ok((thread_info, deadlocked)) = fetch_threads(&*connector).await {
             let mut store = self.store.write().await;
             store.record_threads(thread_info);
+            store.record_deadlocks(deadlocked);
+        }
+
+        if let Ok(pauses) = connector.get_recent_gc_pauses().await {
+            let mut store = self.store.write().await;
+            store.record_gc_pauses(pauses);
+        }
+
+        if let Ok(class_histogram) = connector.get_class_histogram().await {
+            let mut store = self.store.write().await;
+            store.record_class_histogram(class_histogram);
+        }
+
+        self.collect_runtime(&*connector).await;
+
+        if let Ok(humongous) = connector.get_humongous_stats().await {
+            let mut store = self.store.write().await;
+            store.record_humongous(humongous);
+        }
+
+        if let Ok(native_memory) = connector.get_native_memory().await {
+            let mut store = self.store.write().await;
+            store.record_native_memory(native_memory);
         }
 
         Ok(())
     }
 }
 
+/// Whether `interval` has passed since the last histogram, stretched when
+/// that one took more than [`MAX_HISTOGRAM_SHARE`] of it.
+fn histogram_due(last: Option<(Instant, Duration)>, now: Instant, interval: Duration) -> bool {
+    let Some((started, took)) = last else {
+        return true;
+    };
+    let wait = interval.max(took.div_f64(MAX_HISTOGRAM_SHARE));
+    now.duration_since(started) >= wait
+}
+
+/// Thread info and deadlocked thread IDs, with CPU times filled in from
+/// `get_thread_cpu_times` for connectors whose thread listing doesn't carry
+/// them. Thread.print headers carry cpu=<ms> since JDK 10, so jcmd-based
+/// connectors never need the second call.
+async fn fetch_threads(connector: &dyn JvmConnector) -> Result<(Vec<ThreadInfo>, Vec<u64>)> {
+    let (mut threads, deadlocked) = connector.get_threads_and_deadlocks().await?;
+
+    if threads.iter().all(|thread| thread.cpu_time_ns.is_none()) {
+        if let Ok(cpu_times) = connector.get_thread_cpu_times().await {
+            for thread in threads.iter_mut().filter(|t| t.cpu_time_ns.is_none()) {
+                thread.cpu_time_ns = cpu_times.get(&thread.id).copied();
+            }
+        }
+    }
+
+    Ok((threads, deadlocked))
+}
+
+/// The next step up (`slower`) or down from `current`, clamped to the step
+/// range.
+pub fn step_interval(current: Duration, slower: bool) -> Duration {
+    if slower {
+        INTERVAL_STEPS
+            .iter()
+            .copied()
+            .find(|step| *step > current)
+            .unwrap_or(INTERVAL_STEPS[INTERVAL_STEPS.len() - 1])
+    } else {
+        INTERVAL_STEPS
+            .iter()
+            .rev()
+            .copied()
+            .find(|step| *step < current)
+            .unwrap_or(MIN_INTERVAL)
+    }
+}
+
+fn next_adaptive_interval(
+    store: &MetricsStore,
+    current: Duration,
+    base: Duration,
+    max: Duration,
+) -> Duration {
+    if is_idle(store) {
+        // Never faster than the base interval, even after '+' raised it past max
+        (current * 2).min(max).max(base)
+    } else {
+        base
+    }
+}
+
+/// A JVM is considered idle when the heap barely moved and no GC ran between
+/// the two most recent samples.
+fn is_idle(store: &MetricsStore) -> bool {
+    let heap_len = store.heap_history.len();
+    let gc_len = store.gc_history.len();
+    if heap_len < 2 || gc_len < 2 {
+        return false;
+    }
+
+    let mut heaps = store.heap_history.iter().skip(heap_len - 2);
+    let (prev_heap, last_heap) = match (heaps.next(), heaps.next()) {
+        (Some(prev), Some(last)) => (prev, last),
+        _ => return false,
+    };
+
+    let mut gcs = store.gc_history.iter().skip(gc_len - 2);
+    let (prev_gc, last_gc) = match (gcs.next(), gcs.next()) {
+        (Some(prev), Some(last)) => (prev, last),
+        _ => return false,
+    };
+
+    let heap_delta = last_heap.used_bytes.abs_diff(prev_heap.used_bytes) as f64;
+    let heap_stable = heap_delta <= prev_heap.used_bytes.max(1) as f64 * IDLE_HEAP_CHANGE_RATIO;
+    let no_new_gcs = last_gc.young_gc_count() == prev_gc.young_gc_count()
+        && last_gc.old_gc_count() == prev_gc.old_gc_count();
+
+    heap_stable && no_new_gcs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::jvm::jdk_tools::connector::JdkToolsConnector;
+    use crate::jvm::types::{GcStats, HeapInfo};
+    use chrono::Local;
+
+    fn heap(used_mb: u64) -> HeapInfo {
+        HeapInfo {
+            used_bytes: used_mb * 1024 * 1024,
+            max_bytes: 1024 * 1024 * 1024,
+            committed_bytes: 512 * 1024 * 1024,
+            pools: vec![],
+            timestamp: Local::now(),
+        }
+    }
+
+    fn gc(young: u64, old: u64) -> GcStats {
+        GcStats::from_generations(young, young * 5, old, old * 50, Local::now())
+    }
+
+    #[test]
+    fn test_histogram_due() {
+        let interval = Duration::from_secs(30);
+        let start = Instant::now();
+        assert!(histogram_due(None, start, interval));
+
+        let cheap = Some((start, Duration::from_millis(200)));
+        assert!(!histogram_due(
+            cheap,
+            start + Duration::from_secs(29),
+            interval
+        ));
+        assert!(histogram_due(cheap, start + interval, interval));
+
+        // 5s is more than a tenth of 30s, so the next one waits 50s
+        let slow = Some((start, Duration::from_secs(5)));
+        assert!(!histogram_due(
+            slow,
+            start + Duration::from_secs(49),
+            interval
+        ));
+        assert!(histogram_due(
+            slow,
+            start + Duration::from_secs(50),
+            interval
+        ));
+    }
+
+    #[test]
+    fn test_adaptive_interval_backs_off_when_idle() {
+        let mut store = MetricsStore::new(10);
+        store.record_heap(heap(100));
+        store.record_heap(heap(101));
+        store.record_gc(gc(10, 1));
+        store.record_gc(gc(10, 1));
+
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(5);
+        assert!(is_idle(&store));
+        assert_eq!(
+            next_adaptive_interval(&store, base, base, max),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            next_adaptive_interval(&store, Duration::from_secs(4), base, max),
+            max
+        );
+
+        let base = Duration::from_secs(10);
+        assert_eq!(next_adaptive_interval(&store, base, base, max), base);
+    }
+
+    #[test]
+    fn test_adaptive_interval_resets_on_activity() {
+        let mut store = MetricsStore::new(10);
+        store.record_heap(heap(100));
+        store.record_heap(heap(101));
+        store.record_gc(gc(10, 1));
+        store.record_gc(gc(11, 1));
+
+        assert!(!is_idle(&store));
+        let base = Duration::from_secs(1);
+        assert_eq!(
+            next_adaptive_interval(&store, Duration::from_secs(4), base, Duration::from_secs(5)),
+            base
+        );
+
+        let mut store = MetricsStore::new(10);
+        store.record_heap(heap(100));
+        store.record_heap(heap(200));
+        store.record_gc(gc(10, 1));
+        store.record_gc(gc(10, 1));
+        assert!(!is_idle(&store));
+    }
+
+    #[test]
+    fn test_metric_set() {
+        let threads_and_classes = MetricSet::THREADS.union(MetricSet::CLASSES);
+        assert!(threads_and_classes.contains(MetricSet::CLASSES));
+        assert!(!threads_and_classes.contains(MetricSet::NATIVE_MEMORY));
+        assert!(MetricSet::ALL.contains(threads_and_classes));
+        assert!(!MetricSet::NONE.contains(MetricSet::THREADS));
+        assert_eq!(MetricSet::from_bits(0xff), MetricSet::ALL);
+
+        let connector: Arc<RwLock<dyn JvmConnector>> = Arc::new(RwLock::new(ExitedJvm));
+        let store = Arc::new(RwLock::new(MetricsStore::new(10)));
+        let collector = MetricsCollector::new(connector, store, MIN_INTERVAL);
+        assert_eq!(collector.wanted(), MetricSet::ALL);
+        collector
+            .metrics_handle()
+            .store(MetricSet::NATIVE_MEMORY.bits(), Ordering::Relaxed);
+        assert_eq!(collector.wanted(), MetricSet::NATIVE_MEMORY);
+    }
+
+    #[test]
+    fn test_step_interval() {
+        let second = Duration::from_secs(1);
+        assert_eq!(step_interval(second, true), Duration::from_secs(2));
+        assert_eq!(step_interval(second, false), Duration::from_millis(500));
+
+        // Off-step values snap to the neighbouring step
+        assert_eq!(
+            step_interval(Duration::from_millis(1500), true),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            step_interval(Duration::from_millis(1500), false),
+            Duration::from_secs(1)
+        );
+
+        assert_eq!(step_interval(MIN_INTERVAL, false), MIN_INTERVAL);
+        assert_eq!(
+            step_interval(Duration::from_secs(60), true),
+            Duration::from_secs(60)
+        );
+    }
+
+    #[test]
+    fn test_poll_status_load() {
+        let status = PollStatus {
+            latency: Duration::from_millis(250),
+            ok: true,
+        };
+        assert!((status.load(Duration::from_secs(1)) - 0.25).abs() < 1e-9);
+        assert!((status.load(Duration::from_millis(250)) - 1.0).abs() < 1e-9);
+        // Below the minimum the collector never polls faster anyway
+        assert!((status.load(Duration::ZERO) - 2.5).abs() < 1e-9);
+    }
+
+    /// Stands in for a JVM that has exited: still "connected", but every
+    /// command fails.
+    struct ExitedJvm;
+
+    #[async_trait::async_trait]
+    impl JvmConnector for ExitedJvm {
+        async fn connect(&mut self, _pid: u32) -> Result<()> {
+            Ok(())
+        }
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn is_connected(&self) -> bool {
+            true
+        }
+        async fn reconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn get_jvm_info(&self) -> Result<crate::jvm::types::JvmInfo> {
+            Err(crate::error::AppError::Connection("exited".to_string()))
+        }
+        async fn get_heap_info(&self) -> Result<HeapInfo> {
+            Err(crate::error::AppError::Connection("exited".to_string()))
+        }
+        async fn get_gc_stats(&self) -> Result<GcStats> {
+            Err(crate::error::AppError::Connection("exited".to_string()))
+        }
+        async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
+            Err(crate::error::AppError::Connection("exited".to_string()))
+        }
+        async fn get_class_histogram(&self) -> Result<Vec<crate::jvm::types::ClassInfo>> {
+            Err(crate::error::AppError::Connection("exited".to_string()))
+        }
+        async fn trigger_gc(&self) -> Result<()> {
+            Err(crate::error::AppError::Connection("exited".to_string()))
+        }
+    }
+
+    /// A healthy JVM with a fixed heap and no threads or classes.
+    struct IdleJvm;
+
+    #[async_trait::async_trait]
+    impl JvmConnector for IdleJvm {
+        async fn connect(&mut self, _pid: u32) -> Result<()> {
+            Ok(())
+        }
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn is_connected(&self) -> bool {
+            true
+        }
+        async fn reconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn get_jvm_info(&self) -> Result<crate::jvm::types::JvmInfo> {
+            Err(crate::error::AppError::Connection("not needed".to_string()))
+        }
+        async fn get_heap_info(&self) -> Result<HeapInfo> {
+            Ok(heap(100))
+        }
+        async fn get_gc_stats(&self) -> Result<GcStats> {
+            Ok(gc(1, 0))
+        }
+        async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
+            Ok(Vec::new())
+        }
+        async fn get_class_histogram(&self) -> Result<Vec<crate::jvm::types::ClassInfo>> {
+            Ok(Vec::new())
+        }
+        async fn trigger_gc(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Like an OpenJ9 JVM attached through jcmd: no heap or GC stats, but
+    /// threads and classes.
+    struct HeaplessJvm;
+
+    #[async_trait::async_trait]
+    impl JvmConnector for HeaplessJvm {
+        async fn connect(&mut self, _pid: u32) -> Result<()> {
+            Ok(())
+        }
+        async fn disconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn is_connected(&self) -> bool {
+            true
+        }
+        async fn reconnect(&mut self) -> Result<()> {
+            Ok(())
+        }
+        async fn get_jvm_info(&self) -> Result<crate::jvm::types::JvmInfo> {
+            Err(AppError::Connection("not needed".to_string()))
+        }
+        async fn get_heap_info(&self) -> Result<HeapInfo> {
+            Err(AppError::Unsupported("no heap info on OpenJ9".to_string()))
+        }
+        async fn get_gc_stats(&self) -> Result<GcStats> {
+            Err(AppError::Unsupported("no GC stats on OpenJ9".to_string()))
+        }
+        async fn get_thread_info(&self) -> Result<Vec<ThreadInfo>> {
+            Ok(vec![ThreadInfo {
+                id: 1,
+                name: "main".to_string(),
+                state: crate::jvm::types::ThreadState::Runnable,
+                stack_trace: Vec::new(),
+                cpu_time_ns: None,
+                daemon: None,
+                locks: Vec::new(),
+            }])
+        }
+        async fn get_class_histogram(&self) -> Result<Vec<crate::jvm::types::ClassInfo>> {
+            Ok(vec![crate::jvm::types::ClassInfo {
+                rank: 1,
+                instances: 10,
+                bytes: 640,
+                name: "java.lang.String".to_string(),
+            }])
+        }
+        async fn trigger_gc(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unsupported_heap_info_keeps_polling() {
+        let connector: Arc<RwLock<dyn JvmConnector>> = Arc::new(RwLock::new(HeaplessJvm));
+        let store = Arc::new(RwLock::new(MetricsStore::new(10)));
+        let (sender, mut events) = mpsc::channel(16);
+        let collector =
+            MetricsCollector::new(connector, store.clone(), MIN_INTERVAL).with_event_log(sender);
+        let unreachable = collector.unreachable_handle();
+        let mut polls = collector.subscribe();
+
+        let handle = tokio::spawn(async move {
+            let _ = collector.run().await;
+        });
+        polls
+            .wait_for(|count| *count > MAX_CONSECUTIVE_FAILURES as u64)
+            .await
+            .unwrap();
+        handle.abort();
+
+        assert!(!unreachable.load(Ordering::Relaxed));
+        let store = store.read().await;
+        assert_eq!(store.thread_snapshot.len(), 1);
+        assert_eq!(store.class_histogram.len(), 1);
+
+        // Reported once per metric, as unavailable rather than failing
+        let mut entries = Vec::new();
+        while let Ok(entry) = events.try_recv() {
+            entries.push(entry);
+        }
+        assert_eq!(entries.len(), 2, "{:?}", entries);
+        assert!(entries.iter().all(|entry| entry.level == LogLevel::Info));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_csv_log_failure_is_reported_once() {
+        // A path under a regular file can never be created
+        let file = std::env::temp_dir().join(format!("jvm-tui-csv-{}", std::process::id()));
+        std::fs::write(&file, "").unwrap();
+
+        let connector: Arc<RwLock<dyn JvmConnector>> = Arc::new(RwLock::new(IdleJvm));
+        let store = Arc::new(RwLock::new(MetricsStore::new(10)));
+        let (sender, mut events) = mpsc::channel(16);
+        let collector = MetricsCollector::new(connector, store, MIN_INTERVAL)
+            .with_csv_log(file.join("metrics.csv"))
+            .with_event_log(sender);
+        let mut polls = collector.subscribe();
+
+        let handle = tokio::spawn(async move {
+            let _ = collector.run().await;
+        });
+        polls.wait_for(|count| *count >= 3).await.unwrap();
+        handle.abort();
+        std::fs::remove_file(&file).unwrap();
+
+        let mut messages = Vec::new();
+        while let Ok(entry) = events.try_recv() {
+            messages.push(entry.message);
+        }
+        assert_eq!(messages.len(), 1, "{:?}", messages);
+        assert!(messages[0].starts_with("CSV log "));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_repeated_failures_mark_jvm_unreachable() {
+        let connector: Arc<RwLock<dyn JvmConnector>> = Arc::new(RwLock::new(ExitedJvm));
+        let store = Arc::new(RwLock::new(MetricsStore::new(10)));
+        let collector = MetricsCollector::new(connector, store, MIN_INTERVAL);
+        let unreachable = collector.unreachable_handle();
+        let poll_status = collector.poll_status_handle();
+        assert!(poll_status.borrow().is_none());
+
+        let handle = tokio::spawn(async move {
+            let _ = collector.run().await;
+        });
+
+        // The first tick fires immediately, so three polls take two intervals
+        tokio::time::sleep(MIN_INTERVAL / 2).await;
+        assert!(!unreachable.load(Ordering::Relaxed));
+        assert!(!poll_status.borrow().unwrap().ok);
+        tokio::time::sleep(MIN_INTERVAL * 4).await;
+        assert!(unreachable.load(Ordering::Relaxed));
+
+        handle.abort();
+    }
 
     #[tokio::test]
     async fn test_metrics_collector() {
@@ -120,8 +934,8 @@ mod tests {
         collector.collect_once().await.unwrap();
 
         let store_read = store.read().await;
-        assert!(store_read.heap_history.len() > 0);
-        assert!(store_read.gc_history.len() > 0);
+        assert!(!store_read.heap_history.is_empty());
+        assert!(!store_read.gc_history.is_empty());
 
         println!("Collected {} heap samples", store_read.heap_history.len());
         println!("Collected {} GC samples", store_read.gc_history.len());
@@ -160,6 +974,6 @@ mod tests {
             "Collected {} heap samples in 350ms",
             store_read.heap_history.len()
         );
-        assert!(store_read.heap_history.len() >= 1);
+        assert!(!store_read.heap_history.is_empty());
     }
 }
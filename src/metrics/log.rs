@@ -0,0 +1,26 @@
+use chrono::{DateTime, Local};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info,
+    Error,
+}
+
+/// A line in the event log: an action taken or a failure that would
+/// otherwise pass unnoticed.
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub timestamp: DateTime<Local>,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+impl LogEntry {
+    pub fn new(level: LogLevel, message: String) -> Self {
+        Self {
+            timestamp: Local::now(),
+            level,
+            message,
+        }
+    }
+}
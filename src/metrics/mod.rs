@@ -1,3 +1,5 @@
+pub mod alerts;
 pub mod collector;
+pub mod log;
 pub mod ring_buffer;
 pub mod store;
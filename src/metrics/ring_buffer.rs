@@ -1,6 +1,7 @@
 use serde::Serialize;
 use std::collections::VecDeque;
 
+/// Keeps the most recent `capacity` items, dropping the oldest on overflow.
 #[derive(Clone, Serialize)]
 pub struct RingBuffer<T: Clone> {
     buffer: VecDeque<T>,
@@ -8,7 +9,9 @@ pub struct RingBuffer<T: Clone> {
 }
 
 impl<T: Clone> RingBuffer<T> {
+    /// A capacity of 0 is treated as 1, so the latest item is always kept.
     pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
         Self {
             buffer: VecDeque::with_capacity(capacity),
             capacity,
@@ -26,6 +29,19 @@ impl<T: Clone> RingBuffer<T> {
         self.buffer.iter()
     }
 
+    /// Most recently pushed item.
+    pub fn latest(&self) -> Option<&T> {
+        self.buffer.back()
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     pub fn len(&self) -> usize {
         self.buffer.len()
     }
@@ -75,6 +91,36 @@ mod tests {
         assert_eq!(values, vec![4, 5, 6]);
     }
 
+    #[test]
+    fn test_ring_buffer_zero_capacity_keeps_latest() {
+        let mut buffer = RingBuffer::new(0);
+        assert_eq!(buffer.capacity(), 1);
+
+        buffer.push(1);
+        buffer.push(2);
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.latest(), Some(&2));
+    }
+
+    #[test]
+    fn test_ring_buffer_latest_and_clear() {
+        let mut buffer = RingBuffer::new(3);
+        assert_eq!(buffer.latest(), None);
+
+        for value in 1..=5 {
+            buffer.push(value);
+        }
+        assert_eq!(buffer.latest(), Some(&5));
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.latest(), None);
+        assert_eq!(buffer.capacity(), 3);
+
+        buffer.push(6);
+        assert_eq!(buffer.latest(), Some(&6));
+    }
+
     #[test]
     fn test_ring_buffer_with_strings() {
         let mut buffer = RingBuffer::new(2);
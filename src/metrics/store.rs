@@ -1,25 +1,106 @@
-use crate::jvm::types::{ClassInfo, GcStats, HeapInfo, ThreadInfo};
+use crate::jvm::types::{
+    ClassInfo, CompilerStats, GcCollectorStat, GcPause, GcStats, HeapInfo, HumongousStats,
+    NativeMemory, RuntimeCounters, ThreadInfo, ThreadStateCounts,
+};
 use crate::metrics::ring_buffer::RingBuffer;
 use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Upper bounds (exclusive, in ms) of the pause histogram buckets; the last
+/// bucket collects everything above.
+pub const GC_PAUSE_BUCKETS_MS: [f64; 6] = [1.0, 5.0, 10.0, 50.0, 100.0, 500.0];
+
+/// Full GCs needed before the post-GC heap trend is judged.
+pub const MIN_TREND_FULL_GCS: usize = 3;
+
+/// Heap left behind by full GCs across the buffered window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trend {
+    /// Full GCs the floors were taken after.
+    pub collections: usize,
+    pub first_floor_bytes: u64,
+    pub last_floor_bytes: u64,
+    /// Every floor was above the one before, which is what a leak looks like.
+    pub rising: bool,
+}
+
+/// Summary of one series over the buffered window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: u64,
+    pub max: u64,
+    pub avg: f64,
+    pub latest: u64,
+}
+
+impl Stats {
+    fn from_samples(samples: impl Iterator<Item = u64>) -> Option<Self> {
+        let (min, max, sum, count, latest) = samples.fold(
+            (u64::MAX, 0, 0u128, 0u64, 0),
+            |(min, max, sum, count, _), value| {
+                (
+                    min.min(value),
+                    max.max(value),
+                    sum + value as u128,
+                    count + 1,
+                    value,
+                )
+            },
+        );
+        (count > 0).then(|| Self {
+            min,
+            max,
+            avg: sum as f64 / count as f64,
+            latest,
+        })
+    }
+}
 
 #[derive(Clone, Serialize)]
 pub struct MetricsStore {
     pub heap_history: RingBuffer<HeapInfo>,
     pub gc_history: RingBuffer<GcStats>,
     pub thread_snapshot: Vec<ThreadInfo>,
+    pub thread_state_history: RingBuffer<ThreadStateCounts>,
+    pub deadlocked_threads: Vec<u64>,
+    /// Percent of one core each thread used between the last two samples.
+    pub thread_cpu_usage: HashMap<u64, f64>,
+    #[serde(skip)]
+    threads_sampled_at: Option<Instant>,
+    pub gc_pauses: Vec<GcPause>,
     pub class_histogram: Vec<ClassInfo>,
+    pub compiler_stats: Option<CompilerStats>,
+    pub runtime_counters: Option<RuntimeCounters>,
+    pub humongous: Option<HumongousStats>,
+    pub native_memory: Option<NativeMemory>,
 }
 
 impl MetricsStore {
+    /// Keeps `history_size` samples per series; 0 keeps just the latest.
     pub fn new(history_size: usize) -> Self {
         Self {
             heap_history: RingBuffer::new(history_size),
             gc_history: RingBuffer::new(history_size),
             thread_snapshot: Vec::new(),
+            thread_state_history: RingBuffer::new(history_size),
+            deadlocked_threads: Vec::new(),
+            thread_cpu_usage: HashMap::new(),
+            threads_sampled_at: None,
+            gc_pauses: Vec::new(),
             class_histogram: Vec::new(),
+            compiler_stats: None,
+            runtime_counters: None,
+            humongous: None,
+            native_memory: None,
         }
     }
 
+    /// Drops all collected data, keeping the configured history size.
+    pub fn clear(&mut self) {
+        *self = Self::new(self.heap_history.capacity());
+    }
+
     pub fn record_heap(&mut self, info: HeapInfo) {
         self.heap_history.push(info);
     }
@@ -29,10 +110,588 @@ impl MetricsStore {
     }
 
     pub fn record_threads(&mut self, threads: Vec<ThreadInfo>) {
+        self.record_threads_at(threads, Instant::now());
+    }
+
+    /// Like `record_threads`, with the sample time given explicitly so CPU
+    /// usage can be derived from the change since the previous sample.
+    pub fn record_threads_at(&mut self, threads: Vec<ThreadInfo>, now: Instant) {
+        self.thread_cpu_usage.clear();
+        if let Some(previous_at) = self.threads_sampled_at {
+            let elapsed_ns = now.duration_since(previous_at).as_nanos() as f64;
+            if elapsed_ns > 0.0 {
+                let previous: HashMap<u64, u64> = self
+                    .thread_snapshot
+                    .iter()
+                    .filter_map(|t| t.cpu_time_ns.map(|cpu| (t.id, cpu)))
+                    .collect();
+
+                for thread in &threads {
+                    if let (Some(cpu), Some(before)) =
+                        (thread.cpu_time_ns, previous.get(&thread.id))
+                    {
+                        let delta = cpu.saturating_sub(*before) as f64;
+                        self.thread_cpu_usage
+                            .insert(thread.id, delta / elapsed_ns * 100.0);
+                    }
+                }
+            }
+        }
+        self.threads_sampled_at = Some(now);
+
+        self.thread_state_history
+            .push(ThreadStateCounts::from_threads(&threads));
         self.thread_snapshot = threads;
     }
 
+    /// Change in total thread count across the buffered window; a steady
+    /// climb here is what a thread leak looks like.
+    pub fn thread_count_trend(&self) -> Option<i64> {
+        if self.thread_state_history.len() < 2 {
+            return None;
+        }
+        let first = self.thread_state_history.iter().next()?;
+        let last = self.thread_state_history.latest()?;
+        Some(last.total() as i64 - first.total() as i64)
+    }
+
+    pub fn record_deadlocks(&mut self, thread_ids: Vec<u64>) {
+        self.deadlocked_threads = thread_ids;
+    }
+
+    pub fn record_gc_pauses(&mut self, pauses: Vec<GcPause>) {
+        self.gc_pauses = pauses;
+    }
+
+    /// Pause counts per bucket of `GC_PAUSE_BUCKETS_MS`, plus a final
+    /// overflow bucket.
+    pub fn gc_pause_histogram(&self) -> Vec<u64> {
+        let mut counts = vec![0; GC_PAUSE_BUCKETS_MS.len() + 1];
+        for pause in &self.gc_pauses {
+            let bucket = GC_PAUSE_BUCKETS_MS
+                .iter()
+                .position(|&bound| pause.duration_ms < bound)
+                .unwrap_or(GC_PAUSE_BUCKETS_MS.len());
+            counts[bucket] += 1;
+        }
+        counts
+    }
+
+    /// How many recent pauses each cause triggered, most frequent first.
+    /// Pauses without a cause, such as G1's Remark, are left out.
+    pub fn gc_cause_counts(&self) -> Vec<(&str, usize)> {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for cause in self.gc_pauses.iter().filter_map(|p| p.cause.as_deref()) {
+            match counts.iter_mut().find(|(c, _)| *c == cause) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((cause, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts
+    }
+
     pub fn record_class_histogram(&mut self, classes: Vec<ClassInfo>) {
         self.class_histogram = classes;
     }
+
+    pub fn record_compiler_stats(&mut self, stats: CompilerStats) {
+        self.compiler_stats = Some(stats);
+    }
+
+    pub fn record_runtime_counters(&mut self, counters: RuntimeCounters) {
+        self.runtime_counters = Some(counters);
+    }
+
+    pub fn record_humongous(&mut self, stats: Option<HumongousStats>) {
+        self.humongous = stats;
+    }
+
+    pub fn record_native_memory(&mut self, native_memory: NativeMemory) {
+        self.native_memory = Some(native_memory);
+    }
+
+    /// Used heap bytes over the buffered window.
+    pub fn heap_used_stats(&self) -> Option<Stats> {
+        Stats::from_samples(self.heap_history.iter().map(|heap| heap.used_bytes))
+    }
+
+    /// Collections and GC time accrued over the buffered window, stamped with
+    /// the latest sample's time. Counter resets clamp to zero.
+    pub fn gc_window_delta(&self) -> Option<GcStats> {
+        let first = self.gc_history.iter().next()?;
+        let last = self.gc_history.latest()?;
+
+        let collectors = last
+            .collectors
+            .iter()
+            .map(|collector| {
+                let base = first
+                    .collectors
+                    .iter()
+                    .find(|base| base.name == collector.name);
+                GcCollectorStat {
+                    count: collector
+                        .count
+                        .saturating_sub(base.map_or(0, |base| base.count)),
+                    time_ms: collector
+                        .time_ms
+                        .saturating_sub(base.map_or(0, |base| base.time_ms)),
+                    ..collector.clone()
+                }
+            })
+            .collect();
+
+        Some(GcStats {
+            collectors,
+            timestamp: last.timestamp,
+        })
+    }
+
+    /// GC time spent between consecutive samples as `(sample index, young ms,
+    /// full ms)`, indexed by the later sample. A counter that went backwards
+    /// (the JVM restarted or we reconnected) counts as zero.
+    pub fn gc_time_deltas(&self) -> Vec<(f64, f64, f64)> {
+        self.gc_history
+            .iter()
+            .zip(self.gc_history.iter().skip(1))
+            .enumerate()
+            .map(|(i, (before, after))| {
+                (
+                    (i + 1) as f64,
+                    after
+                        .young_gc_time_ms()
+                        .saturating_sub(before.young_gc_time_ms()) as f64,
+                    after
+                        .old_gc_time_ms()
+                        .saturating_sub(before.old_gc_time_ms()) as f64,
+                )
+            })
+            .collect()
+    }
+
+    /// Young and full collections per minute over the buffered window.
+    pub fn gc_rate_per_min(&self) -> Option<f64> {
+        let first = self.gc_history.iter().next()?;
+        let delta = self.gc_window_delta()?;
+
+        let wall_ms = (delta.timestamp - first.timestamp).num_milliseconds();
+        if wall_ms <= 0 {
+            return None;
+        }
+
+        let collections = delta.young_gc_count() + delta.old_gc_count();
+        Some(collections as f64 * 60_000.0 / wall_ms as f64)
+    }
+
+    /// Estimated allocation rate over the buffered window, in MB/s: the heap
+    /// growth between consecutive samples, skipping the drops GCs cause.
+    /// Allocation between a sample and the GC that follows it goes uncounted,
+    /// so this is a lower bound.
+    pub fn allocation_rate_mb_per_sec(&self) -> Option<f64> {
+        let first = self.heap_history.iter().next()?;
+        let last = self.heap_history.latest()?;
+
+        let wall_ms = (last.timestamp - first.timestamp).num_milliseconds();
+        if wall_ms <= 0 {
+            return None;
+        }
+
+        let allocated: u64 = self
+            .heap_history
+            .iter()
+            .zip(self.heap_history.iter().skip(1))
+            .map(|(before, after)| after.used_bytes.saturating_sub(before.used_bytes))
+            .sum();
+        Some(allocated as f64 / (1024.0 * 1024.0) / (wall_ms as f64 / 1000.0))
+    }
+
+    /// Heap used after each full GC in the window. A full GC is seen as an
+    /// increase in the full GC count between two GC samples; its floor is the
+    /// lowest heap sample from then until the next GC sample. `None` until
+    /// [`MIN_TREND_FULL_GCS`] full GCs have been seen.
+    pub fn post_gc_heap_trend(&self) -> Option<Trend> {
+        let gc: Vec<&GcStats> = self.gc_history.iter().collect();
+        let floors: Vec<u64> = gc
+            .windows(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[1].old_gc_count() > pair[0].old_gc_count())
+            .filter_map(|(i, pair)| {
+                let until = gc.get(i + 2).map(|next| next.timestamp);
+                self.heap_history
+                    .iter()
+                    .filter(|heap| {
+                        heap.timestamp > pair[0].timestamp
+                            && until.map_or(true, |until| heap.timestamp <= until)
+                    })
+                    .map(|heap| heap.used_bytes)
+                    .min()
+            })
+            .collect();
+
+        if floors.len() < MIN_TREND_FULL_GCS {
+            return None;
+        }
+        Some(Trend {
+            collections: floors.len(),
+            first_floor_bytes: floors[0],
+            last_floor_bytes: floors[floors.len() - 1],
+            rising: floors.windows(2).all(|pair| pair[1] > pair[0]),
+        })
+    }
+
+    /// Application throughput over the buffered window: the share of wall-clock
+    /// time not spent in GC pauses, as a percentage.
+    pub fn gc_throughput_percent(&self) -> Option<f64> {
+        let first = self.gc_history.iter().next()?;
+        let last = self.gc_history.latest()?;
+
+        let wall_ms = (last.timestamp - first.timestamp).num_milliseconds();
+        if wall_ms <= 0 {
+            return None;
+        }
+
+        let gc_ms = (last.young_gc_time_ms() + last.old_gc_time_ms())
+            .saturating_sub(first.young_gc_time_ms() + first.old_gc_time_ms());
+        let gc_ratio = (gc_ms as f64 / wall_ms as f64).min(1.0);
+
+        Some((1.0 - gc_ratio) * 100.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Local};
+
+    fn gc_sample(seconds: i64, young_ms: u64, old_ms: u64) -> GcStats {
+        GcStats::from_generations(
+            young_ms / 10,
+            young_ms,
+            old_ms / 100,
+            old_ms,
+            Local::now() + Duration::seconds(seconds),
+        )
+    }
+
+    #[test]
+    fn test_gc_throughput_percent() {
+        let mut store = MetricsStore::new(10);
+        assert!(store.gc_throughput_percent().is_none());
+
+        store.record_gc(gc_sample(0, 1000, 500));
+        assert!(store.gc_throughput_percent().is_none());
+
+        store.record_gc(gc_sample(10, 1300, 700));
+        let throughput = store.gc_throughput_percent().unwrap();
+        assert!((throughput - 95.0).abs() < 0.01);
+    }
+
+    fn heap_sample(used_mb: u64) -> HeapInfo {
+        HeapInfo {
+            used_bytes: used_mb * 1024 * 1024,
+            committed_bytes: 512 * 1024 * 1024,
+            max_bytes: 1024 * 1024 * 1024,
+            pools: Vec::new(),
+            timestamp: Local::now(),
+        }
+    }
+
+    #[test]
+    fn test_allocation_rate() {
+        let start = Local::now();
+        let at = |used_mb, seconds| HeapInfo {
+            timestamp: start + Duration::seconds(seconds),
+            ..heap_sample(used_mb)
+        };
+
+        let mut store = MetricsStore::new(10);
+        store.record_heap(at(100, 0));
+        assert!(store.allocation_rate_mb_per_sec().is_none());
+
+        // +100 MB, a GC drops 250 MB, then +150 MB, over 10 seconds
+        store.record_heap(at(200, 4));
+        store.record_heap(at(50, 6));
+        store.record_heap(at(200, 10));
+        let rate = store.allocation_rate_mb_per_sec().unwrap();
+        assert!((rate - 25.0).abs() < 0.01);
+
+        // Samples read in the same instant give no rate
+        let mut store = MetricsStore::new(10);
+        store.record_heap(at(100, 0));
+        store.record_heap(at(200, 0));
+        assert!(store.allocation_rate_mb_per_sec().is_none());
+    }
+
+    /// One poll: a heap sample, then the GC counters read just after it.
+    fn poll(store: &mut MetricsStore, seconds: i64, used_mb: u64, full_gcs: u64) {
+        let at = Local::now() - Duration::hours(1) + Duration::seconds(seconds);
+        store.record_heap(HeapInfo {
+            timestamp: at,
+            ..heap_sample(used_mb)
+        });
+        store.record_gc(GcStats::from_generations(
+            0,
+            0,
+            full_gcs,
+            full_gcs * 100,
+            at + Duration::milliseconds(10),
+        ));
+    }
+
+    #[test]
+    fn test_post_gc_heap_trend() {
+        let mut store = MetricsStore::new(100);
+        // Each full GC leaves more behind: 100, 150, 220 MB
+        let polls = [
+            (500, 0),
+            (100, 1),
+            (300, 1),
+            (600, 1),
+            (150, 2),
+            (400, 2),
+            (220, 3),
+            (240, 3),
+        ];
+        for (i, (used_mb, full_gcs)) in polls.into_iter().enumerate() {
+            poll(&mut store, i as i64 * 5, used_mb, full_gcs);
+            if i == 4 {
+                assert!(store.post_gc_heap_trend().is_none());
+            }
+        }
+
+        let trend = store.post_gc_heap_trend().unwrap();
+        assert_eq!(trend.collections, 3);
+        assert_eq!(trend.first_floor_bytes, 100 * 1024 * 1024);
+        assert_eq!(trend.last_floor_bytes, 220 * 1024 * 1024);
+        assert!(trend.rising);
+
+        // A fourth full GC gets back down to where the first one did
+        poll(&mut store, 60, 100, 4);
+        let trend = store.post_gc_heap_trend().unwrap();
+        assert_eq!(trend.collections, 4);
+        assert!(!trend.rising);
+    }
+
+    #[test]
+    fn test_heap_used_stats() {
+        let mut store = MetricsStore::new(3);
+        assert!(store.heap_used_stats().is_none());
+
+        for used_mb in [400, 100, 300, 200] {
+            store.record_heap(heap_sample(used_mb));
+        }
+
+        // The 400 MB sample has rotated out of the window
+        let stats = store.heap_used_stats().unwrap();
+        assert_eq!(stats.min, 100 * 1024 * 1024);
+        assert_eq!(stats.max, 300 * 1024 * 1024);
+        assert_eq!(stats.latest, 200 * 1024 * 1024);
+        assert!((stats.avg - 200.0 * 1024.0 * 1024.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_gc_rate_per_min() {
+        let mut store = MetricsStore::new(10);
+        store.record_gc(gc_sample(0, 1000, 500));
+        assert!(store.gc_rate_per_min().is_none());
+
+        // 30 young + 2 full collections in 30 seconds
+        store.record_gc(gc_sample(30, 1300, 700));
+        let rate = store.gc_rate_per_min().unwrap();
+        assert!((rate - 64.0).abs() < 0.01);
+
+        let delta = store.gc_window_delta().unwrap();
+        assert_eq!(delta.young_gc_count(), 30);
+        assert_eq!(delta.old_gc_time_ms(), 200);
+        assert!((delta.avg_young_gc_ms() - 10.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_gc_time_deltas() {
+        let mut store = MetricsStore::new(10);
+        store.record_gc(gc_sample(0, 1000, 500));
+        assert!(store.gc_time_deltas().is_empty());
+
+        store.record_gc(gc_sample(5, 1040, 500));
+        store.record_gc(gc_sample(10, 1100, 700));
+        assert_eq!(
+            store.gc_time_deltas(),
+            vec![(1.0, 40.0, 0.0), (2.0, 60.0, 200.0)]
+        );
+    }
+
+    #[test]
+    fn test_gc_time_deltas_clamp_counter_resets() {
+        let mut store = MetricsStore::new(10);
+        store.record_gc(gc_sample(0, 1000, 500));
+        // Reconnected to a restarted JVM
+        store.record_gc(gc_sample(5, 20, 0));
+        store.record_gc(gc_sample(10, 50, 100));
+        assert_eq!(
+            store.gc_time_deltas(),
+            vec![(1.0, 0.0, 0.0), (2.0, 30.0, 100.0)]
+        );
+    }
+
+    #[test]
+    fn test_gc_window_delta_clamps_counter_resets() {
+        let mut store = MetricsStore::new(10);
+        store.record_gc(gc_sample(0, 1000, 500));
+        store.record_gc(gc_sample(10, 100, 0));
+
+        let delta = store.gc_window_delta().unwrap();
+        assert_eq!(delta.young_gc_count(), 0);
+        assert_eq!(delta.old_gc_time_ms(), 0);
+        assert_eq!(store.gc_rate_per_min(), Some(0.0));
+    }
+
+    #[test]
+    fn test_clear_keeps_history_size() {
+        let mut store = MetricsStore::new(3);
+        for seconds in 0..5 {
+            store.record_gc(gc_sample(seconds, 0, 0));
+        }
+        store.record_deadlocks(vec![7]);
+
+        store.clear();
+        assert!(store.gc_history.is_empty());
+        assert!(store.deadlocked_threads.is_empty());
+        assert_eq!(store.gc_history.capacity(), 3);
+
+        let store = MetricsStore::new(0);
+        assert_eq!(store.heap_history.capacity(), 1);
+    }
+
+    #[test]
+    fn test_record_threads_tracks_state_history() {
+        use crate::jvm::types::ThreadState;
+
+        let thread = |id, state| ThreadInfo {
+            id,
+            name: format!("t{}", id),
+            state,
+            stack_trace: Vec::new(),
+            cpu_time_ns: None,
+            daemon: None,
+            locks: Vec::new(),
+        };
+
+        let mut store = MetricsStore::new(10);
+        assert_eq!(store.thread_count_trend(), None);
+        store.record_threads(vec![
+            thread(1, ThreadState::Runnable),
+            thread(2, ThreadState::Blocked),
+        ]);
+        store.record_threads(vec![
+            thread(1, ThreadState::Blocked),
+            thread(2, ThreadState::Blocked),
+            thread(3, ThreadState::Waiting),
+        ]);
+
+        let history: Vec<_> = store.thread_state_history.iter().collect();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].blocked, 1);
+        assert_eq!(history[1].blocked, 2);
+        assert_eq!(history[1].total(), 3);
+        assert_eq!(store.thread_count_trend(), Some(1));
+    }
+
+    #[test]
+    fn test_thread_cpu_usage_from_deltas() {
+        use crate::jvm::types::ThreadState;
+        use std::time::Duration;
+
+        let thread = |id, cpu_time_ns| ThreadInfo {
+            id,
+            name: format!("t{}", id),
+            state: ThreadState::Runnable,
+            stack_trace: Vec::new(),
+            cpu_time_ns,
+            daemon: None,
+            locks: Vec::new(),
+        };
+
+        let mut store = MetricsStore::new(10);
+        let start = Instant::now();
+        store.record_threads_at(
+            vec![thread(1, Some(1_000_000_000)), thread(2, Some(0))],
+            start,
+        );
+        assert!(store.thread_cpu_usage.is_empty());
+
+        store.record_threads_at(
+            vec![
+                thread(1, Some(1_500_000_000)),
+                thread(2, Some(1_000_000_000)),
+                thread(3, Some(200_000_000)),
+                thread(4, None),
+            ],
+            start + Duration::from_secs(1),
+        );
+        assert_eq!(store.thread_cpu_usage.get(&1), Some(&50.0));
+        assert_eq!(store.thread_cpu_usage.get(&2), Some(&100.0));
+        // New and unmeasured threads have no previous sample to compare with
+        assert!(!store.thread_cpu_usage.contains_key(&3));
+        assert!(!store.thread_cpu_usage.contains_key(&4));
+    }
+
+    #[test]
+    fn test_gc_pause_histogram() {
+        let pause = |duration_ms| GcPause {
+            gc_id: 0,
+            kind: "Young (Normal)".to_string(),
+            cause: Some("G1 Evacuation Pause".to_string()),
+            duration_ms,
+            timestamp: None,
+            uptime_seconds: None,
+        };
+
+        let mut store = MetricsStore::new(10);
+        store.record_gc_pauses(vec![
+            pause(0.4),
+            pause(3.2),
+            pause(4.9),
+            pause(12.0),
+            pause(100.0),
+            pause(1200.0),
+        ]);
+
+        assert_eq!(store.gc_pause_histogram(), vec![1, 2, 0, 1, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_gc_cause_counts() {
+        let pause = |cause: Option<&str>| GcPause {
+            gc_id: 0,
+            kind: "Young".to_string(),
+            cause: cause.map(str::to_string),
+            duration_ms: 1.0,
+            timestamp: None,
+            uptime_seconds: None,
+        };
+
+        let mut store = MetricsStore::new(10);
+        store.record_gc_pauses(vec![
+            pause(Some("System.gc()")),
+            pause(Some("Allocation Failure")),
+            pause(None),
+            pause(Some("Allocation Failure")),
+            pause(Some("Allocation Failure")),
+            pause(Some("System.gc()")),
+        ]);
+
+        assert_eq!(
+            store.gc_cause_counts(),
+            vec![("Allocation Failure", 3), ("System.gc()", 2)]
+        );
+    }
+
+    #[test]
+    fn test_gc_throughput_clamps_counter_resets() {
+        let mut store = MetricsStore::new(10);
+        store.record_gc(gc_sample(0, 5000, 0));
+        store.record_gc(gc_sample(10, 100, 0));
+        assert_eq!(store.gc_throughput_percent(), Some(100.0));
+    }
 }
@@ -0,0 +1,90 @@
+use crate::error::Result;
+use crate::export;
+use crate::metrics::store::MetricsStore;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::sync::RwLock;
+
+/// Content type Prometheus expects for the text exposition format.
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Serves `/metrics` from the live store until `shutdown` resolves. The
+/// collector keeps writing to `store`; each scrape renders the latest sample.
+pub async fn serve(
+    listener: TcpListener,
+    store: Arc<RwLock<MetricsStore>>,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<()> {
+    let app = Router::new()
+        .route("/metrics", get(metrics))
+        .with_state(store);
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown)
+        .await?;
+    Ok(())
+}
+
+async fn metrics(State(store): State<Arc<RwLock<MetricsStore>>>) -> Response {
+    let rendered = export::render_metrics_prometheus(&*store.read().await);
+    match rendered {
+        Ok(body) => ([(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], body).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jvm::types::HeapInfo;
+    use chrono::Local;
+
+    #[tokio::test]
+    async fn test_metrics_endpoint_serves_latest_sample() {
+        let store = Arc::new(RwLock::new(MetricsStore::new(10)));
+        store.write().await.record_heap(HeapInfo {
+            used_bytes: 1024,
+            max_bytes: 4096,
+            committed_bytes: 2048,
+            pools: vec![],
+            timestamp: Local::now(),
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(serve(listener, store, async {
+            let _ = stopped.await;
+        }));
+
+        // Bypass any proxy from the environment for the loopback address
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let response = client
+            .get(format!("http://{}/metrics", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.headers()[header::CONTENT_TYPE.as_str()]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4"));
+        let body = response.text().await.unwrap();
+        assert!(body.contains("jvm_memory_heap_used_bytes 1024"));
+
+        let missing = client
+            .get(format!("http://{}/other", addr))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), 404);
+
+        stop.send(()).unwrap();
+        server.await.unwrap().unwrap();
+    }
+}
@@ -1,3 +1,4 @@
+use crate::jvm::types::PoolType;
 use ratatui::style::Color;
 
 #[derive(Debug, Clone, Copy)]
@@ -56,6 +57,11 @@ impl Theme {
         Color::Yellow
     }
 
+    /// Text drawn on a coloured background, such as the heap composition bar.
+    pub fn text_on_color(&self) -> Color {
+        Color::Black
+    }
+
     pub fn gauge_filled(&self) -> Color {
         Color::Cyan
     }
@@ -100,6 +106,41 @@ impl Theme {
         Color::Reset
     }
 
+    pub fn pool_eden(&self) -> Color {
+        Color::Green
+    }
+
+    pub fn pool_survivor(&self) -> Color {
+        Color::LightGreen
+    }
+
+    pub fn pool_old(&self) -> Color {
+        Color::Blue
+    }
+
+    pub fn pool_metaspace(&self) -> Color {
+        Color::Magenta
+    }
+
+    pub fn pool_code_cache(&self) -> Color {
+        Color::Cyan
+    }
+
+    pub fn pool_other(&self) -> Color {
+        Color::Indexed(8)
+    }
+
+    pub fn pool_color(&self, pool_type: PoolType) -> Color {
+        match pool_type {
+            PoolType::Eden => self.pool_eden(),
+            PoolType::Survivor => self.pool_survivor(),
+            PoolType::Old => self.pool_old(),
+            PoolType::Metaspace => self.pool_metaspace(),
+            PoolType::CodeCache => self.pool_code_cache(),
+            PoolType::Other => self.pool_other(),
+        }
+    }
+
     pub fn chart_line_primary(&self) -> Color {
         Color::Cyan
     }
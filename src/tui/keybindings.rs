@@ -0,0 +1,830 @@
+use crate::app::Tab;
+use crate::config::KeyList;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// What a key does on the monitoring screen. The event loop dispatches on
+/// these, and the help overlay and footer are generated from [`BINDINGS`],
+/// so all three describe the same keys. Config files name them in snake_case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Help,
+    SelectTab,
+    NextTab,
+    PreviousTab,
+    ScrollDown,
+    ScrollUp,
+    PanTimelineBack,
+    PanTimelineForward,
+    ZoomTimelineIn,
+    ZoomTimelineOut,
+    TriggerGc,
+    HeapDump,
+    Reset,
+    PollSlower,
+    PollFaster,
+    Alerts,
+    Export,
+    SupportBundle,
+    Shell,
+    DiagnosticCommand,
+    SystemProperties,
+    ProcessInfo,
+    RawOutput,
+    ToggleCompact,
+    ToggleEventLog,
+    Search,
+    Sort,
+    Baseline,
+    Columns,
+    CopyStack,
+    CopyThreadDump,
+    ShowAllThreads,
+}
+
+/// Help overlay section a binding is listed under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Section {
+    Global,
+    Navigation,
+    Actions,
+    View,
+}
+
+impl Section {
+    pub fn all() -> &'static [Section] {
+        &[
+            Section::Global,
+            Section::Navigation,
+            Section::Actions,
+            Section::View,
+        ]
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Section::Global => "Global",
+            Section::Navigation => "Navigation",
+            Section::Actions => "Actions",
+            Section::View => "View-Specific",
+        }
+    }
+}
+
+pub struct KeyBinding {
+    pub action: Action,
+    /// Matched whatever the modifiers, since terminals report Shift on
+    /// capitals inconsistently.
+    pub keys: &'static [KeyCode],
+    /// How the keys are written in the help overlay.
+    pub label: &'static str,
+    pub description: &'static str,
+    /// Tabs the binding works on; empty for every tab.
+    pub tabs: &'static [Tab],
+    pub section: Section,
+    /// Short form for the footer, if the binding earns a place there.
+    pub hint: Option<&'static str>,
+}
+
+impl KeyBinding {
+    pub fn applies_to(&self, tab: Tab) -> bool {
+        self.tabs.is_empty() || self.tabs.contains(&tab)
+    }
+
+    /// The description, with the tabs it's limited to appended.
+    pub fn help_text(&self) -> String {
+        if self.tabs.is_empty() {
+            return self.description.to_string();
+        }
+        let tabs: Vec<&str> = self.tabs.iter().map(|tab| tab.title()).collect();
+        let noun = if self.tabs.len() == 1 {
+            "view"
+        } else {
+            "views"
+        };
+        format!("{} ({} {})", self.description, tabs.join("/"), noun)
+    }
+}
+
+/// Keys the search prompt handles; it takes free text, so these are shown in
+/// the help overlay but matched in the search handler itself.
+pub const SEARCH_KEYS: &[(&str, &str)] = &[
+    ("n", "Next search result (during search)"),
+    ("N", "Previous search result (during search)"),
+    ("Esc", "Cancel search (during search)"),
+];
+
+/// Every monitoring-screen binding. Lookup takes the first match, so
+/// tab-specific bindings come before the general ones they shadow.
+pub const BINDINGS: &[KeyBinding] = &[
+    KeyBinding {
+        action: Action::Quit,
+        keys: &[KeyCode::Char('q')],
+        label: "q / Ctrl-C",
+        description: "Quit application",
+        tabs: &[],
+        section: Section::Global,
+        hint: Some("q: Quit"),
+    },
+    KeyBinding {
+        action: Action::Help,
+        keys: &[KeyCode::Char('?')],
+        label: "?",
+        description: "Toggle this help screen",
+        tabs: &[],
+        section: Section::Global,
+        hint: Some("?: Help"),
+    },
+    KeyBinding {
+        action: Action::SelectTab,
+        keys: &[
+            KeyCode::Char('1'),
+            KeyCode::Char('2'),
+            KeyCode::Char('3'),
+            KeyCode::Char('4'),
+            KeyCode::Char('5'),
+        ],
+        label: "1-5",
+        description: "Switch to tab (Overview/Memory/Threads/GC/Classes)",
+        tabs: &[],
+        section: Section::Navigation,
+        hint: Some("1-5: Switch Tab"),
+    },
+    KeyBinding {
+        action: Action::PanTimelineBack,
+        keys: &[KeyCode::Char('h'), KeyCode::Left],
+        label: "h / ←",
+        description: "Pan the GC timeline back",
+        tabs: &[Tab::GC],
+        section: Section::View,
+        hint: Some("h/l/←/→: Pan Timeline"),
+    },
+    KeyBinding {
+        action: Action::PanTimelineForward,
+        keys: &[KeyCode::Char('l'), KeyCode::Right],
+        label: "l / →",
+        description: "Pan the GC timeline forward; the end follows new samples",
+        tabs: &[Tab::GC],
+        section: Section::View,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::ZoomTimelineIn,
+        keys: &[KeyCode::Char('+'), KeyCode::Char('=')],
+        label: "+",
+        description: "Zoom the GC timeline in",
+        tabs: &[Tab::GC],
+        section: Section::View,
+        hint: Some("+/-: Zoom"),
+    },
+    KeyBinding {
+        action: Action::ZoomTimelineOut,
+        keys: &[KeyCode::Char('-')],
+        label: "-",
+        description: "Zoom the GC timeline out",
+        tabs: &[Tab::GC],
+        section: Section::View,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::NextTab,
+        keys: &[KeyCode::Char('l'), KeyCode::Right, KeyCode::Tab],
+        label: "l / → / Tab",
+        description: "Next tab",
+        tabs: &[],
+        section: Section::Navigation,
+        hint: Some("h/l/←/→: Prev/Next"),
+    },
+    KeyBinding {
+        action: Action::PreviousTab,
+        keys: &[KeyCode::Char('h'), KeyCode::Left, KeyCode::BackTab],
+        label: "h / ← / Shift+Tab",
+        description: "Previous tab",
+        tabs: &[],
+        section: Section::Navigation,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::ScrollDown,
+        keys: &[KeyCode::Char('j'), KeyCode::Down],
+        label: "j / ↓",
+        description: "Scroll down",
+        tabs: &[Tab::Overview, Tab::Threads, Tab::Classes],
+        section: Section::View,
+        hint: Some("j/k/↑/↓: Scroll"),
+    },
+    KeyBinding {
+        action: Action::ScrollUp,
+        keys: &[KeyCode::Char('k'), KeyCode::Up],
+        label: "k / ↑",
+        description: "Scroll up",
+        tabs: &[Tab::Overview, Tab::Threads, Tab::Classes],
+        section: Section::View,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::Search,
+        keys: &[KeyCode::Char('/')],
+        label: "/",
+        description: "Search threads / filter classes",
+        tabs: &[Tab::Threads, Tab::Classes],
+        section: Section::View,
+        hint: Some("/: Search"),
+    },
+    KeyBinding {
+        action: Action::Sort,
+        keys: &[KeyCode::Char('s')],
+        label: "s",
+        description: "Cycle the sort column",
+        tabs: &[Tab::Threads, Tab::Classes],
+        section: Section::View,
+        hint: Some("s: Sort"),
+    },
+    KeyBinding {
+        action: Action::Baseline,
+        keys: &[KeyCode::Char('b')],
+        label: "b",
+        description: "Capture/drop a histogram baseline to diff against",
+        tabs: &[Tab::Classes],
+        section: Section::View,
+        hint: Some("b: Baseline Diff"),
+    },
+    KeyBinding {
+        action: Action::CopyStack,
+        keys: &[KeyCode::Char('y')],
+        label: "y",
+        description: "Copy the selected thread's stack to the clipboard",
+        tabs: &[Tab::Threads],
+        section: Section::View,
+        hint: Some("y/Y: Copy Thread/Dump"),
+    },
+    KeyBinding {
+        action: Action::CopyThreadDump,
+        keys: &[KeyCode::Char('Y')],
+        label: "Y",
+        description: "Copy the full thread dump to the clipboard",
+        tabs: &[Tab::Threads],
+        section: Section::View,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::ShowAllThreads,
+        keys: &[KeyCode::Char('A')],
+        label: "A",
+        description: "Show all threads / only max_threads_displayed",
+        tabs: &[Tab::Threads],
+        section: Section::View,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::Columns,
+        keys: &[KeyCode::Char('c')],
+        label: "c",
+        description: "Show/hide table columns",
+        tabs: &[Tab::Threads, Tab::Classes],
+        section: Section::View,
+        hint: Some("c: Columns"),
+    },
+    KeyBinding {
+        action: Action::TriggerGc,
+        keys: &[KeyCode::Char('g')],
+        label: "g",
+        description: "Trigger garbage collection (with confirmation)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: Some("g: Trigger GC"),
+    },
+    KeyBinding {
+        action: Action::HeapDump,
+        keys: &[KeyCode::Char('D')],
+        label: "D",
+        description: "Write a heap dump (shows size and free space first)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::Reset,
+        keys: &[KeyCode::Char('r')],
+        label: "r",
+        description: "Reset metrics store",
+        tabs: &[],
+        section: Section::Actions,
+        hint: Some("r: Reset"),
+    },
+    KeyBinding {
+        action: Action::PollSlower,
+        keys: &[KeyCode::Char('+'), KeyCode::Char('=')],
+        label: "+",
+        description: "Poll less often (outside the GC view)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::PollFaster,
+        keys: &[KeyCode::Char('-')],
+        label: "-",
+        description: "Poll more often (outside the GC view)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::Alerts,
+        keys: &[KeyCode::Char('a')],
+        label: "a",
+        description: "Show alerts fired this session",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::Export,
+        keys: &[KeyCode::Char('e')],
+        label: "e",
+        description: "Export metrics (the thread dump on the Threads tab)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::SupportBundle,
+        keys: &[KeyCode::Char('E')],
+        label: "E",
+        description: "Write a support bundle zip (metrics, threads, flags, properties)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::Shell,
+        keys: &[KeyCode::Char('!')],
+        label: "!",
+        description: "Suspend and run a shell command ($JVM_TUI_PID is set)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::DiagnosticCommand,
+        keys: &[KeyCode::Char(':')],
+        label: ":",
+        description: "Run a jcmd diagnostic command and page its output",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::SystemProperties,
+        keys: &[KeyCode::Char('p')],
+        label: "p",
+        description: "Show JVM system properties (type to filter)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::ProcessInfo,
+        keys: &[KeyCode::Char('i')],
+        label: "i",
+        description: "Show the process command line and working directory",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::RawOutput,
+        keys: &[KeyCode::Char('o')],
+        label: "o",
+        description: "Show the raw output of the latest tool calls (--debug-raw)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::ToggleCompact,
+        keys: &[KeyCode::Char('m')],
+        label: "m",
+        description: "Toggle the compact layout (always on below 25 rows)",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+    KeyBinding {
+        action: Action::ToggleEventLog,
+        keys: &[KeyCode::Char('L')],
+        label: "L",
+        description: "Toggle the event log pane",
+        tabs: &[],
+        section: Section::Actions,
+        hint: None,
+    },
+];
+
+/// [`BINDINGS`] with the keys from the config's `[keybindings]` section
+/// swapped in. Actions the config doesn't mention keep their default keys.
+#[derive(Debug, Clone, Default)]
+pub struct KeyMap {
+    overrides: HashMap<Action, Vec<KeyCode>>,
+}
+
+impl KeyMap {
+    pub fn new(config: &BTreeMap<Action, KeyList>) -> Result<Self, String> {
+        let mut overrides = HashMap::new();
+        for (action, keys) in config {
+            let codes = keys
+                .keys()
+                .iter()
+                .map(|key| parse_key(key).map_err(|e| format!("{}: {}", action_name(*action), e)))
+                .collect::<Result<Vec<_>, _>>()?;
+            if *action == Action::SelectTab && codes.len() != Tab::all().len() {
+                return Err(format!(
+                    "select_tab needs {} keys, one per tab in order",
+                    Tab::all().len()
+                ));
+            }
+            overrides.insert(*action, codes);
+        }
+
+        let keymap = Self { overrides };
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// Two bindings may share a key only when one is limited to tabs the
+    /// other isn't, as the GC timeline's `h`/`l` shadow tab switching.
+    fn check_conflicts(&self) -> Result<(), String> {
+        for (i, binding) in BINDINGS.iter().enumerate() {
+            for other in &BINDINGS[i + 1..] {
+                let overlapping = if binding.tabs.is_empty() || other.tabs.is_empty() {
+                    binding.tabs.is_empty() && other.tabs.is_empty()
+                } else {
+                    binding.tabs.iter().any(|tab| other.tabs.contains(tab))
+                };
+                if !overlapping {
+                    continue;
+                }
+                if let Some(code) = self
+                    .keys(binding)
+                    .iter()
+                    .find(|code| self.keys(other).contains(code))
+                {
+                    return Err(format!(
+                        "'{}' is bound to both {} and {}",
+                        key_name(*code),
+                        action_name(binding.action),
+                        action_name(other.action)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The keys `binding` answers to.
+    pub fn keys<'a>(&'a self, binding: &'a KeyBinding) -> &'a [KeyCode] {
+        self.overrides
+            .get(&binding.action)
+            .map_or(binding.keys, Vec::as_slice)
+    }
+
+    /// The keys bound to `action`, in order; `SelectTab`'s are one per tab.
+    pub fn keys_for(&self, action: Action) -> &[KeyCode] {
+        BINDINGS
+            .iter()
+            .find(|binding| binding.action == action)
+            .map_or(&[], |binding| self.keys(binding))
+    }
+
+    /// The action `key` triggers on `tab`, if any.
+    pub fn action_for(&self, key: &KeyEvent, tab: Tab) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) {
+            return (key.code == KeyCode::Char('c')).then_some(Action::Quit);
+        }
+
+        BINDINGS
+            .iter()
+            .find(|binding| binding.applies_to(tab) && self.keys(binding).contains(&key.code))
+            .map(|binding| binding.action)
+    }
+
+    /// How the binding's keys are written in the help overlay.
+    pub fn label(&self, binding: &KeyBinding) -> String {
+        let Some(keys) = self.overrides.get(&binding.action) else {
+            return binding.label.to_string();
+        };
+        let mut names: Vec<String> = keys.iter().map(|code| key_name(*code)).collect();
+        if binding.action == Action::Quit {
+            names.push("Ctrl-C".to_string());
+        }
+        if names.is_empty() {
+            "(unbound)".to_string()
+        } else {
+            names.join(" / ")
+        }
+    }
+
+    /// The binding's footer hint. A hint can cover a pair of bindings, as in
+    /// "j/k/↑/↓: Scroll"; if either is remapped its keys are rewritten.
+    fn hint(&self, binding: &KeyBinding) -> Option<String> {
+        let hint = binding.hint?;
+        let Some((keys, description)) = hint.split_once(": ") else {
+            return Some(hint.to_string());
+        };
+        let covered: Vec<&KeyBinding> = BINDINGS
+            .iter()
+            .filter(|other| {
+                other.action == binding.action
+                    || (other.section == binding.section
+                        && other.tabs == binding.tabs
+                        && other
+                            .keys
+                            .first()
+                            .is_some_and(|code| keys.split('/').any(|key| key == key_name(*code))))
+            })
+            .collect();
+        if !covered
+            .iter()
+            .any(|other| self.overrides.contains_key(&other.action))
+        {
+            return Some(hint.to_string());
+        }
+        let names: Vec<String> = covered
+            .iter()
+            .flat_map(|other| self.keys(other))
+            .map(|code| key_name(*code))
+            .collect();
+        (!names.is_empty()).then(|| format!("{}: {}", names.join("/"), description))
+    }
+
+    /// Footer hints for `tab`: navigation first, then what the tab adds, then
+    /// the actions that work everywhere. A binding is left out if the tab
+    /// gives any of its keys to something else.
+    pub fn footer_hints(&self, tab: Tab) -> Vec<String> {
+        let reachable = |binding: &KeyBinding| {
+            binding.applies_to(tab)
+                && self.keys(binding).iter().all(|code| {
+                    self.action_for(&KeyEvent::new(*code, KeyModifiers::NONE), tab)
+                        == Some(binding.action)
+                })
+        };
+        [
+            Section::Navigation,
+            Section::View,
+            Section::Actions,
+            Section::Global,
+        ]
+        .into_iter()
+        .flat_map(|section| section_bindings(section).filter(|binding| reachable(binding)))
+        .filter_map(|binding| self.hint(binding))
+        .collect()
+    }
+}
+
+/// Bindings listed under `section` in the help overlay.
+pub fn section_bindings(section: Section) -> impl Iterator<Item = &'static KeyBinding> {
+    BINDINGS
+        .iter()
+        .filter(move |binding| binding.section == section)
+}
+
+/// The name config files use for `action`, e.g. `trigger_gc`.
+fn action_name(action: Action) -> String {
+    serde_json::to_value(action)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_else(|| format!("{:?}", action))
+}
+
+/// Reads a key as written in the config: a single character, or a named key
+/// such as `Esc`, `Tab`, `Up` or `F5` (case-insensitive).
+pub fn parse_key(key: &str) -> Result<KeyCode, String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+
+    let lower = key.to_ascii_lowercase();
+    let code = match lower.as_str() {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" | "shift+tab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=12) => KeyCode::F(n),
+            _ => {
+                return Err(format!(
+                    "unknown key '{}' (use a single character or a name like Esc, Tab, Up, PageDown, F5)",
+                    key
+                ))
+            }
+        },
+    };
+    Ok(code)
+}
+
+/// How a key is shown in the help overlay and footer.
+pub fn key_name(code: KeyCode) -> String {
+    match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn action_for(key: &KeyEvent, tab: Tab) -> Option<Action> {
+        KeyMap::default().action_for(key, tab)
+    }
+
+    fn keymap(toml: &str) -> Result<KeyMap, String> {
+        #[derive(Deserialize)]
+        struct Section {
+            keybindings: BTreeMap<Action, KeyList>,
+        }
+        let section: Section = toml::from_str(toml).unwrap();
+        KeyMap::new(&section.keybindings)
+    }
+
+    #[test]
+    fn test_tab_specific_bindings_shadow_general_ones() {
+        assert_eq!(
+            action_for(&key(KeyCode::Char('l')), Tab::GC),
+            Some(Action::PanTimelineForward)
+        );
+        assert_eq!(
+            action_for(&key(KeyCode::Char('l')), Tab::Memory),
+            Some(Action::NextTab)
+        );
+        assert_eq!(
+            action_for(&key(KeyCode::Char('-')), Tab::GC),
+            Some(Action::ZoomTimelineOut)
+        );
+        assert_eq!(
+            action_for(&key(KeyCode::Char('-')), Tab::Threads),
+            Some(Action::PollFaster)
+        );
+        assert_eq!(action_for(&key(KeyCode::Char('b')), Tab::Threads), None);
+        assert_eq!(action_for(&key(KeyCode::Char('/')), Tab::GC), None);
+    }
+
+    #[test]
+    fn test_modifiers() {
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(action_for(&ctrl_c, Tab::Threads), Some(Action::Quit));
+        let ctrl_g = KeyEvent::new(KeyCode::Char('g'), KeyModifiers::CONTROL);
+        assert_eq!(action_for(&ctrl_g, Tab::Overview), None);
+
+        let shift_d = KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT);
+        assert_eq!(action_for(&shift_d, Tab::Memory), Some(Action::HeapDump));
+    }
+
+    #[test]
+    fn test_every_action_is_reachable() {
+        for binding in BINDINGS {
+            let reachable = Tab::all().iter().any(|tab| {
+                binding
+                    .keys
+                    .iter()
+                    .any(|code| action_for(&key(*code), *tab) == Some(binding.action))
+            });
+            assert!(reachable, "{:?} is shadowed on every tab", binding.action);
+        }
+    }
+
+    #[test]
+    fn test_footer_hints_follow_the_tab() {
+        let keymap = KeyMap::default();
+        let gc = keymap.footer_hints(Tab::GC);
+        assert!(gc.iter().any(|hint| hint == "h/l/←/→: Pan Timeline"));
+        assert!(!gc.iter().any(|hint| hint == "h/l/←/→: Prev/Next"));
+        assert!(!gc.iter().any(|hint| hint == "/: Search"));
+        assert_eq!(gc.first().map(String::as_str), Some("1-5: Switch Tab"));
+        assert_eq!(gc.last().map(String::as_str), Some("?: Help"));
+
+        let classes = keymap.footer_hints(Tab::Classes);
+        assert!(classes.iter().any(|hint| hint == "b: Baseline Diff"));
+        assert!(!classes.iter().any(|hint| hint == "y/Y: Copy Thread/Dump"));
+    }
+
+    #[test]
+    fn test_remapped_keys() {
+        let keymap = keymap(
+            r#"
+            [keybindings]
+            trigger_gc = "G"
+            scroll_down = ["Down"]
+            scroll_up = ["Up"]
+            quit = ["x", "Esc"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('G')), Tab::Memory),
+            Some(Action::TriggerGc)
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('g')), Tab::Memory),
+            None
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('j')), Tab::Threads),
+            None
+        );
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Esc), Tab::Threads),
+            Some(Action::Quit)
+        );
+        // Unmapped actions keep their defaults
+        assert_eq!(
+            keymap.action_for(&key(KeyCode::Char('e')), Tab::Threads),
+            Some(Action::Export)
+        );
+
+        let quit = BINDINGS.iter().find(|b| b.action == Action::Quit).unwrap();
+        assert_eq!(keymap.label(quit), "x / Esc / Ctrl-C");
+        let hints = keymap.footer_hints(Tab::Threads);
+        assert!(hints.iter().any(|hint| hint == "↓/↑: Scroll"));
+        assert!(hints.iter().any(|hint| hint == "G: Trigger GC"));
+        assert!(hints.iter().any(|hint| hint == "x/Esc: Quit"));
+    }
+
+    #[test]
+    fn test_remapped_tab_keys() {
+        let keymap = keymap(
+            r#"
+            [keybindings]
+            select_tab = ["F1", "F2", "F3", "F4", "F5"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(keymap.keys_for(Action::SelectTab)[3], KeyCode::F(4));
+        assert_eq!(keymap.action_for(&key(KeyCode::Char('1')), Tab::GC), None);
+
+        let error = keymap_error("select_tab = [\"a\", \"b\"]");
+        assert!(error.contains("5 keys"), "{}", error);
+    }
+
+    fn keymap_error(bindings: &str) -> String {
+        keymap(&format!("[keybindings]\n{}", bindings)).unwrap_err()
+    }
+
+    #[test]
+    fn test_invalid_keybindings() {
+        assert!(keymap_error("trigger_gc = \"Ctrl+G\"").contains("trigger_gc: unknown key"));
+        assert_eq!(
+            keymap_error("trigger_gc = \"q\""),
+            "'q' is bound to both quit and trigger_gc"
+        );
+        // Tab-specific bindings may shadow general ones
+        assert!(keymap("[keybindings]\nsort = \"g\"").is_ok());
+    }
+
+    #[test]
+    fn test_default_keys_parse_back() {
+        for binding in BINDINGS {
+            for code in binding.keys {
+                let name = match code {
+                    KeyCode::Up => "Up".to_string(),
+                    KeyCode::Down => "Down".to_string(),
+                    KeyCode::Left => "Left".to_string(),
+                    KeyCode::Right => "Right".to_string(),
+                    other => key_name(*other),
+                };
+                assert_eq!(parse_key(&name), Ok(*code), "{}", name);
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub mod event;
+pub mod keybindings;
 pub mod screens;
 pub mod terminal;
 pub mod views;
@@ -3,11 +3,11 @@ use crate::jvm::discovery::DiscoveredJvm;
 use crate::theme::Theme;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
 
 pub enum PickerItem {
-    SavedConnection(ConnectionProfile),
+    SavedConnection(Box<ConnectionProfile>),
     DiscoveredJvm(DiscoveredJvm),
 }
 
@@ -28,9 +28,17 @@ impl PickerItem {
     }
 }
 
+/// Outcome of the connection test run when a saved connection is picked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerStatus {
+    Testing(String),
+    Failed(String),
+}
+
 pub struct JvmPickerScreen {
     pub items: Vec<PickerItem>,
     pub list_state: ListState,
+    pub status: Option<PickerStatus>,
 }
 
 impl JvmPickerScreen {
@@ -38,7 +46,7 @@ impl JvmPickerScreen {
         let mut items: Vec<PickerItem> = Vec::new();
 
         for conn in saved_connections {
-            items.push(PickerItem::SavedConnection(conn));
+            items.push(PickerItem::SavedConnection(Box::new(conn)));
         }
 
         for jvm in jvms {
@@ -50,10 +58,15 @@ impl JvmPickerScreen {
             list_state.select(Some(0));
         }
 
-        Self { items, list_state }
+        Self {
+            items,
+            list_state,
+            status: None,
+        }
     }
 
     pub fn next(&mut self) {
+        self.status = None;
         if self.items.is_empty() {
             return;
         }
@@ -71,6 +84,7 @@ impl JvmPickerScreen {
     }
 
     pub fn previous(&mut self) {
+        self.status = None;
         if self.items.is_empty() {
             return;
         }
@@ -108,12 +122,18 @@ impl JvmPickerScreen {
     pub fn render(&mut self, frame: &mut Frame, theme: &Theme) {
         let area = frame.area();
 
+        // Errors can wrap or carry a hint on a second line
+        let controls_height = match self.status {
+            Some(PickerStatus::Failed(_)) => 6,
+            Some(PickerStatus::Testing(_)) => 4,
+            None => 3,
+        };
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(3),
                 Constraint::Min(0),
-                Constraint::Length(3),
+                Constraint::Length(controls_height),
             ])
             .split(area);
 
@@ -170,8 +190,26 @@ impl JvmPickerScreen {
             frame.render_stateful_widget(list, chunks[1], &mut self.list_state);
         }
 
-        let help = Paragraph::new("↑/k: Up | ↓/j: Down | Enter: Connect | r: Refresh | q: Quit")
+        let mut lines = Vec::new();
+        match &self.status {
+            Some(PickerStatus::Testing(name)) => lines.push(Line::from(Span::styled(
+                format!("Testing connection to {}...", name),
+                Style::default().fg(theme.info()),
+            ))),
+            Some(PickerStatus::Failed(error)) => lines.extend(error.lines().map(|line| {
+                Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(theme.error()),
+                ))
+            })),
+            None => {}
+        }
+        lines.push(Line::from(
+            "↑/k: Up | ↓/j: Down | Enter: Connect | r: Refresh | q: Quit",
+        ));
+        let help = Paragraph::new(lines)
             .style(Style::default().fg(theme.text_dim()))
+            .wrap(Wrap { trim: true })
             .block(Block::default().borders(Borders::ALL).title("Controls"));
 
         frame.render_widget(help, chunks[2]);
@@ -179,9 +217,52 @@ impl JvmPickerScreen {
 }
 
 fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
+    if s.chars().count() <= max_len {
+        return s.to_string();
+    }
+
+    // Cut on a char boundary so multi-byte paths can't panic
+    let keep = max_len.saturating_sub(3);
+    let end = s.char_indices().nth(keep).map_or(s.len(), |(idx, _)| idx);
+    format!("{}...", &s[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_moving_clears_connection_error() {
+        let jvm = DiscoveredJvm {
+            pid: 42,
+            main_class: "com.example.App".to_string(),
+        };
+        let mut picker = JvmPickerScreen::new(vec![jvm.clone(), jvm], Vec::new());
+        picker.status = Some(PickerStatus::Failed("connection refused".to_string()));
+
+        picker.next();
+        assert_eq!(picker.status, None);
+        assert_eq!(picker.list_state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_truncate_ascii() {
+        assert_eq!(truncate("com.example.App", 60), "com.example.App");
+        assert_eq!(truncate("abcdefghij", 8), "abcde...");
+    }
+
+    #[test]
+    fn test_truncate_multibyte_near_cutoff() {
+        let path = "/opt/apps/données/überservice.jar";
+        let truncated = truncate(path, 15);
+        assert_eq!(truncated, "/opt/apps/do...");
+        assert_eq!(truncated.chars().count(), 15);
+
+        // A byte slice at 15 would land inside 'é'
+        let truncated = truncate(path, 18);
+        assert_eq!(truncated, "/opt/apps/donné...");
+
+        let truncated = truncate("日本語のパス/アプリケーション.jar", 10);
+        assert_eq!(truncated, "日本語のパス/...");
     }
 }
@@ -1,13 +1,17 @@
 use crate::app::{App, AppMode, Tab};
+use crate::metrics::collector::PollStatus;
 use crate::metrics::store::MetricsStore;
 use crate::tui::views::{
     classes::ClassesView, gc::GcView, memory::MemoryView, overview::OverviewView,
     threads::ThreadsView,
 };
 use crate::tui::widgets::{
-    confirmation_dialog::ConfirmationDialog, error_screen::ErrorScreen,
+    alert_history::AlertHistory, column_menu::ColumnMenu, command_output::CommandOutput,
+    command_prompt::CommandPrompt, confirmation_dialog::ConfirmationDialog,
+    error_screen::ErrorScreen, event_log::EventLogPane,
     format_selector_dialog::FormatSelectorDialog, help_overlay::HelpOverlay,
-    loading_screen::LoadingScreen, search_bar::SearchBar,
+    loading_screen::LoadingScreen, process_info::ProcessInfoPopup, search_bar::SearchBar,
+    system_properties::SystemPropertiesPanel, toast::ToastStack,
 };
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -15,28 +19,62 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Tabs},
 };
 
+/// Terminals this short get the compact layout even when it's switched off.
+const COMPACT_MAX_HEIGHT: u16 = 24;
+
+/// Height of the 'L' event log pane, borders included.
+const EVENT_LOG_HEIGHT: u16 = 8;
+
 pub struct MonitoringScreen;
 
 impl MonitoringScreen {
     pub fn render(frame: &mut Frame, app: &App, store: &MetricsStore) {
-        let chunks = Layout::default()
-            .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Length(3),
-                Constraint::Min(0),
-                Constraint::Length(3),
-            ])
-            .split(frame.area());
+        let compact = app.compact || frame.area().height <= COMPACT_MAX_HEIGHT;
+        if compact {
+            Self::render_compact(frame, app, store);
+        } else {
+            let alert_height = if app.active_alerts.is_empty() {
+                0
+            } else {
+                app.active_alerts.len() as u16 + 2
+            };
 
-        Self::render_header(frame, chunks[0], app);
-        Self::render_tabs(frame, chunks[1], app);
-        Self::render_content(frame, chunks[2], app, store);
-        Self::render_footer(frame, chunks[3], app);
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(3),
+                    Constraint::Length(alert_height),
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                    Constraint::Length(3),
+                ])
+                .split(frame.area());
+
+            Self::render_header(frame, chunks[0], app);
+            if !app.active_alerts.is_empty() {
+                Self::render_alerts(frame, chunks[1], app);
+            }
+            Self::render_tabs(frame, chunks[2], app);
+            Self::render_content(frame, chunks[3], app, store);
+            Self::render_footer(frame, chunks[4], app);
+        }
 
         match &app.mode {
             AppMode::Help => {
-                HelpOverlay::render(frame, frame.area(), &app.theme);
+                HelpOverlay::render(frame, frame.area(), &app.keymap, &app.theme);
+            }
+            AppMode::ConfirmGc if app.confirm_destructive => {
+                ConfirmationDialog::render_with_prompt(
+                    frame,
+                    frame.area(),
+                    "Trigger Garbage Collection",
+                    &format!(
+                        "This may pause the JVM briefly.\n\nType gc to confirm: {}_",
+                        app.gc_confirmation_input
+                    ),
+                    "Enter: Confirm | Esc: Cancel",
+                    &app.theme,
+                );
             }
             AppMode::ConfirmGc => {
                 ConfirmationDialog::render(
@@ -47,6 +85,17 @@ impl MonitoringScreen {
                     &app.theme,
                 );
             }
+            AppMode::ConfirmHeapDump => {
+                if let Some(ref plan) = app.heap_dump_plan {
+                    ConfirmationDialog::render(
+                        frame,
+                        frame.area(),
+                        "Heap Dump",
+                        &plan.confirmation_message(),
+                        &app.theme,
+                    );
+                }
+            }
             AppMode::SelectExportFormat => {
                 FormatSelectorDialog::render(
                     frame,
@@ -65,15 +114,6 @@ impl MonitoringScreen {
                 };
                 ConfirmationDialog::render(frame, frame.area(), "Export Data", message, &app.theme);
             }
-            AppMode::ExportSuccess(path) => {
-                ConfirmationDialog::render(
-                    frame,
-                    frame.area(),
-                    "Export Successful",
-                    &format!("Data exported to:\n\n{}\n\nPress Enter to continue", path),
-                    &app.theme,
-                );
-            }
             AppMode::Error(message) => {
                 ErrorScreen::render(frame, frame.area(), message, &app.theme);
             }
@@ -90,11 +130,147 @@ impl MonitoringScreen {
                     &app.theme,
                 );
             }
+            AppMode::ShellPrompt => {
+                CommandPrompt::render(
+                    frame,
+                    frame.area(),
+                    "!",
+                    &app.shell_command,
+                    " Shell command | Enter: run (empty for a shell) | Esc: cancel ",
+                    &app.theme,
+                );
+            }
+            AppMode::Command => {
+                CommandPrompt::render(
+                    frame,
+                    frame.area(),
+                    ":",
+                    &app.diagnostic_command,
+                    " jcmd command, e.g. Thread.print -l | Enter: run | Esc: cancel ",
+                    &app.theme,
+                );
+            }
+            AppMode::CommandOutput => {
+                CommandOutput::render(
+                    frame,
+                    frame.area(),
+                    &format!("jcmd {}", app.diagnostic_command),
+                    &app.diagnostic_output,
+                    app.diagnostic_scroll,
+                    &app.theme,
+                );
+            }
+            AppMode::RawOutput => {
+                CommandOutput::render(
+                    frame,
+                    frame.area(),
+                    "Raw tool output",
+                    &app.diagnostic_output,
+                    app.diagnostic_scroll,
+                    &app.theme,
+                );
+            }
+            AppMode::ProcessInfo => {
+                if let Some(ref info) = app.process_info {
+                    ProcessInfoPopup::render(
+                        frame,
+                        frame.area(),
+                        app.jvm_info.as_ref(),
+                        info,
+                        &app.theme,
+                    );
+                }
+            }
+            AppMode::SystemProperties => {
+                SystemPropertiesPanel::render(
+                    frame,
+                    frame.area(),
+                    &app.filtered_system_properties(),
+                    app.system_properties.len(),
+                    &app.system_properties_filter,
+                    app.system_properties_scroll,
+                    &app.theme,
+                );
+            }
+            AppMode::ColumnMenu => {
+                ColumnMenu::render(
+                    frame,
+                    frame.area(),
+                    &app.column_menu_items(),
+                    app.column_menu_index,
+                    &app.theme,
+                );
+            }
+            AppMode::AlertHistory => {
+                AlertHistory::render(
+                    frame,
+                    frame.area(),
+                    &app.alert_history,
+                    &app.alert_counts(),
+                    app.alert_history_scroll,
+                    &app.theme,
+                );
+            }
             AppMode::Normal => {}
         }
+
+        // Toasts sit above the footer so the key hints stay readable
+        let footer_height = if compact { 1 } else { 3 };
+        let above_footer = Rect {
+            height: frame.area().height.saturating_sub(footer_height),
+            ..frame.area()
+        };
+        ToastStack::render(frame, above_footer, &app.toasts, &app.theme);
     }
 
-    fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+    /// Header and tabs share one borderless line, alerts and controls take a
+    /// line each, and everything else goes to the view.
+    fn render_compact(frame: &mut Frame, app: &App, store: &MetricsStore) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(app.active_alerts.len() as u16),
+                Constraint::Min(0),
+                Constraint::Length(1),
+            ])
+            .split(frame.area());
+
+        let mut spans = Self::tab_titles(app)
+            .into_iter()
+            .flat_map(|title| [Span::raw(" ")].into_iter().chain(title.spans))
+            .collect::<Vec<_>>();
+        spans.push(Span::styled(
+            " │ ",
+            Style::default().fg(app.theme.text_dim()),
+        ));
+        spans.extend(
+            Self::header_spans(app)
+                .into_iter()
+                .map(|span| span.style(Style::default().fg(app.theme.primary()))),
+        );
+        frame.render_widget(Paragraph::new(Line::from(spans)), chunks[0]);
+
+        if !app.active_alerts.is_empty() {
+            frame.render_widget(
+                Paragraph::new(Self::alert_lines(app)).style(
+                    Style::default()
+                        .fg(app.theme.error())
+                        .add_modifier(Modifier::BOLD),
+                ),
+                chunks[1],
+            );
+        }
+
+        Self::render_content(frame, chunks[2], app, store);
+
+        frame.render_widget(
+            Paragraph::new(Self::footer_line(app)).style(Style::default().fg(app.theme.text_dim())),
+            chunks[3],
+        );
+    }
+
+    fn header_spans(app: &App) -> Vec<Span<'static>> {
         let header_text = if let Some(jvm_info) = &app.jvm_info {
             format!(
                 "PID: {} │ JDK {} │ Uptime: {}h {}m",
@@ -107,7 +283,43 @@ impl MonitoringScreen {
             "Loading JVM info...".to_string()
         };
 
-        let header = Paragraph::new(header_text)
+        let mut spans = vec![Span::raw(header_text)];
+        if let (Some(label), Some(status)) = (&app.connection_label, app.poll_status) {
+            spans.push(Span::raw(" │ "));
+            spans.push(Self::latency_span(label, status, app));
+        }
+        if let Some(warning) = &app.warning {
+            spans.push(Span::raw(" │ "));
+            spans.push(Span::styled(
+                format!("⚠ {}", warning),
+                Style::default().fg(app.theme.warning()),
+            ));
+        }
+        spans
+    }
+
+    /// Green while a poll fits comfortably in the interval, amber past half
+    /// of it and red once polling can't keep up or fails.
+    fn latency_span(label: &str, status: PollStatus, app: &App) -> Span<'static> {
+        let (text, color) = if !status.ok {
+            (format!("{} · failed", label), app.theme.error())
+        } else {
+            let text = format!("{} · {}ms", label, status.latency.as_millis());
+            let load = status.load(app.poll_interval);
+            let color = if load >= 1.0 {
+                app.theme.error()
+            } else if load >= 0.5 {
+                app.theme.warning()
+            } else {
+                app.theme.success()
+            };
+            (text, color)
+        };
+        Span::styled(text, Style::default().fg(color))
+    }
+
+    fn render_header(frame: &mut Frame, area: Rect, app: &App) {
+        let header = Paragraph::new(Line::from(Self::header_spans(app)))
             .style(
                 Style::default()
                     .fg(app.theme.primary())
@@ -118,8 +330,32 @@ impl MonitoringScreen {
         frame.render_widget(header, area);
     }
 
-    fn render_tabs(frame: &mut Frame, area: Rect, app: &App) {
-        let titles: Vec<Line> = Tab::all()
+    fn alert_lines(app: &App) -> Vec<Line<'static>> {
+        app.active_alerts
+            .iter()
+            .map(|alert| Line::from(format!("🔔 {}", alert)))
+            .collect()
+    }
+
+    fn render_alerts(frame: &mut Frame, area: Rect, app: &App) {
+        let banner = Paragraph::new(Self::alert_lines(app))
+            .style(
+                Style::default()
+                    .fg(app.theme.error())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(app.theme.error()))
+                    .title("ALERT"),
+            );
+
+        frame.render_widget(banner, area);
+    }
+
+    fn tab_titles(app: &App) -> Vec<Line<'static>> {
+        Tab::all()
             .iter()
             .enumerate()
             .map(|(i, tab)| {
@@ -135,9 +371,11 @@ impl MonitoringScreen {
                     Line::from(title).style(Style::default().fg(app.theme.text_dim()))
                 }
             })
-            .collect();
+            .collect()
+    }
 
-        let tabs = Tabs::new(titles)
+    fn render_tabs(frame: &mut Frame, area: Rect, app: &App) {
+        let tabs = Tabs::new(Self::tab_titles(app))
             .block(Block::default().borders(Borders::ALL).title("Views"))
             .divider(" ");
 
@@ -145,48 +383,97 @@ impl MonitoringScreen {
     }
 
     fn render_content(frame: &mut Frame, area: Rect, app: &App, store: &MetricsStore) {
+        if !app.show_event_log {
+            Self::render_view(frame, area, app, store);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(EVENT_LOG_HEIGHT)])
+            .split(area);
+        Self::render_view(frame, chunks[0], app, store);
+        EventLogPane::render(frame, chunks[1], &app.event_log, &app.theme);
+    }
+
+    fn render_view(frame: &mut Frame, area: Rect, app: &App, store: &MetricsStore) {
         match app.current_tab {
             Tab::Overview => {
-                OverviewView::render(frame, area, store, &app.theme);
+                OverviewView::render(
+                    frame,
+                    area,
+                    store,
+                    app.jvm_info.as_ref(),
+                    app.scroll_offset,
+                    &app.theme,
+                );
             }
             Tab::Memory => {
                 MemoryView::render(frame, area, store, &app.theme);
             }
             Tab::Threads => {
-                ThreadsView::render_with_scroll(frame, area, store, app.scroll_offset, &app.theme);
+                ThreadsView::render_with_scroll(
+                    frame,
+                    area,
+                    store,
+                    app.scroll_offset,
+                    app.thread_sort,
+                    app.thread_limit(),
+                    &app.visible_thread_columns,
+                    &app.theme,
+                );
             }
             Tab::GC => {
-                GcView::render(frame, area, store, &app.theme);
+                GcView::render(frame, area, store, &app.gc_timeline, &app.theme);
             }
             Tab::Classes => {
-                ClassesView::render_with_scroll(frame, area, store, app.scroll_offset, &app.theme);
+                if let Some(baseline) = &app.class_baseline {
+                    ClassesView::render_diff(
+                        frame,
+                        area,
+                        store,
+                        baseline,
+                        app.scroll_offset,
+                        &app.class_filter,
+                        &app.theme,
+                    );
+                    return;
+                }
+                ClassesView::render_with_scroll(
+                    frame,
+                    area,
+                    store,
+                    app.scroll_offset,
+                    &app.class_filter,
+                    app.class_sort,
+                    &app.visible_class_columns,
+                    &app.theme,
+                );
             }
         }
     }
 
     fn render_footer(frame: &mut Frame, area: Rect, app: &App) {
-        let footer_text = match app.current_tab {
-            Tab::Overview => {
-                "1-5: Switch Tab | h/l/←/→: Prev/Next | g: Trigger GC | r: Reset | ?: Help | q: Quit"
-            }
-            Tab::Memory => {
-                "1-5: Switch Tab | h/l/←/→: Prev/Next | g: Trigger GC | r: Reset | ?: Help | q: Quit"
-            }
-            Tab::Threads => {
-                "1-5: Switch Tab | j/k/↑/↓: Scroll | g: Trigger GC | r: Reset | ?: Help | q: Quit"
-            }
-            Tab::GC => {
-                "1-5: Switch Tab | h/l/←/→: Prev/Next | g: Trigger GC | r: Reset | ?: Help | q: Quit"
-            }
-            Tab::Classes => {
-                "1-5: Switch Tab | j/k/↑/↓: Scroll | g: Trigger GC | r: Reset | ?: Help | q: Quit"
-            }
-        };
-
-        let footer = Paragraph::new(footer_text)
+        let footer = Paragraph::new(Self::footer_line(app))
             .style(Style::default().fg(app.theme.text_dim()))
             .block(Block::default().borders(Borders::ALL).title("Controls"));
 
         frame.render_widget(footer, area);
     }
+
+    /// The poll interval and the keys for the current tab.
+    fn footer_line(app: &App) -> String {
+        // +/- zooms the timeline on the GC tab instead of changing the interval
+        let interval_keys = if app.current_tab == Tab::GC {
+            ""
+        } else {
+            " (+/-)"
+        };
+        format!(
+            "Every {}{} | {}",
+            humantime::format_duration(app.poll_interval),
+            interval_keys,
+            app.keymap.footer_hints(app.current_tab).join(" | ")
+        )
+    }
 }
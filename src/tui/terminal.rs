@@ -4,7 +4,8 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
-use std::io::{self, Stdout};
+use std::io::{self, BufRead, Stdout, Write};
+use std::process::{Command, ExitStatus};
 
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
@@ -23,3 +24,62 @@ pub fn restore_terminal(terminal: &mut Tui) -> Result<()> {
     terminal.show_cursor()?;
     Ok(())
 }
+
+/// Rings the terminal bell.
+pub fn beep() -> Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(b"\x07")?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Leaves the TUI, runs `command` through the user's shell (or starts an
+/// interactive shell when it is empty), then restores the TUI. Rendering is
+/// paused for the duration since this blocks the caller.
+pub fn suspend_and_run(
+    terminal: &mut Tui,
+    command: &str,
+    env: &[(String, String)],
+) -> Result<ExitStatus> {
+    restore_terminal(terminal)?;
+
+    let result = run_in_shell(command, env);
+
+    if !command.trim().is_empty() {
+        match &result {
+            Ok(status) => println!("\n[{}] Press Enter to return to jvm-tui...", status),
+            Err(e) => println!("\n[failed: {}] Press Enter to return to jvm-tui...", e),
+        }
+        io::stdout().flush()?;
+        let mut line = String::new();
+        io::stdin().lock().read_line(&mut line)?;
+    }
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    result
+}
+
+fn run_in_shell(command: &str, env: &[(String, String)]) -> Result<ExitStatus> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        if !command.trim().is_empty() {
+            cmd.args(["/C", command]);
+        }
+        cmd
+    } else {
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "sh".to_string());
+        let mut cmd = Command::new(shell);
+        if !command.trim().is_empty() {
+            cmd.args(["-c", command]);
+        }
+        cmd
+    };
+
+    let status = cmd
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .status()?;
+    Ok(status)
+}
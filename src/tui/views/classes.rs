@@ -1,16 +1,40 @@
+use crate::app::ClassSort;
+use crate::config::ClassColumn;
+use crate::jvm::types::ClassInfo;
 use crate::metrics::store::MetricsStore;
 use crate::theme::Theme;
+use crate::tui::views::{clamp_scroll, render_scrollbar, table_rows, window_label};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
     widgets::{Block, Borders, Cell, Paragraph, Row, Table},
 };
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+
+/// Change in one class between the baseline histogram and the current one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassDelta {
+    pub name: String,
+    pub instances: u64,
+    pub instances_delta: i64,
+    pub bytes_delta: i64,
+}
 
 pub struct ClassesView;
 
 impl ClassesView {
     pub fn render(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        Self::render_with_scroll(frame, area, store, 0, theme);
+        Self::render_with_scroll(
+            frame,
+            area,
+            store,
+            0,
+            "",
+            ClassSort::Bytes,
+            &ClassColumn::all(),
+            theme,
+        );
     }
 
     pub fn render_with_scroll(
@@ -18,6 +42,9 @@ impl ClassesView {
         area: Rect,
         store: &MetricsStore,
         scroll: usize,
+        filter: &str,
+        sort: ClassSort,
+        columns: &[ClassColumn],
         theme: &Theme,
     ) {
         let chunks = Layout::default()
@@ -25,25 +52,265 @@ impl ClassesView {
             .constraints([Constraint::Length(7), Constraint::Min(0)])
             .split(area);
 
-        Self::render_summary(frame, chunks[0], store, theme);
-        Self::render_class_list(frame, chunks[1], store, scroll, theme);
+        let classes = Self::filter_classes(store, filter);
+
+        let filtered = !filter.is_empty();
+        let mut ranked: Vec<(usize, &ClassInfo)> = classes
+            .iter()
+            .enumerate()
+            .map(|(idx, class)| {
+                let rank = if filtered {
+                    idx + 1
+                } else {
+                    class.rank as usize
+                };
+                (rank, *class)
+            })
+            .collect();
+        Self::sort_classes(&mut ranked, sort);
+
+        let title = if filtered {
+            format!("Classes matching '{}' by {}", filter, sort.display_name())
+        } else {
+            format!("Classes by {}", sort.display_name())
+        };
+
+        Self::render_summary(frame, chunks[0], &classes, filter, theme);
+        Self::render_class_list(
+            frame, chunks[1], store, &ranked, scroll, &title, columns, theme,
+        );
+    }
+
+    /// Filters the histogram by class name. The pattern matches either as a
+    /// case-insensitive substring, a regex, or a glob (`*`/`?` wildcards).
+    pub fn filter_classes<'a>(store: &'a MetricsStore, pattern: &str) -> Vec<&'a ClassInfo> {
+        if pattern.is_empty() {
+            return store.class_histogram.iter().collect();
+        }
+
+        let pattern_lower = pattern.to_lowercase();
+        let matcher = build_matcher(pattern);
+
+        store
+            .class_histogram
+            .iter()
+            .filter(|class| class_matches(class, &pattern_lower, matcher.as_ref()))
+            .collect()
+    }
+
+    /// Positions in the `sort`-ordered histogram of classes matching `query`,
+    /// using the same matching rules as `filter_classes`.
+    pub fn search_classes(store: &MetricsStore, sort: ClassSort, query: &str) -> Vec<usize> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let query_lower = query.to_lowercase();
+        let matcher = build_matcher(query);
+
+        let mut rows: Vec<(usize, &ClassInfo)> = store
+            .class_histogram
+            .iter()
+            .map(|class| (class.rank as usize, class))
+            .collect();
+        Self::sort_classes(&mut rows, sort);
+
+        rows.iter()
+            .enumerate()
+            .filter(|(_, (_, class))| class_matches(class, &query_lower, matcher.as_ref()))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Diff mode: per-class growth since `baseline`, largest byte growth first.
+    pub fn render_diff(
+        frame: &mut Frame,
+        area: Rect,
+        store: &MetricsStore,
+        baseline: &[ClassInfo],
+        scroll: usize,
+        filter: &str,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(7), Constraint::Min(0)])
+            .split(area);
+
+        let deltas: Vec<ClassDelta> = Self::diff(baseline, &store.class_histogram)
+            .into_iter()
+            .filter(|delta| {
+                filter.is_empty() || delta.name.to_lowercase().contains(&filter.to_lowercase())
+            })
+            .collect();
+
+        let grown = deltas.iter().filter(|d| d.bytes_delta > 0).count();
+        let shrunk = deltas.iter().filter(|d| d.bytes_delta < 0).count();
+        let net_bytes: i64 = deltas.iter().map(|d| d.bytes_delta).sum();
+        let net_instances: i64 = deltas.iter().map(|d| d.instances_delta).sum();
+
+        let summary_text = format!(
+            "Baseline: {} classes\n\
+             Grown: {}   Shrunk: {}\n\
+             Net Instances: {:+}\n\
+             Net Memory: {:+.2} MB\n\
+             Press 'b' to drop the baseline",
+            baseline.len(),
+            grown,
+            shrunk,
+            net_instances,
+            net_bytes as f64 / 1024.0 / 1024.0,
+        );
+
+        let summary = Paragraph::new(summary_text)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Class Growth Since Baseline"),
+            )
+            .style(Style::default().fg(theme.text()));
+        frame.render_widget(summary, chunks[0]);
+
+        let header = Row::new(
+            ["Instances", "Δ Instances", "Δ Bytes", "Δ MB", "Class Name"]
+                .into_iter()
+                .map(|title| Cell::from(title).style(Style::default().fg(theme.highlight())))
+                .collect::<Vec<_>>(),
+        )
+        .height(1);
+
+        let scroll = clamp_scroll(scroll, deltas.len());
+        let shown = table_rows(chunks[1]).min(deltas.len().saturating_sub(scroll));
+        let rows: Vec<Row> = deltas
+            .iter()
+            .skip(scroll)
+            .take(shown)
+            .map(|delta| {
+                let color = if delta.bytes_delta > 0 {
+                    theme.memory_high()
+                } else if delta.bytes_delta < 0 {
+                    theme.success()
+                } else {
+                    theme.text_dim()
+                };
+
+                Row::new(vec![
+                    Cell::from(delta.instances.to_string()),
+                    Cell::from(format!("{:+}", delta.instances_delta)),
+                    Cell::from(format!("{:+}", delta.bytes_delta)),
+                    Cell::from(format!(
+                        "{:+.2}",
+                        delta.bytes_delta as f64 / 1024.0 / 1024.0
+                    ))
+                    .style(Style::default().fg(color)),
+                    Cell::from(delta.name.clone()),
+                ])
+            })
+            .collect();
+
+        let widths = [
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Length(10),
+            Constraint::Percentage(60),
+        ];
+
+        let window = window_label(scroll, shown, deltas.len());
+        let title = if filter.is_empty() {
+            format!("Class Growth, largest first ({})", window)
+        } else {
+            format!(
+                "Class Growth matching '{}', largest first ({})",
+                filter, window
+            )
+        };
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .style(Style::default().fg(theme.text()));
+
+        frame.render_widget(table, chunks[1]);
+        render_scrollbar(frame, chunks[1], scroll, deltas.len(), theme);
+    }
+
+    /// Deltas by class name between two histograms, sorted by byte growth
+    /// (largest first). Classes missing from one side count as zero there, so
+    /// new classes show their full size and vanished ones go negative.
+    pub fn diff(baseline: &[ClassInfo], current: &[ClassInfo]) -> Vec<ClassDelta> {
+        let before: HashMap<&str, &ClassInfo> =
+            baseline.iter().map(|c| (c.name.as_str(), c)).collect();
+
+        let mut deltas: Vec<ClassDelta> = current
+            .iter()
+            .map(|class| {
+                let (instances, bytes) = before
+                    .get(class.name.as_str())
+                    .map(|b| (b.instances, b.bytes))
+                    .unwrap_or((0, 0));
+                ClassDelta {
+                    name: class.name.clone(),
+                    instances: class.instances,
+                    instances_delta: class.instances as i64 - instances as i64,
+                    bytes_delta: class.bytes as i64 - bytes as i64,
+                }
+            })
+            .collect();
+
+        let present: HashSet<&str> = current.iter().map(|c| c.name.as_str()).collect();
+        deltas.extend(
+            baseline
+                .iter()
+                .filter(|class| !present.contains(class.name.as_str()))
+                .map(|class| ClassDelta {
+                    name: class.name.clone(),
+                    instances: 0,
+                    instances_delta: -(class.instances as i64),
+                    bytes_delta: -(class.bytes as i64),
+                }),
+        );
+
+        deltas.sort_by_key(|delta| std::cmp::Reverse(delta.bytes_delta));
+        deltas
     }
 
-    fn render_summary(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let classes = &store.class_histogram;
+    /// Sorts `(rank, class)` rows in place. The sort is stable, so rows with
+    /// equal keys keep their histogram order.
+    pub fn sort_classes(rows: &mut [(usize, &ClassInfo)], sort: ClassSort) {
+        match sort {
+            ClassSort::Bytes => rows.sort_by_key(|row| std::cmp::Reverse(row.1.bytes)),
+            ClassSort::Instances => rows.sort_by_key(|row| std::cmp::Reverse(row.1.instances)),
+            ClassSort::Name => rows.sort_by(|a, b| a.1.name.cmp(&b.1.name)),
+        }
+    }
 
+    fn render_summary(
+        frame: &mut Frame,
+        area: Rect,
+        classes: &[&ClassInfo],
+        filter: &str,
+        theme: &Theme,
+    ) {
         let total_instances: u64 = classes.iter().map(|c| c.instances).sum();
         let total_bytes: u64 = classes.iter().map(|c| c.bytes).sum();
 
+        let footer = if filter.is_empty() {
+            "Showing top memory consumers...".to_string()
+        } else {
+            format!("Filter: {}", filter)
+        };
+
         let summary_text = format!(
             "Total Classes: {}\n\
              Total Instances: {}\n\
              Total Memory: {:.2} MB\n\
              \n\
-             Showing top memory consumers...",
+             {}",
             classes.len(),
             total_instances,
-            total_bytes as f64 / 1024.0 / 1024.0
+            total_bytes as f64 / 1024.0 / 1024.0,
+            footer
         );
 
         let summary = Paragraph::new(summary_text)
@@ -61,12 +328,13 @@ impl ClassesView {
         frame: &mut Frame,
         area: Rect,
         store: &MetricsStore,
+        ranked: &[(usize, &ClassInfo)],
         scroll: usize,
+        title: &str,
+        columns: &[ClassColumn],
         theme: &Theme,
     ) {
-        let classes = &store.class_histogram;
-
-        if classes.is_empty() {
+        if store.class_histogram.is_empty() {
             let placeholder = Paragraph::new(
                 "No class histogram data available.\n\n\
                  Class histogram collection is expensive and runs less frequently.\n\
@@ -79,20 +347,23 @@ impl ClassesView {
             return;
         }
 
-        let header = Row::new(vec![
-            Cell::from("Rank").style(Style::default().fg(theme.highlight())),
-            Cell::from("Instances").style(Style::default().fg(theme.highlight())),
-            Cell::from("Bytes").style(Style::default().fg(theme.highlight())),
-            Cell::from("MB").style(Style::default().fg(theme.highlight())),
-            Cell::from("Class Name").style(Style::default().fg(theme.highlight())),
-        ])
+        let header = Row::new(
+            columns
+                .iter()
+                .map(|column| {
+                    Cell::from(column.title()).style(Style::default().fg(theme.highlight()))
+                })
+                .collect::<Vec<_>>(),
+        )
         .height(1);
 
-        let rows: Vec<Row> = classes
+        let scroll = clamp_scroll(scroll, ranked.len());
+        let shown = table_rows(area).min(ranked.len().saturating_sub(scroll));
+        let rows: Vec<Row> = ranked
             .iter()
             .skip(scroll)
-            .take(100)
-            .map(|class| {
+            .take(shown)
+            .map(|(rank, class)| {
                 let mb = class.bytes as f64 / 1024.0 / 1024.0;
                 let color = if mb > 50.0 {
                     theme.memory_critical()
@@ -102,34 +373,218 @@ impl ClassesView {
                     theme.text()
                 };
 
-                Row::new(vec![
-                    Cell::from(class.rank.to_string()),
-                    Cell::from(class.instances.to_string()),
-                    Cell::from(class.bytes.to_string()),
-                    Cell::from(format!("{:.2}", mb)).style(Style::default().fg(color)),
-                    Cell::from(class.name.clone()),
-                ])
+                Row::new(
+                    columns
+                        .iter()
+                        .map(|column| match column {
+                            ClassColumn::Rank => Cell::from(rank.to_string()),
+                            ClassColumn::Instances => Cell::from(class.instances.to_string()),
+                            ClassColumn::Bytes => Cell::from(class.bytes.to_string()),
+                            ClassColumn::Megabytes => {
+                                Cell::from(format!("{:.2}", mb)).style(Style::default().fg(color))
+                            }
+                            ClassColumn::Name => Cell::from(class.name.clone()),
+                        })
+                        .collect::<Vec<_>>(),
+                )
             })
             .collect();
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(6),
-                Constraint::Length(12),
-                Constraint::Length(12),
-                Constraint::Length(8),
-                Constraint::Percentage(60),
-            ],
-        )
-        .header(header)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Top 100 Classes by Memory Usage"),
-        )
-        .style(Style::default().fg(theme.text()));
+        let widths: Vec<Constraint> = columns
+            .iter()
+            .map(|column| match column {
+                ClassColumn::Rank => Constraint::Length(6),
+                ClassColumn::Instances => Constraint::Length(12),
+                ClassColumn::Bytes => Constraint::Length(12),
+                ClassColumn::Megabytes => Constraint::Length(8),
+                ClassColumn::Name => Constraint::Percentage(60),
+            })
+            .collect();
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "{} ({})",
+                title,
+                window_label(scroll, shown, ranked.len())
+            )))
+            .style(Style::default().fg(theme.text()));
 
         frame.render_widget(table, area);
+        render_scrollbar(frame, area, scroll, ranked.len(), theme);
+    }
+}
+
+fn class_matches(class: &ClassInfo, pattern_lower: &str, matcher: Option<&Regex>) -> bool {
+    class.name.to_lowercase().contains(pattern_lower)
+        || matcher.is_some_and(|re| re.is_match(&class.name))
+}
+
+fn build_matcher(pattern: &str) -> Option<Regex> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .ok()
+        .or_else(|| {
+            RegexBuilder::new(&glob_to_regex(pattern))
+                .case_insensitive(true)
+                .build()
+                .ok()
+        })
+}
+
+/// Globs match the whole class name, so `*Service` skips `OrderService$Cache`.
+fn glob_to_regex(pattern: &str) -> String {
+    let body = regex::escape(pattern)
+        .replace(r"\*", ".*")
+        .replace(r"\?", ".");
+    format!("^{}$", body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(rank: u32, bytes: u64, name: &str) -> ClassInfo {
+        ClassInfo {
+            rank,
+            instances: bytes / 16,
+            bytes,
+            name: name.to_string(),
+        }
+    }
+
+    fn sample_store() -> MetricsStore {
+        let mut store = MetricsStore::new(10);
+        store.record_class_histogram(vec![
+            class(1, 4096, "[B"),
+            class(2, 2048, "java.lang.String"),
+            class(3, 1024, "com.example.OrderService"),
+            class(4, 512, "com.example.OrderService$Cache"),
+            class(5, 256, "com.example.billing.InvoiceService"),
+        ]);
+        store
+    }
+
+    #[test]
+    fn test_sort_classes_is_stable() {
+        let mut store = sample_store();
+        store.class_histogram[1].instances = store.class_histogram[0].instances;
+        let mut rows: Vec<(usize, &ClassInfo)> = store
+            .class_histogram
+            .iter()
+            .map(|c| (c.rank as usize, c))
+            .collect();
+
+        ClassesView::sort_classes(&mut rows, ClassSort::Instances);
+        let ranks: Vec<usize> = rows.iter().map(|(rank, _)| *rank).collect();
+        assert_eq!(ranks, vec![1, 2, 3, 4, 5]);
+
+        ClassesView::sort_classes(&mut rows, ClassSort::Name);
+        let names: Vec<&str> = rows.iter().map(|(_, c)| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "[B",
+                "com.example.OrderService",
+                "com.example.OrderService$Cache",
+                "com.example.billing.InvoiceService",
+                "java.lang.String"
+            ]
+        );
+
+        ClassesView::sort_classes(&mut rows, ClassSort::Bytes);
+        let ranks: Vec<usize> = rows.iter().map(|(rank, _)| *rank).collect();
+        assert_eq!(ranks, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_search_classes_follows_sort_order() {
+        let store = sample_store();
+
+        assert_eq!(
+            ClassesView::search_classes(&store, ClassSort::Bytes, "service"),
+            vec![2, 3, 4]
+        );
+        // By name: [B, OrderService, OrderService$Cache, InvoiceService, String
+        assert_eq!(
+            ClassesView::search_classes(&store, ClassSort::Name, "*Service"),
+            vec![1, 3]
+        );
+        assert!(ClassesView::search_classes(&store, ClassSort::Bytes, "").is_empty());
+        assert!(ClassesView::search_classes(&store, ClassSort::Bytes, "nope").is_empty());
+    }
+
+    #[test]
+    fn test_diff_added_removed_and_grown() {
+        let baseline = vec![
+            class(1, 4096, "[B"),
+            class(2, 2048, "java.lang.String"),
+            class(3, 1024, "com.example.Gone"),
+        ];
+        let current = vec![
+            class(1, 8192, "[B"),
+            class(2, 2048, "java.lang.String"),
+            class(3, 512, "com.example.New"),
+        ];
+
+        let deltas = ClassesView::diff(&baseline, &current);
+        let names: Vec<&str> = deltas.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "[B",
+                "com.example.New",
+                "java.lang.String",
+                "com.example.Gone"
+            ]
+        );
+
+        assert_eq!(deltas[0].bytes_delta, 4096);
+        assert_eq!(deltas[0].instances_delta, 256);
+        assert_eq!(deltas[1].bytes_delta, 512);
+        assert_eq!(deltas[1].instances, 32);
+        assert_eq!(deltas[2].bytes_delta, 0);
+        assert_eq!(deltas[3].bytes_delta, -1024);
+        assert_eq!(deltas[3].instances, 0);
+    }
+
+    #[test]
+    fn test_filter_classes_empty_pattern_returns_all() {
+        let store = sample_store();
+        assert_eq!(ClassesView::filter_classes(&store, "").len(), 5);
+    }
+
+    #[test]
+    fn test_filter_classes_substring_is_case_insensitive() {
+        let store = sample_store();
+        let matches = ClassesView::filter_classes(&store, "service$cache");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].name, "com.example.OrderService$Cache");
+    }
+
+    #[test]
+    fn test_filter_classes_regex() {
+        let store = sample_store();
+        let matches = ClassesView::filter_classes(&store, r"^com\.example\.[A-Z]\w+Service$");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].rank, 3);
+    }
+
+    #[test]
+    fn test_filter_classes_glob() {
+        let store = sample_store();
+        let matches = ClassesView::filter_classes(&store, "*Service");
+        let names: Vec<&str> = matches.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "com.example.OrderService",
+                "com.example.billing.InvoiceService"
+            ]
+        );
+
+        let matches = ClassesView::filter_classes(&store, "*.Order*");
+        assert_eq!(matches.len(), 2);
     }
 }
@@ -1,105 +1,206 @@
-use crate::jvm::types::GcStats;
-use crate::metrics::store::MetricsStore;
+use crate::app::TimelineWindow;
+use crate::jvm::types::{GcStats, HumongousStats};
+use crate::metrics::store::{MetricsStore, GC_PAUSE_BUCKETS_MS};
 use crate::theme::Theme;
+use crate::tui::views::{collecting_placeholder, time_axis_labels, MIN_CHART_SAMPLES};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Paragraph},
+    widgets::{
+        Axis, BarChart, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table,
+    },
 };
 
+/// Causes listed in the "Top GC Causes" box; the rest are left out.
+const MAX_CAUSES: usize = 5;
+
 pub struct GcView;
 
 impl GcView {
-    pub fn render(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        store: &MetricsStore,
+        timeline: &TimelineWindow,
+        theme: &Theme,
+    ) {
+        // One summary line per collector beyond the young/full pair
+        let collectors = store
+            .gc_history
+            .latest()
+            .map_or(0, |gc| gc.collectors.len());
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(8),
+                Constraint::Length((collectors as u16 + 7).max(9)),
                 Constraint::Length(12),
                 Constraint::Min(0),
             ])
             .split(area);
 
         Self::render_gc_summary(frame, chunks[0], store, theme);
-        Self::render_gc_timeline(frame, chunks[1], store, theme);
-        Self::render_gc_stats(frame, chunks[2], store, theme);
+        let charts = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(chunks[1]);
+        Self::render_gc_timeline(frame, charts[0], store, timeline, theme);
+        Self::render_gc_time_chart(frame, charts[1], store, timeline, theme);
+
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[2]);
+
+        let causes = store.gc_cause_counts();
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(causes.len().clamp(1, MAX_CAUSES) as u16 + 2),
+            ])
+            .split(bottom[0]);
+
+        Self::render_gc_stats(frame, left[0], store, theme);
+        Self::render_gc_causes(frame, left[1], &causes, theme);
+        Self::render_gc_pauses(frame, bottom[1], store, theme);
     }
 
     fn render_gc_summary(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let latest_gc = store.gc_history.iter().last();
+        let latest_gc = store.gc_history.latest();
 
         let summary_text = if let Some(gc) = latest_gc {
-            let total_gc_time = (gc.young_gc_time_ms + gc.old_gc_time_ms) as f64 / 1000.0;
-            let avg_young = if gc.young_gc_count > 0 {
-                gc.young_gc_time_ms as f64 / gc.young_gc_count as f64
-            } else {
-                0.0
-            };
-            let avg_old = if gc.old_gc_count > 0 {
-                gc.old_gc_time_ms as f64 / gc.old_gc_count as f64
-            } else {
-                0.0
-            };
+            let total_gc_time = (gc.young_gc_time_ms() + gc.old_gc_time_ms()) as f64 / 1000.0;
+            let collectors: String = gc
+                .collectors
+                .iter()
+                .map(|collector| {
+                    format!(
+                        "{} ({:?}): {} collections, {:.2}s total (avg {:.2}ms)\n",
+                        collector.name,
+                        collector.category,
+                        collector.count,
+                        collector.time_ms as f64 / 1000.0,
+                        collector.avg_ms()
+                    )
+                })
+                .collect();
 
             format!(
                 "Total Collections: {}\n\
-                 Young GC: {} collections, {:.2}s total (avg {:.2}ms)\n\
-                 Full GC: {} collections, {:.2}s total (avg {:.2}ms)\n\
-                 \n\
-                 Total GC Time: {:.2}s\n\
-                 GC Overhead: Calculating...",
-                gc.young_gc_count + gc.old_gc_count,
-                gc.young_gc_count,
-                gc.young_gc_time_ms as f64 / 1000.0,
-                avg_young,
-                gc.old_gc_count,
-                gc.old_gc_time_ms as f64 / 1000.0,
-                avg_old,
+                 {}\n\
+                 Total GC Time: {:.2}s",
+                gc.young_gc_count() + gc.old_gc_count(),
+                collectors,
                 total_gc_time
             )
         } else {
             "No GC data available yet...".to_string()
         };
 
-        let summary = Paragraph::new(summary_text)
+        let mut lines: Vec<Line> = summary_text
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        if latest_gc.is_some() {
+            lines.push(Self::throughput_line(store, theme));
+        }
+        if let Some(humongous) = &store.humongous {
+            lines.push(Self::humongous_line(humongous, theme));
+        }
+
+        let summary = Paragraph::new(lines)
             .block(Block::default().borders(Borders::ALL).title("GC Summary"))
             .style(Style::default().fg(theme.text()));
 
         frame.render_widget(summary, area);
     }
 
-    fn render_gc_timeline(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let gc_history: Vec<&GcStats> = store.gc_history.iter().collect();
+    /// G1 humongous readout, flagged once humongous objects take a
+    /// significant share of the heap's regions.
+    fn humongous_line(humongous: &HumongousStats, theme: &Theme) -> Line<'static> {
+        let text = format!(
+            "Humongous regions: {} ({} objects, {:.1}% of {} regions)",
+            humongous.regions,
+            humongous.objects,
+            humongous.percent_of_heap(),
+            humongous.total_regions
+        );
+        if humongous.is_significant() {
+            Line::from(Span::styled(
+                format!("{} - risk of full GCs", text),
+                Style::default().fg(theme.warning()),
+            ))
+        } else {
+            Line::from(text)
+        }
+    }
 
-        if gc_history.is_empty() {
-            let placeholder = Paragraph::new("Waiting for GC data...")
-                .block(Block::default().borders(Borders::ALL).title("GC Timeline"))
-                .style(Style::default().fg(theme.text_dim()));
-            frame.render_widget(placeholder, area);
+    /// Throughput line colored against the usual GC tuning targets
+    /// (>= 95% healthy, >= 90% borderline).
+    pub fn throughput_line(store: &MetricsStore, theme: &Theme) -> Line<'static> {
+        match store.gc_throughput_percent() {
+            Some(throughput) => {
+                let color = if throughput >= 95.0 {
+                    theme.success()
+                } else if throughput >= 90.0 {
+                    theme.warning()
+                } else {
+                    theme.error()
+                };
+                Line::from(vec![
+                    Span::raw("Throughput: "),
+                    Span::styled(
+                        format!("{:.2}%", throughput),
+                        Style::default().fg(color).add_modifier(Modifier::BOLD),
+                    ),
+                    Span::raw(format!(" (GC overhead {:.2}%)", 100.0 - throughput)),
+                ])
+            }
+            None => Line::from("Throughput: Calculating..."),
+        }
+    }
+
+    fn render_gc_timeline(
+        frame: &mut Frame,
+        area: Rect,
+        store: &MetricsStore,
+        timeline: &TimelineWindow,
+        theme: &Theme,
+    ) {
+        let full_history: Vec<&GcStats> = store.gc_history.iter().collect();
+
+        if full_history.len() < MIN_CHART_SAMPLES {
+            frame.render_widget(
+                collecting_placeholder("GC Event Timeline", full_history.len(), theme),
+                area,
+            );
             return;
         }
 
+        let range = timeline.range(full_history.len());
+        let gc_history = &full_history[range.clone()];
+
         let young_data: Vec<(f64, f64)> = gc_history
             .iter()
-            .enumerate()
-            .map(|(i, gc)| (i as f64, gc.young_gc_count as f64))
+            .zip(range.clone())
+            .map(|(gc, i)| (i as f64, gc.young_gc_count() as f64))
             .collect();
 
         let old_data: Vec<(f64, f64)> = gc_history
             .iter()
-            .enumerate()
-            .map(|(i, gc)| (i as f64, gc.old_gc_count as f64))
+            .zip(range.clone())
+            .map(|(gc, i)| (i as f64, gc.old_gc_count() as f64))
             .collect();
 
         let max_young = gc_history
             .iter()
-            .map(|gc| gc.young_gc_count)
+            .map(|gc| gc.young_gc_count())
             .max()
             .unwrap_or(1) as f64;
 
         let max_old = gc_history
             .iter()
-            .map(|gc| gc.old_gc_count)
+            .map(|gc| gc.old_gc_count())
             .max()
             .unwrap_or(1) as f64;
 
@@ -120,32 +221,131 @@ impl GcView {
                 .data(&old_data),
         ];
 
+        let title = if timeline.is_whole(full_history.len()) {
+            "GC Event Timeline".to_string()
+        } else {
+            format!(
+                "GC Event Timeline - samples {}-{} of {}{}",
+                range.start + 1,
+                range.end,
+                full_history.len(),
+                if timeline.follow { " (following)" } else { "" }
+            )
+        };
+
+        let x_bounds = [
+            range.start as f64,
+            (range.end.saturating_sub(1) as f64).max(range.start as f64 + 1.0),
+        ];
+
+        // Relative to the newest sample, so a panned-back window reads "-5m"
+        let times: Vec<_> = gc_history.iter().map(|gc| gc.timestamp).collect();
+        let now = full_history[full_history.len() - 1].timestamp;
+        let x_labels = time_axis_labels(&times, now);
+
+        let chart = Chart::new(datasets)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(theme.text_dim()))
+                    .bounds(x_bounds)
+                    .labels(x_labels),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("Count")
+                    .style(Style::default().fg(theme.text_dim()))
+                    .bounds([0.0, max_count]),
+            );
+
+        frame.render_widget(chart, area);
+    }
+
+    /// GC time spent in each poll interval; a rising line is a pause-time
+    /// regression the cumulative counts hide. Follows the timeline's window.
+    fn render_gc_time_chart(
+        frame: &mut Frame,
+        area: Rect,
+        store: &MetricsStore,
+        timeline: &TimelineWindow,
+        theme: &Theme,
+    ) {
+        let samples = store.gc_history.len();
+        if samples < MIN_CHART_SAMPLES {
+            frame.render_widget(
+                collecting_placeholder("GC Time per Interval", samples, theme),
+                area,
+            );
+            return;
+        }
+
+        let range = timeline.range(samples);
+        let deltas: Vec<_> = store
+            .gc_time_deltas()
+            .into_iter()
+            .filter(|(i, _, _)| range.contains(&(*i as usize)))
+            .collect();
+        let young_data: Vec<(f64, f64)> = deltas.iter().map(|(i, young, _)| (*i, *young)).collect();
+        let old_data: Vec<(f64, f64)> = deltas.iter().map(|(i, _, old)| (*i, *old)).collect();
+
+        let max_ms = deltas
+            .iter()
+            .map(|(_, young, old)| young.max(*old))
+            .fold(10.0, f64::max);
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Young GC")
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.chart_line_primary()))
+                .data(&young_data),
+            Dataset::default()
+                .name("Full GC")
+                .marker(symbols::Marker::Dot)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.chart_line_secondary()))
+                .data(&old_data),
+        ];
+
+        let x_bounds = [
+            range.start as f64,
+            (range.end.saturating_sub(1) as f64).max(range.start as f64 + 1.0),
+        ];
+
         let chart = Chart::new(datasets)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("GC Event Timeline"),
+                    .title("GC Time per Interval"),
             )
             .x_axis(
                 Axis::default()
-                    .title("Samples")
                     .style(Style::default().fg(theme.text_dim()))
-                    .bounds([0.0, gc_history.len() as f64]),
+                    .bounds(x_bounds),
             )
             .y_axis(
                 Axis::default()
-                    .title("Count")
+                    .title("ms")
                     .style(Style::default().fg(theme.text_dim()))
-                    .bounds([0.0, max_count]),
+                    .bounds([0.0, max_ms])
+                    .labels(vec!["0".to_string(), format!("{:.0}", max_ms)]),
             );
 
         frame.render_widget(chart, area);
     }
 
-    fn render_gc_stats(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let gc_history: Vec<&GcStats> = store.gc_history.iter().collect();
+    /// Estimated allocation rate, shared with the Overview tab.
+    pub fn allocation_rate_text(store: &MetricsStore) -> String {
+        store
+            .allocation_rate_mb_per_sec()
+            .map(|rate| format!("{:.1} MB/s", rate))
+            .unwrap_or_else(|| "n/a".to_string())
+    }
 
-        if gc_history.is_empty() {
+    fn render_gc_stats(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        let (Some(latest), Some(delta)) = (store.gc_history.latest(), store.gc_window_delta())
+        else {
             let placeholder = Paragraph::new("Waiting for GC statistics...")
                 .block(
                     Block::default()
@@ -155,17 +355,12 @@ impl GcView {
                 .style(Style::default().fg(theme.text_dim()));
             frame.render_widget(placeholder, area);
             return;
-        }
-
-        let latest = gc_history.last().unwrap();
-        let first = gc_history.first().unwrap();
+        };
 
-        let young_diff = latest.young_gc_count.saturating_sub(first.young_gc_count);
-        let old_diff = latest.old_gc_count.saturating_sub(first.old_gc_count);
-        let young_time_diff = latest
-            .young_gc_time_ms
-            .saturating_sub(first.young_gc_time_ms);
-        let old_time_diff = latest.old_gc_time_ms.saturating_sub(first.old_gc_time_ms);
+        let rate = store
+            .gc_rate_per_min()
+            .map(|rate| format!("{:.1}/min", rate))
+            .unwrap_or_else(|| "n/a".to_string());
 
         let stats_text = format!(
             "Statistics (Last {} samples):\n\
@@ -177,26 +372,22 @@ impl GcView {
              Full GC Time: {:.2}s (Δ{:.2}s)\n\
              \n\
              Recent Avg Young GC: {:.2}ms\n\
-             Recent Avg Full GC: {:.2}ms",
-            gc_history.len(),
-            latest.young_gc_count,
-            young_diff,
-            latest.young_gc_time_ms as f64 / 1000.0,
-            young_time_diff as f64 / 1000.0,
-            latest.old_gc_count,
-            old_diff,
-            latest.old_gc_time_ms as f64 / 1000.0,
-            old_time_diff as f64 / 1000.0,
-            if young_diff > 0 {
-                young_time_diff as f64 / young_diff as f64
-            } else {
-                0.0
-            },
-            if old_diff > 0 {
-                old_time_diff as f64 / old_diff as f64
-            } else {
-                0.0
-            }
+             Recent Avg Full GC: {:.2}ms\n\
+             Collection Rate: {}\n\
+             Allocation Rate: {}",
+            store.gc_history.len(),
+            latest.young_gc_count(),
+            delta.young_gc_count(),
+            latest.young_gc_time_ms() as f64 / 1000.0,
+            delta.young_gc_time_ms() as f64 / 1000.0,
+            latest.old_gc_count(),
+            delta.old_gc_count(),
+            latest.old_gc_time_ms() as f64 / 1000.0,
+            delta.old_gc_time_ms() as f64 / 1000.0,
+            delta.avg_young_gc_ms(),
+            delta.avg_old_gc_ms(),
+            rate,
+            Self::allocation_rate_text(store)
         );
 
         let stats = Paragraph::new(stats_text)
@@ -209,4 +400,152 @@ impl GcView {
 
         frame.render_widget(stats, area);
     }
+
+    fn render_gc_causes(frame: &mut Frame, area: Rect, causes: &[(&str, usize)], theme: &Theme) {
+        if causes.is_empty() {
+            // jstat only reports counts, so causes need a GC log
+            let placeholder = Paragraph::new("Unknown; causes are read from --gc-log")
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title("Top GC Causes"),
+                )
+                .style(Style::default().fg(theme.text_dim()));
+            frame.render_widget(placeholder, area);
+            return;
+        }
+
+        let total: usize = causes.iter().map(|(_, count)| count).sum();
+        let rows: Vec<Row> = causes
+            .iter()
+            .take(MAX_CAUSES)
+            .map(|(cause, count)| {
+                Row::new(vec![
+                    Cell::from(format!("{:.0}%", *count as f64 / total as f64 * 100.0)),
+                    Cell::from(count.to_string()),
+                    Cell::from(*cause),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(5),
+                Constraint::Length(6),
+                Constraint::Min(10),
+            ],
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Top GC Causes ({} collections)", total)),
+        )
+        .style(Style::default().fg(theme.text()));
+
+        frame.render_widget(table, area);
+    }
+
+    fn render_gc_pauses(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        if store.gc_pauses.is_empty() {
+            let placeholder =
+                Paragraph::new("No GC pauses yet. Pass --gc-log or set preferences.gc_log_path.")
+                    .block(Block::default().borders(Borders::ALL).title("GC Pauses"))
+                    .style(Style::default().fg(theme.text_dim()));
+            frame.render_widget(placeholder, area);
+            return;
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(8), Constraint::Min(0)])
+            .split(area);
+
+        let labels = gc_pause_bucket_labels();
+        let counts = store.gc_pause_histogram();
+        let data: Vec<(&str, u64)> = labels
+            .iter()
+            .map(String::as_str)
+            .zip(counts.iter().copied())
+            .collect();
+
+        let histogram = BarChart::default()
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "Pause Durations ({} pauses)",
+                store.gc_pauses.len()
+            )))
+            .data(&data)
+            .bar_width(7)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(theme.chart_line_primary()))
+            .value_style(
+                Style::default()
+                    .fg(theme.background())
+                    .bg(theme.chart_line_primary()),
+            )
+            .label_style(Style::default().fg(theme.text_dim()));
+
+        frame.render_widget(histogram, chunks[0]);
+
+        let header = Row::new(vec!["GC", "Uptime", "Duration", "Cause"])
+            .style(Style::default().add_modifier(Modifier::BOLD));
+
+        let rows: Vec<Row> = store
+            .gc_pauses
+            .iter()
+            .rev()
+            .map(|pause| {
+                let when = match (pause.uptime_seconds, pause.timestamp) {
+                    (Some(uptime), _) => format!("{:.3}s", uptime),
+                    (None, Some(timestamp)) => timestamp.format("%H:%M:%S").to_string(),
+                    (None, None) => "-".to_string(),
+                };
+                let duration_color = if pause.duration_ms >= 100.0 {
+                    theme.error()
+                } else if pause.duration_ms >= 10.0 {
+                    theme.warning()
+                } else {
+                    theme.text()
+                };
+                Row::new(vec![
+                    Cell::from(pause.gc_id.to_string()),
+                    Cell::from(when),
+                    Cell::from(format!("{:.3}ms", pause.duration_ms))
+                        .style(Style::default().fg(duration_color)),
+                    Cell::from(pause.description()),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(7),
+                Constraint::Length(11),
+                Constraint::Length(12),
+                Constraint::Min(20),
+            ],
+        )
+        .header(header)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Recent Pauses"),
+        )
+        .style(Style::default().fg(theme.text()));
+
+        frame.render_widget(table, chunks[1]);
+    }
+}
+
+fn gc_pause_bucket_labels() -> Vec<String> {
+    let mut labels: Vec<String> = GC_PAUSE_BUCKETS_MS
+        .iter()
+        .map(|bound| format!("<{}ms", bound))
+        .collect();
+    labels.push(format!(
+        "{}ms+",
+        GC_PAUSE_BUCKETS_MS[GC_PAUSE_BUCKETS_MS.len() - 1]
+    ));
+    labels
 }
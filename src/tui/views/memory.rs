@@ -1,7 +1,10 @@
+use crate::jvm::types::{MemoryPool, NativeMemory, NativeMemoryReport, PoolType};
 use crate::metrics::store::MetricsStore;
 use crate::theme::Theme;
+use crate::tui::views::{collecting_placeholder, time_axis_labels, time_ruler, MIN_CHART_SAMPLES};
+use crate::tui::widgets::heap_composition::HeapCompositionBar;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     prelude::*,
     widgets::{Block, Borders, Gauge, Paragraph, Sparkline},
 };
@@ -12,91 +15,242 @@ impl MemoryView {
     pub fn render(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(10), Constraint::Min(0)])
+            .constraints([
+                Constraint::Length(10),
+                Constraint::Length(3),
+                Constraint::Min(0),
+            ])
             .split(area);
 
         Self::render_heap_sparkline(frame, chunks[0], store, theme);
-        Self::render_memory_pools(frame, chunks[1], store, theme);
+        if let Some(heap) = store.heap_history.latest() {
+            HeapCompositionBar::render(frame, chunks[1], heap, theme);
+        }
+
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[2]);
+
+        Self::render_memory_pools(frame, bottom[0], store, theme);
+        Self::render_native_memory(frame, bottom[1], store, theme);
     }
 
     fn render_heap_sparkline(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        // One column per sample, so only the newest that fit are drawn
+        let width = area.width.saturating_sub(2) as usize;
+        let skip = store.heap_history.len().saturating_sub(width);
         let heap_data: Vec<u64> = store
             .heap_history
             .iter()
-            .map(|h| (h.used_bytes / 1024 / 1024) as u64)
+            .skip(skip)
+            .map(|h| h.used_bytes / 1024 / 1024)
             .collect();
 
-        let max_heap = heap_data.iter().max().copied().unwrap_or(1);
+        if heap_data.len() < MIN_CHART_SAMPLES {
+            frame.render_widget(
+                collecting_placeholder("Heap Usage Timeline", heap_data.len(), theme),
+                area,
+            );
+            return;
+        }
 
-        let latest_heap = store.heap_history.iter().last();
-        let sparkline_title = if latest_heap.is_some() {
-            format!("Heap Usage Timeline (max: {} MB)", max_heap)
-        } else {
-            "Heap Usage Timeline".to_string()
+        let stats = store.heap_used_stats();
+        // A flat zero series would otherwise divide by a zero max
+        let max_heap = stats.map_or(1, |s| s.max / 1024 / 1024).max(1);
+        let sparkline_title = match stats {
+            Some(s) => format!(
+                "Heap Usage Timeline (min: {} / avg: {:.0} / max: {} MB)",
+                s.min / 1024 / 1024,
+                s.avg / 1024.0 / 1024.0,
+                max_heap
+            ),
+            None => "Heap Usage Timeline".to_string(),
         };
 
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(sparkline_title);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+
         let sparkline = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(sparkline_title),
-            )
             .data(&heap_data)
             .max(max_heap)
             .style(Style::default().fg(theme.chart_line_primary()));
+        frame.render_widget(sparkline, rows[0]);
+
+        let heap_times: Vec<_> = store
+            .heap_history
+            .iter()
+            .skip(skip)
+            .map(|h| h.timestamp)
+            .collect();
+        if let Some(&now) = heap_times.last() {
+            let labels = time_axis_labels(&heap_times, now);
+            // The ruler spans the drawn columns, not the whole width
+            let ruler = time_ruler(&labels, heap_data.len().min(rows[1].width as usize));
+            frame.render_widget(
+                Paragraph::new(ruler).style(Style::default().fg(theme.text_dim())),
+                rows[1],
+            );
+        }
+    }
+
+    fn render_native_memory(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Native Memory (committed)");
+
+        let lines = match &store.native_memory {
+            Some(NativeMemory::Tracked(report)) => {
+                // Borders, name, gap, bar, "  12345.6 MB"
+                let bar_width = (area.width as usize).saturating_sub(2 + 24 + 2 + 12).max(4);
+                Self::native_memory_lines(report, bar_width, theme)
+            }
+            Some(NativeMemory::Disabled) => vec![
+                Line::from(Span::styled(
+                    "Native Memory Tracking is not enabled.",
+                    Style::default().fg(theme.warning()),
+                )),
+                Line::from(""),
+                Line::from("Restart the JVM with -XX:NativeMemoryTracking=summary"),
+                Line::from("to see off-heap usage by category (threads, code"),
+                Line::from("cache, GC structures, metaspace...)."),
+            ],
+            None => vec![Line::from("No native memory data available")],
+        };
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    /// One bar per category, largest committed first, scaled to the largest.
+    fn native_memory_lines(
+        report: &NativeMemoryReport,
+        bar_width: usize,
+        theme: &Theme,
+    ) -> Vec<Line<'static>> {
+        let mut categories: Vec<_> = report
+            .categories
+            .iter()
+            .filter(|c| c.committed_bytes > 0)
+            .collect();
+        categories.sort_by_key(|c| std::cmp::Reverse(c.committed_bytes));
+
+        let largest = categories
+            .first()
+            .map(|c| c.committed_bytes)
+            .unwrap_or(1)
+            .max(1);
+
+        let mut lines = vec![Line::from(format!(
+            "Total: {} MB committed / {} MB reserved",
+            report.total_committed_bytes / 1024 / 1024,
+            report.total_reserved_bytes / 1024 / 1024
+        ))];
+
+        for category in categories {
+            let filled = ((category.committed_bytes as f64 / largest as f64) * bar_width as f64)
+                .round()
+                .max(1.0) as usize;
+            lines.push(Line::from(vec![
+                Span::raw(format!("{:<24.24}  ", category.name)),
+                Span::styled(
+                    "█".repeat(filled),
+                    Style::default().fg(theme.chart_line_primary()),
+                ),
+                Span::raw(" ".repeat(bar_width - filled)),
+                Span::raw(format!(
+                    "  {:>7.1} MB",
+                    category.committed_bytes as f64 / 1024.0 / 1024.0
+                )),
+            ]));
+        }
+
+        lines
+    }
 
-        frame.render_widget(sparkline, area);
+    /// Young generation first, then old, then non-heap, whatever order the
+    /// connector reported them in.
+    fn ordered_pools(pools: &[MemoryPool]) -> Vec<&MemoryPool> {
+        let mut ordered: Vec<&MemoryPool> = pools.iter().collect();
+        ordered.sort_by(|a, b| {
+            a.pool_type
+                .cmp(&b.pool_type)
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        ordered
+    }
+
+    fn legend_line(pools: &[&MemoryPool], theme: &Theme) -> Line<'static> {
+        let mut spans = Vec::new();
+        for pool_type in PoolType::all() {
+            if pools.iter().any(|pool| pool.pool_type == *pool_type) {
+                spans.push(Span::styled(
+                    "■ ",
+                    Style::default().fg(theme.pool_color(*pool_type)),
+                ));
+                spans.push(Span::raw(format!("{}  ", pool_type.label())));
+            }
+        }
+        Line::from(spans)
     }
 
     fn render_memory_pools(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let latest_heap = store.heap_history.iter().last();
+        let latest_heap = store.heap_history.latest();
 
         if let Some(heap) = latest_heap {
-            let pool_count = heap.pools.len();
-            let constraints: Vec<Constraint> =
-                (0..pool_count).map(|_| Constraint::Length(4)).collect();
+            let pools = Self::ordered_pools(&heap.pools);
+            let constraints: Vec<Constraint> = std::iter::once(Constraint::Length(1))
+                .chain(pools.iter().map(|_| Constraint::Length(4)))
+                .collect();
 
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints(constraints)
                 .split(area);
 
-            for (i, pool) in heap.pools.iter().enumerate() {
-                if i < chunks.len() {
-                    let ratio = if pool.max_bytes > 0 {
-                        pool.used_bytes as f64 / pool.max_bytes as f64
-                    } else {
-                        0.0
-                    };
-
-                    let gauge_color = if ratio > 0.9 {
-                        theme.memory_critical()
-                    } else if ratio > 0.7 {
-                        theme.memory_high()
-                    } else {
-                        theme.success()
-                    };
-
-                    let label = format!(
-                        "{}: {} / {} MB ({:.1}%)",
-                        pool.name,
-                        pool.used_bytes / 1024 / 1024,
-                        pool.max_bytes / 1024 / 1024,
-                        ratio * 100.0
-                    );
-
-                    let gauge = Gauge::default()
-                        .block(Block::default().borders(Borders::ALL))
-                        .gauge_style(
-                            Style::default()
-                                .fg(gauge_color)
-                                .bg(theme.gauge_background()),
-                        )
-                        .label(label)
-                        .ratio(ratio);
-
-                    frame.render_widget(gauge, chunks[i]);
+            frame.render_widget(Paragraph::new(Self::legend_line(&pools, theme)), chunks[0]);
+
+            for (pool, chunk) in pools.iter().zip(chunks.iter().skip(1)) {
+                if pool.max_bytes == 0 {
+                    Self::render_unbounded_pool(frame, *chunk, pool, theme);
+                    continue;
                 }
+
+                let ratio = (pool.used_bytes as f64 / pool.max_bytes as f64).min(1.0);
+
+                // Pools nearly at their limit stand out whatever their type
+                let gauge_color = if ratio > 0.9 {
+                    theme.memory_critical()
+                } else {
+                    theme.pool_color(pool.pool_type)
+                };
+
+                let label = format!(
+                    "{}: {} / {} MB ({:.1}%)",
+                    pool.name,
+                    pool.used_bytes / 1024 / 1024,
+                    pool.max_bytes / 1024 / 1024,
+                    ratio * 100.0
+                );
+
+                let gauge = Gauge::default()
+                    .block(Self::pool_block(pool, theme))
+                    .gauge_style(
+                        Style::default()
+                            .fg(gauge_color)
+                            .bg(theme.gauge_background()),
+                    )
+                    .label(label)
+                    .ratio(ratio);
+
+                frame.render_widget(gauge, *chunk);
             }
         } else {
             let no_data = Paragraph::new("No memory pool data available")
@@ -104,4 +258,89 @@ impl MemoryView {
             frame.render_widget(no_data, area);
         }
     }
+
+    fn pool_block(pool: &MemoryPool, theme: &Theme) -> Block<'static> {
+        Block::default().borders(Borders::ALL).title(Span::styled(
+            pool.pool_type.label(),
+            Style::default().fg(theme.pool_color(pool.pool_type)),
+        ))
+    }
+
+    /// Pools without a maximum get a hatched bar and used / committed, since
+    /// a percentage of nothing would read as 0%.
+    fn render_unbounded_pool(frame: &mut Frame, area: Rect, pool: &MemoryPool, theme: &Theme) {
+        let block = Self::pool_block(pool, theme);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let hatch: Vec<Line> = (0..inner.height)
+            .map(|_| Line::from("░".repeat(inner.width as usize)))
+            .collect();
+        frame.render_widget(
+            Paragraph::new(hatch).style(Style::default().fg(theme.text_dim())),
+            inner,
+        );
+
+        let label = format!(
+            "{}: {} / {} MB committed (no max)",
+            pool.name,
+            pool.used_bytes / 1024 / 1024,
+            pool.committed_bytes / 1024 / 1024
+        );
+        // Same row a Gauge puts its label on
+        let label_area = Rect {
+            y: inner.y + inner.height / 2,
+            height: inner.height.min(1),
+            ..inner
+        };
+        frame.render_widget(
+            Paragraph::new(label)
+                .style(Style::default().fg(theme.text()))
+                .alignment(Alignment::Center),
+            label_area,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(name: &str, pool_type: PoolType) -> MemoryPool {
+        MemoryPool {
+            name: name.to_string(),
+            pool_type,
+            used_bytes: 0,
+            max_bytes: 0,
+            committed_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_ordered_pools_young_then_old_then_non_heap() {
+        let pools = vec![
+            pool("Metaspace", PoolType::Metaspace),
+            pool("G1 Old Gen", PoolType::Old),
+            pool("CodeHeap 'non-nmethods'", PoolType::CodeCache),
+            pool("G1 Survivor Space", PoolType::Survivor),
+            pool("Compressed Class Space", PoolType::Metaspace),
+            pool("G1 Eden Space", PoolType::Eden),
+        ];
+
+        let names: Vec<&str> = MemoryView::ordered_pools(&pools)
+            .iter()
+            .map(|pool| pool.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "G1 Eden Space",
+                "G1 Survivor Space",
+                "G1 Old Gen",
+                "Compressed Class Space",
+                "Metaspace",
+                "CodeHeap 'non-nmethods'",
+            ]
+        );
+    }
 }
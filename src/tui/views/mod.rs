@@ -3,3 +3,184 @@ pub mod gc;
 pub mod memory;
 pub mod overview;
 pub mod threads;
+
+use crate::theme::Theme;
+use chrono::{DateTime, Local};
+use ratatui::{
+    layout::{Margin, Rect},
+    style::Style,
+    widgets::{Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+/// Charts need two samples to draw anything meaningful; until then views
+/// show a placeholder instead.
+pub(crate) const MIN_CHART_SAMPLES: usize = 2;
+
+/// X-axis bounds for a chart indexed by sample number, never zero-width.
+pub(crate) fn sample_bounds(len: usize) -> [f64; 2] {
+    [0.0, len.saturating_sub(1).max(1) as f64]
+}
+
+/// How long before `now` a sample was taken, e.g. "-45s", "-2m", "-1h5m".
+pub(crate) fn relative_time_label(at: DateTime<Local>, now: DateTime<Local>) -> String {
+    let seconds = (now - at).num_seconds().max(0);
+    match seconds {
+        0 => "now".to_string(),
+        s if s < 60 => format!("-{}s", s),
+        s if s < 3600 && s % 60 == 0 => format!("-{}m", s / 60),
+        s if s < 3600 => format!("-{}m{}s", s / 60, s % 60),
+        s if s % 3600 < 60 => format!("-{}h", s / 3600),
+        s => format!("-{}h{}m", s / 3600, s % 3600 / 60),
+    }
+}
+
+/// Labels for the oldest, middle and newest of `times` (oldest first), for a
+/// chart x-axis or [`time_ruler`].
+pub(crate) fn time_axis_labels(times: &[DateTime<Local>], now: DateTime<Local>) -> Vec<String> {
+    match times {
+        [] => Vec::new(),
+        [only] => vec![relative_time_label(*only, now)],
+        [first, .., last] => vec![
+            relative_time_label(*first, now),
+            relative_time_label(times[times.len() / 2], now),
+            relative_time_label(*last, now),
+        ],
+    }
+}
+
+/// `labels` spread over `width` columns: the first flush left, the last flush
+/// right and the rest centred between. Labels that would overlap are dropped.
+pub(crate) fn time_ruler(labels: &[String], width: usize) -> String {
+    let mut ruler = vec![' '; width];
+    let mut free_from = 0;
+    for (i, label) in labels.iter().enumerate() {
+        let len = label.chars().count();
+        let start = match i {
+            0 => 0,
+            i if i + 1 == labels.len() => width.saturating_sub(len),
+            i => (i * width.saturating_sub(1) / (labels.len() - 1)).saturating_sub(len / 2),
+        };
+        if start < free_from || start + len > width {
+            continue;
+        }
+        for (slot, c) in ruler[start..].iter_mut().zip(label.chars()) {
+            *slot = c;
+        }
+        // Keep a gap between neighbouring labels
+        free_from = start + len + 1;
+    }
+    ruler.into_iter().collect()
+}
+
+/// Rows a bordered table with a one-line header has room for in `area`.
+pub(crate) fn table_rows(area: Rect) -> usize {
+    area.height.saturating_sub(3) as usize
+}
+
+/// `scroll` kept within a list of `total` rows, so scrolling past the end
+/// leaves the last row in view rather than an empty table.
+pub(crate) fn clamp_scroll(scroll: usize, total: usize) -> usize {
+    scroll.min(total.saturating_sub(1))
+}
+
+/// Which rows of `total` are visible, e.g. "26-50 of 180", for table titles.
+pub(crate) fn window_label(scroll: usize, shown: usize, total: usize) -> String {
+    if shown == 0 {
+        return format!("0 of {}", total);
+    }
+    format!("{}-{} of {}", scroll + 1, scroll + shown, total)
+}
+
+/// A scrollbar on the right border of the bordered table in `area`; nothing
+/// when every row fits.
+pub(crate) fn render_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    scroll: usize,
+    total: usize,
+    theme: &Theme,
+) {
+    let visible = table_rows(area);
+    if total <= visible {
+        return;
+    }
+
+    let mut state = ScrollbarState::new(total.saturating_sub(visible))
+        .position(scroll)
+        .viewport_content_length(visible);
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .style(Style::default().fg(theme.border_focused()));
+    frame.render_stateful_widget(
+        scrollbar,
+        area.inner(Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut state,
+    );
+}
+
+pub(crate) fn collecting_placeholder<'a>(
+    title: &'a str,
+    samples: usize,
+    theme: &Theme,
+) -> Paragraph<'a> {
+    Paragraph::new(format!(
+        "Collecting... ({}/{} samples)",
+        samples, MIN_CHART_SAMPLES
+    ))
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .style(Style::default().fg(theme.text_dim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_time_label() {
+        let now = Local::now();
+        let ago = |seconds| now - chrono::Duration::seconds(seconds);
+
+        assert_eq!(relative_time_label(now, now), "now");
+        assert_eq!(relative_time_label(ago(45), now), "-45s");
+        assert_eq!(relative_time_label(ago(120), now), "-2m");
+        assert_eq!(relative_time_label(ago(90), now), "-1m30s");
+        assert_eq!(relative_time_label(ago(3600), now), "-1h");
+        assert_eq!(relative_time_label(ago(3900), now), "-1h5m");
+        // Clock skew never shows a sample in the future
+        assert_eq!(relative_time_label(ago(-5), now), "now");
+    }
+
+    #[test]
+    fn test_time_ruler() {
+        let labels: Vec<String> = ["-2m", "-1m", "now"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(time_ruler(&labels, 21), "-2m      -1m      now");
+        // Too narrow for the middle label
+        assert_eq!(time_ruler(&labels, 8), "-2m  now");
+        assert_eq!(time_ruler(&labels[..1], 5), "-2m  ");
+    }
+
+    #[test]
+    fn test_scroll_window() {
+        assert_eq!(clamp_scroll(500, 180), 179);
+        assert_eq!(clamp_scroll(3, 0), 0);
+        assert_eq!(window_label(25, 25, 180), "26-50 of 180");
+        assert_eq!(window_label(175, 5, 180), "176-180 of 180");
+        assert_eq!(window_label(0, 0, 0), "0 of 0");
+    }
+
+    #[test]
+    fn test_sample_bounds_never_degenerate() {
+        assert_eq!(sample_bounds(0), [0.0, 1.0]);
+        assert_eq!(sample_bounds(1), [0.0, 1.0]);
+        assert_eq!(sample_bounds(2), [0.0, 1.0]);
+        assert_eq!(sample_bounds(60), [0.0, 59.0]);
+    }
+}
@@ -1,15 +1,36 @@
-use crate::metrics::store::MetricsStore;
+use crate::jvm::types::JvmInfo;
+use crate::metrics::store::{MetricsStore, Trend};
 use crate::theme::Theme;
+use crate::tui::views::gc::GcView;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    widgets::{Block, Borders, Gauge, Paragraph, Sparkline},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Sparkline},
 };
 
 pub struct OverviewView;
 
 impl OverviewView {
-    pub fn render(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        store: &MetricsStore,
+        jvm_info: Option<&JvmInfo>,
+        scroll: usize,
+        theme: &Theme,
+    ) {
+        let area = match store.post_gc_heap_trend().filter(|trend| trend.rising) {
+            Some(trend) => {
+                let rows = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Length(1), Constraint::Min(0)])
+                    .split(area);
+                Self::render_leak_warning(frame, rows[0], &trend, theme);
+                rows[1]
+            }
+            None => area,
+        };
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
@@ -21,7 +42,55 @@ impl OverviewView {
 
         Self::render_heap_section(frame, chunks[0], store, theme);
         Self::render_gc_section(frame, chunks[1], store, theme);
-        Self::render_summary_section(frame, chunks[2], store, theme);
+
+        let bottom = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[2]);
+
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(6),
+                Constraint::Length(5),
+            ])
+            .split(bottom[0]);
+
+        Self::render_summary_section(frame, left[0], store, theme);
+        Self::render_runtime_section(frame, left[1], store, theme);
+        Self::render_compiler_section(frame, left[2], store, theme);
+        Self::render_vm_section(frame, bottom[1], store, jvm_info, scroll, theme);
+    }
+
+    pub fn format_uptime(seconds: u64) -> String {
+        let days = seconds / 86_400;
+        let hours = (seconds % 86_400) / 3600;
+        let minutes = (seconds % 3600) / 60;
+
+        if days > 0 {
+            format!("{}d {}h {}m", days, hours, minutes)
+        } else if hours > 0 {
+            format!("{}h {}m", hours, minutes)
+        } else {
+            format!("{}m {}s", minutes, seconds % 60)
+        }
+    }
+
+    fn render_leak_warning(frame: &mut Frame, area: Rect, trend: &Trend, theme: &Theme) {
+        let text = format!(
+            " Possible memory leak: post-GC heap rising ({} -> {} MB over {} full GCs); \
+             a baseline diff on the Classes tab shows what's growing",
+            trend.first_floor_bytes / 1024 / 1024,
+            trend.last_floor_bytes / 1024 / 1024,
+            trend.collections
+        );
+        let warning = Paragraph::new(text).style(
+            Style::default()
+                .fg(theme.warning())
+                .add_modifier(Modifier::BOLD),
+        );
+        frame.render_widget(warning, area);
     }
 
     fn render_heap_section(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
@@ -33,10 +102,10 @@ impl OverviewView {
         let heap_data: Vec<u64> = store
             .heap_history
             .iter()
-            .map(|h| (h.used_bytes / 1024 / 1024) as u64)
+            .map(|h| h.used_bytes / 1024 / 1024)
             .collect();
 
-        let latest_heap = store.heap_history.iter().last();
+        let latest_heap = store.heap_history.latest();
 
         let sparkline_title = if let Some(heap) = latest_heap {
             format!(
@@ -61,7 +130,12 @@ impl OverviewView {
         frame.render_widget(sparkline, inner[0]);
 
         if let Some(heap) = latest_heap {
-            let ratio = heap.used_bytes as f64 / heap.max_bytes as f64;
+            // Gauge panics outside 0..=1, and max can be 0 before the heap is sized
+            let ratio = if heap.max_bytes > 0 {
+                (heap.used_bytes as f64 / heap.max_bytes as f64).min(1.0)
+            } else {
+                0.0
+            };
             let gauge = Gauge::default()
                 .block(Block::default().borders(Borders::ALL).title("Heap Gauge"))
                 .gauge_style(
@@ -82,7 +156,7 @@ impl OverviewView {
     }
 
     fn render_gc_section(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let latest_gc = store.gc_history.iter().last();
+        let latest_gc = store.gc_history.latest();
 
         let gc_text = if let Some(gc) = latest_gc {
             format!(
@@ -90,28 +164,35 @@ impl OverviewView {
                  Full GC: {} collections ({:.2}s total)\n\
                  Total GC Time: {:.2}s\n\
                  Avg Young GC: {:.2}ms\n\
-                 Avg Full GC: {:.2}ms",
-                gc.young_gc_count,
-                gc.young_gc_time_ms as f64 / 1000.0,
-                gc.old_gc_count,
-                gc.old_gc_time_ms as f64 / 1000.0,
-                (gc.young_gc_time_ms + gc.old_gc_time_ms) as f64 / 1000.0,
-                if gc.young_gc_count > 0 {
-                    gc.young_gc_time_ms as f64 / gc.young_gc_count as f64
-                } else {
-                    0.0
-                },
-                if gc.old_gc_count > 0 {
-                    gc.old_gc_time_ms as f64 / gc.old_gc_count as f64
-                } else {
-                    0.0
-                },
+                 Avg Full GC: {:.2}ms\n\
+                 Collection Rate: {}\n\
+                 Allocation Rate: {}",
+                gc.young_gc_count(),
+                gc.young_gc_time_ms() as f64 / 1000.0,
+                gc.old_gc_count(),
+                gc.old_gc_time_ms() as f64 / 1000.0,
+                (gc.young_gc_time_ms() + gc.old_gc_time_ms()) as f64 / 1000.0,
+                gc.avg_young_gc_ms(),
+                gc.avg_old_gc_ms(),
+                store
+                    .gc_rate_per_min()
+                    .map(|rate| format!("{:.1}/min", rate))
+                    .unwrap_or_else(|| "n/a".to_string()),
+                GcView::allocation_rate_text(store),
             )
         } else {
             "No GC data available".to_string()
         };
 
-        let gc_widget = Paragraph::new(gc_text)
+        let mut lines: Vec<Line> = gc_text
+            .lines()
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+        if latest_gc.is_some() {
+            lines.push(GcView::throughput_line(store, theme));
+        }
+
+        let gc_widget = Paragraph::new(lines)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -123,7 +204,7 @@ impl OverviewView {
     }
 
     fn render_summary_section(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        let latest_heap = store.heap_history.iter().last();
+        let latest_heap = store.heap_history.latest();
 
         let summary_text = if let Some(heap) = latest_heap {
             let metaspace = heap
@@ -160,4 +241,193 @@ impl OverviewView {
 
         frame.render_widget(summary, area);
     }
+
+    fn render_runtime_section(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Runtime Counters");
+        let Some(counters) = &store.runtime_counters else {
+            let placeholder = Paragraph::new("No performance counters available")
+                .block(block)
+                .style(Style::default().fg(theme.text_dim()));
+            frame.render_widget(placeholder, area);
+            return;
+        };
+
+        let text = format!(
+            "Safepoints: {} ({:.1}ms, {:.1}ms sync)\n\
+             JIT Time: {:.2}s ({} compiles)\n\
+             Classes: {} loaded, {} unloaded\n\
+             GC: {} collections ({:.2}s)",
+            counters.safepoints,
+            counters.safepoint_time_ms,
+            counters.safepoint_sync_time_ms,
+            counters.jit_time_ms / 1000.0,
+            counters.jit_compiles,
+            counters.classes_loaded,
+            counters.classes_unloaded,
+            counters.gc_invocations,
+            counters.gc_time_ms / 1000.0,
+        );
+
+        let widget = Paragraph::new(text)
+            .block(block)
+            .style(Style::default().fg(theme.text()));
+        frame.render_widget(widget, area);
+    }
+
+    fn render_compiler_section(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        let Some(stats) = &store.compiler_stats else {
+            let placeholder = Paragraph::new("No compiler data available")
+                .block(Block::default().borders(Borders::ALL).title("JIT Compiler"))
+                .style(Style::default().fg(theme.text_dim()));
+            frame.render_widget(placeholder, area);
+            return;
+        };
+
+        let methods = Line::from(format!(
+            "Compiled: {} methods ({} failed, {:.2}s)",
+            stats.compiled_methods, stats.failed_compilations, stats.compile_time_seconds
+        ));
+
+        // A full code cache silently turns the JIT off, so flag it early
+        let code_cache = if stats.code_cache_max_bytes > 0 {
+            let ratio = stats.code_cache_used_bytes as f64 / stats.code_cache_max_bytes as f64;
+            let color = if !stats.compilation_enabled || ratio > 0.9 {
+                theme.memory_critical()
+            } else if ratio > 0.7 {
+                theme.memory_high()
+            } else {
+                theme.text()
+            };
+            let mut text = format!(
+                "Code Cache: {} / {} MB ({:.1}%)",
+                stats.code_cache_used_bytes / 1024 / 1024,
+                stats.code_cache_max_bytes / 1024 / 1024,
+                ratio * 100.0
+            );
+            if !stats.compilation_enabled {
+                text.push_str(" - JIT disabled");
+            }
+            Line::from(Span::styled(text, Style::default().fg(color)))
+        } else {
+            Line::from(Span::styled(
+                "Code Cache: N/A",
+                Style::default().fg(theme.text_dim()),
+            ))
+        };
+
+        let widget = Paragraph::new(vec![methods, code_cache])
+            .block(Block::default().borders(Borders::ALL).title("JIT Compiler"))
+            .style(Style::default().fg(theme.text()));
+        frame.render_widget(widget, area);
+    }
+
+    /// The heap alone nearly filling the container leaves no room for
+    /// metaspace, threads and other native memory, and the kernel kills the
+    /// process without a Java OutOfMemoryError.
+    fn container_limit_line(limit: u64, heap_max: Option<u64>, theme: &Theme) -> Line<'static> {
+        let heap_text = heap_max
+            .map(|max| format!("{} MB", max / 1024 / 1024))
+            .unwrap_or_else(|| "N/A".to_string());
+        let text = format!(
+            "Container Limit: {} MB / Heap Max: {}",
+            limit / 1024 / 1024,
+            heap_text
+        );
+
+        let ratio = heap_max.map_or(0.0, |max| max as f64 / limit as f64);
+        let color = if ratio > 0.9 {
+            theme.memory_critical()
+        } else if ratio > 0.75 {
+            theme.memory_high()
+        } else {
+            theme.text()
+        };
+        Line::from(Span::styled(text, Style::default().fg(color)))
+    }
+
+    fn render_vm_section(
+        frame: &mut Frame,
+        area: Rect,
+        store: &MetricsStore,
+        jvm_info: Option<&JvmInfo>,
+        scroll: usize,
+        theme: &Theme,
+    ) {
+        let info = match jvm_info {
+            Some(info) => info,
+            None => {
+                let placeholder = Paragraph::new("No JVM info available")
+                    .block(Block::default().borders(Borders::ALL).title("VM"))
+                    .style(Style::default().fg(theme.text_dim()));
+                frame.render_widget(placeholder, area);
+                return;
+            }
+        };
+
+        let mut vm_lines = vec![
+            Line::from(format!("JDK {}", info.version)),
+            Line::from(format!(
+                "Uptime: {}",
+                Self::format_uptime(info.uptime_seconds)
+            )),
+        ];
+        if let Some(limit) = info.container_memory_limit {
+            let heap_max = store.heap_history.latest().map(|heap| heap.max_bytes);
+            vm_lines.push(Self::container_limit_line(limit, heap_max, theme));
+        }
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(vm_lines.len() as u16 + 2),
+                Constraint::Min(0),
+            ])
+            .split(area);
+
+        let vm_widget = Paragraph::new(vm_lines)
+            .block(Block::default().borders(Borders::ALL).title("VM"))
+            .style(Style::default().fg(theme.text()));
+        frame.render_widget(vm_widget, chunks[0]);
+
+        let scroll = scroll.min(info.vm_flags.len().saturating_sub(1));
+        let items: Vec<ListItem> = info
+            .vm_flags
+            .iter()
+            .skip(scroll)
+            .map(|flag| {
+                let style = if flag.starts_with("-Xm") || flag.contains("HeapSize") {
+                    Style::default().fg(theme.highlight())
+                } else {
+                    Style::default().fg(theme.text())
+                };
+                ListItem::new(flag.as_str()).style(style)
+            })
+            .collect();
+
+        let title = if info.vm_flags.is_empty() {
+            "VM Flags".to_string()
+        } else {
+            format!("VM Flags ({}/{})", scroll + 1, info.vm_flags.len())
+        };
+
+        let flags = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(flags, chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_uptime() {
+        assert_eq!(OverviewView::format_uptime(42), "0m 42s");
+        assert_eq!(OverviewView::format_uptime(3 * 3600 + 5 * 60), "3h 5m");
+        assert_eq!(
+            OverviewView::format_uptime(2 * 86_400 + 4 * 3600 + 30 * 60),
+            "2d 4h 30m"
+        );
+    }
 }
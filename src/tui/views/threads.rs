@@ -1,45 +1,107 @@
-use crate::jvm::types::ThreadState;
+use crate::app::ThreadSort;
+use crate::config::ThreadColumn;
+use crate::jvm::types::{ThreadInfo, ThreadState};
 use crate::metrics::store::MetricsStore;
 use crate::theme::Theme;
+use crate::tui::views::{
+    clamp_scroll, collecting_placeholder, render_scrollbar, sample_bounds, table_rows,
+    window_label, MIN_CHART_SAMPLES,
+};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     prelude::*,
-    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Paragraph, Row, Table},
 };
 use std::collections::HashMap;
 
+/// CPU usage (percent of one core) at which a thread is flagged as hot.
+const HOT_THREAD_CPU_PERCENT: f64 = 50.0;
+const WARM_THREAD_CPU_PERCENT: f64 = 10.0;
+
 pub struct ThreadsView;
 
 impl ThreadsView {
     pub fn render(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
-        Self::render_with_scroll(frame, area, store, 0, theme);
+        Self::render_with_scroll(
+            frame,
+            area,
+            store,
+            0,
+            ThreadSort::Dump,
+            None,
+            &ThreadColumn::all(),
+            theme,
+        );
     }
 
+    /// `limit` caps the thread list to its first threads in `sort` order.
+    #[allow(clippy::too_many_arguments)]
     pub fn render_with_scroll(
         frame: &mut Frame,
         area: Rect,
         store: &MetricsStore,
         scroll: usize,
+        sort: ThreadSort,
+        limit: Option<usize>,
+        columns: &[ThreadColumn],
         theme: &Theme,
     ) {
+        let summary_height = if store.deadlocked_threads.is_empty() {
+            10
+        } else {
+            11
+        };
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(9), Constraint::Min(0)])
+            .constraints([
+                Constraint::Length(summary_height.max(12)),
+                Constraint::Min(0),
+            ])
             .split(area);
 
-        Self::render_summary_section(frame, chunks[0], store, theme);
-        Self::render_thread_list(frame, chunks[1], store, scroll, theme);
+        let top_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Length(38), Constraint::Min(0)])
+            .split(chunks[0]);
+
+        Self::render_summary_section(frame, top_chunks[0], store, theme);
+        Self::render_state_history(frame, top_chunks[1], store, theme);
+        Self::render_thread_list(frame, chunks[1], store, scroll, sort, limit, columns, theme);
+    }
+
+    /// Threads in display order. Sorting by CPU puts the busiest threads first
+    /// and threads without a usage figure last; the sort is stable.
+    pub fn ordered_threads(store: &MetricsStore, sort: ThreadSort) -> Vec<&ThreadInfo> {
+        let mut threads: Vec<&ThreadInfo> = store.thread_snapshot.iter().collect();
+        if sort == ThreadSort::Cpu {
+            let usage = |thread: &ThreadInfo| {
+                store
+                    .thread_cpu_usage
+                    .get(&thread.id)
+                    .copied()
+                    .unwrap_or(-1.0)
+            };
+            threads.sort_by(|a, b| usage(b).total_cmp(&usage(a)));
+        }
+        threads
     }
 
-    pub fn search_threads(store: &MetricsStore, query: &str) -> Vec<usize> {
+    /// Positions of the matching threads among the first `limit` listed.
+    pub fn search_threads(
+        store: &MetricsStore,
+        sort: ThreadSort,
+        limit: Option<usize>,
+        query: &str,
+    ) -> Vec<usize> {
         if query.is_empty() {
             return Vec::new();
         }
 
         let query_lower = query.to_lowercase();
-        store
-            .thread_snapshot
-            .iter()
+        Self::ordered_threads(store, sort)
+            .into_iter()
+            .take(limit.unwrap_or(usize::MAX))
             .enumerate()
             .filter_map(|(idx, thread)| {
                 if thread.name.to_lowercase().contains(&query_lower)
@@ -53,6 +115,17 @@ impl ThreadsView {
             .collect()
     }
 
+    /// The thread with the most stack frames, the first in the dump on a tie;
+    /// runaway recursion shows up here. `None` when no stacks were captured.
+    pub fn deepest_thread(store: &MetricsStore) -> Option<&ThreadInfo> {
+        store
+            .thread_snapshot
+            .iter()
+            .rev()
+            .filter(|thread| !thread.stack_trace.is_empty())
+            .max_by_key(|thread| thread.stack_trace.len())
+    }
+
     fn render_summary_section(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
         let threads = &store.thread_snapshot;
 
@@ -76,7 +149,49 @@ impl ThreadsView {
             state_counts.get(&ThreadState::Terminated).unwrap_or(&0),
         );
 
-        let summary = Paragraph::new(summary_text)
+        let mut lines: Vec<Line> = Vec::new();
+        if !store.deadlocked_threads.is_empty() {
+            lines.push(Line::styled(
+                format!(
+                    "DEADLOCK DETECTED: {} threads deadlocked",
+                    store.deadlocked_threads.len()
+                ),
+                Style::default()
+                    .fg(theme.error())
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.extend(
+            summary_text
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+
+        if let Some(deepest) = Self::deepest_thread(store) {
+            lines.push(Line::from(format!(
+                "Deepest:       {} in {}",
+                deepest.stack_trace.len(),
+                deepest.name
+            )));
+        }
+
+        if let Some(trend) = store.thread_count_trend() {
+            let color = if trend > 0 {
+                theme.warning()
+            } else {
+                theme.text_dim()
+            };
+            lines.push(Line::styled(
+                format!(
+                    "Trend:         {:+} over {} samples",
+                    trend,
+                    store.thread_state_history.len()
+                ),
+                Style::default().fg(color),
+            ));
+        }
+
+        let summary = Paragraph::new(lines)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
@@ -87,27 +202,188 @@ impl ThreadsView {
         frame.render_widget(summary, area);
     }
 
+    /// Thread states over time, drawn as stacked lines: each line is the running
+    /// total of its state and every state below it, so the top line is the
+    /// total thread count.
+    fn render_state_history(frame: &mut Frame, area: Rect, store: &MetricsStore, theme: &Theme) {
+        let history: Vec<_> = store.thread_state_history.iter().collect();
+
+        if history.len() < MIN_CHART_SAMPLES {
+            frame.render_widget(
+                collecting_placeholder("Thread States Over Time", history.len(), theme),
+                area,
+            );
+            return;
+        }
+
+        let stacked = |depth: usize| -> Vec<(f64, f64)> {
+            history
+                .iter()
+                .enumerate()
+                .map(|(i, counts)| {
+                    let layers = [
+                        counts.runnable,
+                        counts.blocked,
+                        counts.waiting,
+                        counts.timed_waiting,
+                        counts.other,
+                    ];
+                    let value: usize = layers[..=depth].iter().sum();
+                    (i as f64, value as f64)
+                })
+                .collect()
+        };
+
+        let runnable = stacked(0);
+        let blocked = stacked(1);
+        let waiting = stacked(2);
+        let timed_waiting = stacked(3);
+        let total = stacked(4);
+
+        let max_total = history
+            .iter()
+            .map(|counts| counts.total())
+            .max()
+            .unwrap_or(0)
+            .max(10) as f64;
+
+        let datasets = vec![
+            Dataset::default()
+                .name("Total")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.thread_state_terminated()))
+                .data(&total),
+            Dataset::default()
+                .name("+Timed Waiting")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.thread_state_timed_waiting()))
+                .data(&timed_waiting),
+            Dataset::default()
+                .name("+Waiting")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.thread_state_waiting()))
+                .data(&waiting),
+            Dataset::default()
+                .name("+Blocked")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.thread_state_blocked()))
+                .data(&blocked),
+            Dataset::default()
+                .name("Runnable")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme.thread_state_runnable()))
+                .data(&runnable),
+        ];
+
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Thread States Over Time (stacked)"),
+            )
+            .x_axis(
+                Axis::default()
+                    .title("Samples")
+                    .style(Style::default().fg(theme.text_dim()))
+                    .bounds(sample_bounds(history.len())),
+            )
+            .y_axis(
+                Axis::default()
+                    .title("Threads")
+                    .style(Style::default().fg(theme.text_dim()))
+                    .bounds([0.0, max_total])
+                    .labels(vec![
+                        Span::raw("0"),
+                        Span::raw(format!("{}", max_total as usize)),
+                    ]),
+            );
+
+        frame.render_widget(chart, area);
+    }
+
+    /// Which thread holds each lock address in the latest dump.
+    pub fn lock_owners(store: &MetricsStore) -> HashMap<&str, &str> {
+        store
+            .thread_snapshot
+            .iter()
+            .flat_map(|thread| {
+                thread
+                    .held_locks()
+                    .filter_map(|lock| Some((lock.address.as_deref()?, thread.name.as_str())))
+            })
+            .collect()
+    }
+
+    /// What the thread waits for and who holds it, else what it holds.
+    pub fn lock_summary(thread: &ThreadInfo, owners: &HashMap<&str, &str>) -> String {
+        if let Some(lock) = thread.awaited_lock() {
+            let owner = lock
+                .address
+                .as_deref()
+                .and_then(|address| owners.get(address))
+                .filter(|owner| **owner != thread.name);
+            return match owner {
+                Some(owner) => format!(
+                    "{} {} held by {}",
+                    lock.kind.short_description(),
+                    lock.short_class_name(),
+                    owner
+                ),
+                None => format!(
+                    "{} {}",
+                    lock.kind.short_description(),
+                    lock.short_class_name()
+                ),
+            };
+        }
+
+        let held: Vec<_> = thread.held_locks().collect();
+        match held.as_slice() {
+            [] => String::new(),
+            [lock] => format!("holds {}", lock.short_class_name()),
+            [lock, rest @ ..] => format!("holds {} +{}", lock.short_class_name(), rest.len()),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn render_thread_list(
         frame: &mut Frame,
         area: Rect,
         store: &MetricsStore,
         scroll: usize,
+        sort: ThreadSort,
+        limit: Option<usize>,
+        columns: &[ThreadColumn],
         theme: &Theme,
     ) {
-        let threads = &store.thread_snapshot;
+        let mut threads = Self::ordered_threads(store, sort);
+        let total = threads.len();
+        if let Some(limit) = limit {
+            threads.truncate(limit);
+        }
+        let owners = Self::lock_owners(store);
+        let scroll = clamp_scroll(scroll, threads.len());
+        let shown = table_rows(area).min(threads.len().saturating_sub(scroll));
 
-        let header = Row::new(vec![
-            Cell::from("ID").style(Style::default().fg(theme.highlight())),
-            Cell::from("Name").style(Style::default().fg(theme.highlight())),
-            Cell::from("State").style(Style::default().fg(theme.highlight())),
-            Cell::from("Stack Depth").style(Style::default().fg(theme.highlight())),
-        ])
+        let header = Row::new(
+            columns
+                .iter()
+                .map(|column| {
+                    Cell::from(column.title()).style(Style::default().fg(theme.highlight()))
+                })
+                .collect::<Vec<_>>(),
+        )
         .height(1);
 
         let rows: Vec<Row> = threads
             .iter()
             .skip(scroll)
-            .take(50)
+            .take(shown)
             .map(|thread| {
                 let state_color = match thread.state {
                     ThreadState::Runnable => theme.thread_state_runnable(),
@@ -127,32 +403,245 @@ impl ThreadsView {
                     ThreadState::New => "NEW",
                 };
 
-                Row::new(vec![
-                    Cell::from(thread.id.to_string()),
-                    Cell::from(thread.name.clone()),
-                    Cell::from(state_str).style(Style::default().fg(state_color)),
-                    Cell::from(thread.stack_trace.len().to_string()),
-                ])
+                let deadlocked = store.deadlocked_threads.contains(&thread.id);
+                let cpu = store.thread_cpu_usage.get(&thread.id).copied();
+
+                let cells: Vec<Cell> = columns
+                    .iter()
+                    .map(|column| match column {
+                        ThreadColumn::Id => Cell::from(thread.id.to_string()),
+                        ThreadColumn::Name if deadlocked => {
+                            Cell::from(format!("[DEADLOCK] {}", thread.name))
+                        }
+                        ThreadColumn::Name => Cell::from(thread.name.clone()),
+                        ThreadColumn::State if deadlocked => Cell::from(state_str),
+                        ThreadColumn::State => {
+                            Cell::from(state_str).style(Style::default().fg(state_color))
+                        }
+                        ThreadColumn::Daemon => Cell::from(match thread.daemon {
+                            Some(true) => "yes",
+                            Some(false) => "no",
+                            None => "-",
+                        }),
+                        ThreadColumn::StackDepth => {
+                            Cell::from(thread.stack_trace.len().to_string())
+                        }
+                        ThreadColumn::Cpu => match cpu {
+                            Some(percent) if !deadlocked => Cell::from(format!("{:.1}", percent))
+                                .style(Self::cpu_style(percent, theme)),
+                            Some(percent) => Cell::from(format!("{:.1}", percent)),
+                            None => Cell::from("-"),
+                        },
+                        ThreadColumn::Lock => Cell::from(Self::lock_summary(thread, &owners)),
+                    })
+                    .collect();
+
+                if deadlocked {
+                    Row::new(cells).style(
+                        Style::default()
+                            .fg(theme.error())
+                            .add_modifier(Modifier::BOLD),
+                    )
+                } else {
+                    Row::new(cells)
+                }
             })
             .collect();
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(6),
-                Constraint::Percentage(50),
-                Constraint::Length(15),
-                Constraint::Length(12),
-            ],
-        )
-        .header(header)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Thread List (Top 50)"),
-        )
-        .style(Style::default().fg(theme.text()));
+        let widths: Vec<Constraint> = columns
+            .iter()
+            .map(|column| match column {
+                ThreadColumn::Id => Constraint::Length(6),
+                ThreadColumn::Name => Constraint::Fill(1),
+                ThreadColumn::State => Constraint::Length(15),
+                ThreadColumn::Daemon => Constraint::Length(7),
+                ThreadColumn::StackDepth => Constraint::Length(12),
+                ThreadColumn::Cpu => Constraint::Length(8),
+                ThreadColumn::Lock => Constraint::Fill(1),
+            })
+            .collect();
+
+        let mut window = window_label(scroll, shown, threads.len());
+        if threads.len() < total {
+            window = format!("{}, showing {} of {}", window, threads.len(), total);
+        }
+        let title = match sort {
+            ThreadSort::Dump => format!("Thread List ({})", window),
+            ThreadSort::Cpu => format!("Thread List by {} ({})", sort.display_name(), window),
+        };
+
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .style(Style::default().fg(theme.text()));
 
         frame.render_widget(table, area);
+        render_scrollbar(frame, area, scroll, threads.len(), theme);
+    }
+
+    fn cpu_style(percent: f64, theme: &Theme) -> Style {
+        if percent >= HOT_THREAD_CPU_PERCENT {
+            Style::default()
+                .fg(theme.error())
+                .add_modifier(Modifier::BOLD)
+        } else if percent >= WARM_THREAD_CPU_PERCENT {
+            Style::default().fg(theme.warning())
+        } else {
+            Style::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jvm::types::{LockInfo, LockKind, StackFrame};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn thread(id: u64) -> ThreadInfo {
+        ThreadInfo {
+            id,
+            name: format!("worker-{}", id),
+            state: ThreadState::Waiting,
+            stack_trace: Vec::new(),
+            cpu_time_ns: None,
+            daemon: None,
+            locks: Vec::new(),
+        }
+    }
+
+    fn with_depth(id: u64, depth: usize) -> ThreadInfo {
+        ThreadInfo {
+            stack_trace: (0..depth)
+                .map(|line| StackFrame {
+                    class_name: "com.example.Parser".to_string(),
+                    method_name: "parse".to_string(),
+                    file_name: None,
+                    line_number: Some(line as u32),
+                })
+                .collect(),
+            ..thread(id)
+        }
+    }
+
+    #[test]
+    fn test_deepest_thread() {
+        let mut store = MetricsStore::new(10);
+        store.record_threads(vec![thread(1)]);
+        assert!(ThreadsView::deepest_thread(&store).is_none());
+
+        store.record_threads(vec![
+            with_depth(1, 3),
+            with_depth(2, 250),
+            with_depth(3, 250),
+            thread(4),
+        ]);
+        assert_eq!(ThreadsView::deepest_thread(&store).unwrap().id, 2);
+    }
+
+    #[test]
+    fn test_lock_summary_names_the_owner() {
+        let lock = |kind, address: &str, class_name: &str| LockInfo {
+            kind,
+            address: Some(address.to_string()),
+            class_name: Some(class_name.to_string()),
+        };
+        let writer = ThreadInfo {
+            locks: vec![
+                lock(LockKind::Locked, "0xa", "java.util.HashMap"),
+                lock(LockKind::Locked, "0xb", "java.lang.Object"),
+            ],
+            ..thread(1)
+        };
+        let reader = ThreadInfo {
+            locks: vec![lock(LockKind::WaitingToLock, "0xa", "java.util.HashMap")],
+            ..thread(2)
+        };
+        let parked = ThreadInfo {
+            locks: vec![lock(
+                LockKind::ParkingToWaitFor,
+                "0xc",
+                "java.util.concurrent.locks.ReentrantLock$NonfairSync",
+            )],
+            ..thread(3)
+        };
+
+        let mut store = MetricsStore::new(10);
+        store.record_threads(vec![writer, reader, parked, thread(4)]);
+        let owners = ThreadsView::lock_owners(&store);
+        let summaries: Vec<String> = store
+            .thread_snapshot
+            .iter()
+            .map(|thread| ThreadsView::lock_summary(thread, &owners))
+            .collect();
+
+        assert_eq!(
+            summaries,
+            vec![
+                "holds HashMap +1",
+                "blocked on HashMap held by worker-1",
+                "parked on ReentrantLock$NonfairSync",
+                "",
+            ]
+        );
+    }
+
+    /// The list's title row and first thread row.
+    fn thread_list_top(store: &MetricsStore, scroll: usize, limit: Option<usize>) -> [String; 2] {
+        let mut terminal = Terminal::new(TestBackend::new(80, 30)).unwrap();
+        terminal
+            .draw(|frame| {
+                ThreadsView::render_with_scroll(
+                    frame,
+                    frame.area(),
+                    store,
+                    scroll,
+                    ThreadSort::Dump,
+                    limit,
+                    &ThreadColumn::all(),
+                    &Theme,
+                )
+            })
+            .unwrap();
+
+        // The list starts below the 12-row summary, its border and header
+        let buffer = terminal.backend().buffer();
+        [12, 14].map(|y| (0..80).map(|x| buffer[(x, y)].symbol()).collect())
+    }
+
+    fn first_listed_thread(store: &MetricsStore, scroll: usize) -> String {
+        let [_, first] = thread_list_top(store, scroll, None);
+        first
+    }
+
+    #[test]
+    fn test_thread_list_starts_at_scroll_offset() {
+        let mut store = MetricsStore::new(10);
+        store.record_threads((0..120).map(thread).collect());
+
+        assert!(first_listed_thread(&store, 0).contains("worker-0 "));
+        assert!(first_listed_thread(&store, 75).contains("worker-75 "));
+        // Past the end the last thread stays in view
+        assert!(first_listed_thread(&store, 500).contains("worker-119 "));
+    }
+
+    #[test]
+    fn test_thread_list_honors_limit() {
+        let mut store = MetricsStore::new(10);
+        store.record_threads((0..120).map(thread).collect());
+
+        let [title, first] = thread_list_top(&store, 10, Some(50));
+        assert!(title.contains("showing 50 of 120"), "{}", title);
+        assert!(first.contains("worker-10 "));
+        // Scrolling stops at the last thread under the cap
+        let [_, first] = thread_list_top(&store, 100, Some(50));
+        assert!(first.contains("worker-49 "));
+
+        let [title, _] = thread_list_top(&store, 0, Some(500));
+        assert!(!title.contains("showing"), "{}", title);
+        assert_eq!(
+            ThreadsView::search_threads(&store, ThreadSort::Dump, Some(50), "worker-4"),
+            vec![4, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49]
+        );
     }
 }
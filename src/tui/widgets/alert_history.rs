@@ -0,0 +1,128 @@
+use crate::app::AlertEvent;
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::*,
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+};
+
+pub struct AlertHistory;
+
+impl AlertHistory {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        events: &[AlertEvent],
+        counts: &[(String, usize)],
+        scroll: usize,
+        theme: &Theme,
+    ) {
+        let popup_area = Self::centered_rect(70, 60, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let outer_block = Block::default()
+            .title(format!(" Alert History ({} fired) ", events.len()))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.warning()))
+            .style(Style::default().bg(theme.background()));
+
+        frame.render_widget(outer_block, popup_area);
+
+        let inner_area = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+
+        let summary_height = counts.len().min(4) as u16 + 1;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(summary_height),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(inner_area);
+
+        let summary: Vec<Line> = if counts.is_empty() {
+            vec![Line::from("No alerts have fired this session.")]
+        } else {
+            counts
+                .iter()
+                .take(4)
+                .map(|(rule, count)| {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("{:>4}× ", count),
+                            Style::default()
+                                .fg(theme.warning())
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                        Span::raw(rule.clone()),
+                    ])
+                })
+                .collect()
+        };
+
+        frame.render_widget(
+            Paragraph::new(summary).style(Style::default().fg(theme.text())),
+            chunks[0],
+        );
+
+        let items: Vec<ListItem> = events
+            .iter()
+            .rev()
+            .skip(scroll)
+            .map(|event| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        event.timestamp.format("%H:%M:%S  ").to_string(),
+                        Style::default().fg(theme.text_dim()),
+                    ),
+                    Span::raw(format!("{}  ", event.rule)),
+                    Span::styled(
+                        format!("(was {:.1})", event.value),
+                        Style::default().fg(theme.error()),
+                    ),
+                ]))
+            })
+            .collect();
+
+        frame.render_widget(
+            List::new(items).style(Style::default().fg(theme.text())),
+            chunks[1],
+        );
+
+        let prompt = Paragraph::new("↑/k ↓/j: Scroll | Esc/a: Close")
+            .style(Style::default().fg(theme.text_dim()))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border())),
+            );
+
+        frame.render_widget(prompt, chunks[2]);
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(r);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
+    }
+}
@@ -0,0 +1,93 @@
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Scrollable pager for the raw output of a diagnostic command.
+pub struct CommandOutput;
+
+impl CommandOutput {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        output: &str,
+        scroll: usize,
+        theme: &Theme,
+    ) {
+        let popup_area = Self::centered_rect(90, 85, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let line_count = output.lines().count();
+        let outer_block = Block::default()
+            .title(format!(
+                " {} (line {}/{}) ",
+                title,
+                (scroll + 1).min(line_count),
+                line_count
+            ))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused()))
+            .style(Style::default().bg(theme.background()));
+
+        frame.render_widget(outer_block, popup_area);
+
+        let inner_area = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(inner_area);
+
+        let lines: Vec<Line> = output
+            .lines()
+            .skip(scroll)
+            .take(chunks[0].height as usize)
+            .map(|line| Line::from(line.to_string()))
+            .collect();
+
+        frame.render_widget(
+            Paragraph::new(lines).style(Style::default().fg(theme.text())),
+            chunks[0],
+        );
+
+        let prompt =
+            Paragraph::new("↑/k ↓/j: Scroll | PgUp/PgDn: Page | g/G: Top/Bottom | Esc/q: Close")
+                .style(Style::default().fg(theme.text_dim()))
+                .alignment(Alignment::Center)
+                .block(
+                    Block::default()
+                        .borders(Borders::TOP)
+                        .border_style(Style::default().fg(theme.border())),
+                );
+
+        frame.render_widget(prompt, chunks[1]);
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(r);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
+    }
+}
@@ -0,0 +1,37 @@
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+pub struct CommandPrompt;
+
+impl CommandPrompt {
+    /// Input line at the bottom of the screen, e.g. `!` for shell commands.
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        prefix: &str,
+        command: &str,
+        title: &str,
+        theme: &Theme,
+    ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(90), Constraint::Min(3)])
+            .split(area);
+
+        let prompt = Paragraph::new(format!("{} {}", prefix, command))
+            .style(Style::default().fg(theme.highlight()))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .border_style(Style::default().fg(theme.border_focused())),
+            )
+            .alignment(Alignment::Left);
+
+        frame.render_widget(prompt, chunks[1]);
+    }
+}
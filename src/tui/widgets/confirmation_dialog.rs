@@ -9,6 +9,24 @@ pub struct ConfirmationDialog;
 
 impl ConfirmationDialog {
     pub fn render(frame: &mut Frame, area: Rect, title: &str, message: &str, theme: &Theme) {
+        Self::render_with_prompt(
+            frame,
+            area,
+            title,
+            message,
+            "Press [Y] to confirm, [N] to cancel",
+            theme,
+        );
+    }
+
+    pub fn render_with_prompt(
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        message: &str,
+        prompt: &str,
+        theme: &Theme,
+    ) {
         let popup_area = Self::centered_rect(50, 25, area);
 
         frame.render_widget(Clear, popup_area);
@@ -39,7 +57,7 @@ impl ConfirmationDialog {
 
         frame.render_widget(message_widget, chunks[0]);
 
-        let prompt = Paragraph::new("Press [Y] to confirm, [N] to cancel")
+        let prompt = Paragraph::new(prompt)
             .style(Style::default().fg(theme.text_dim()))
             .alignment(Alignment::Center)
             .block(
@@ -0,0 +1,47 @@
+use crate::metrics::log::{LogEntry, LogLevel};
+use crate::metrics::ring_buffer::RingBuffer;
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// Bottom pane listing the most recent event log entries, newest last.
+pub struct EventLogPane;
+
+impl EventLogPane {
+    pub fn render(frame: &mut Frame, area: Rect, log: &RingBuffer<LogEntry>, theme: &Theme) {
+        let block = Block::default()
+            .title(format!(" Event Log ({}) ", log.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border()));
+
+        let visible = area.height.saturating_sub(2) as usize;
+        let lines: Vec<Line> = if log.is_empty() {
+            vec![Line::styled(
+                "Nothing logged yet.",
+                Style::default().fg(theme.text_dim()),
+            )]
+        } else {
+            log.iter()
+                .skip(log.len().saturating_sub(visible))
+                .map(|entry| {
+                    let color = match entry.level {
+                        LogLevel::Info => theme.text(),
+                        LogLevel::Error => theme.error(),
+                    };
+                    Line::from(vec![
+                        Span::styled(
+                            entry.timestamp.format("%H:%M:%S  ").to_string(),
+                            Style::default().fg(theme.text_dim()),
+                        ),
+                        Span::styled(entry.message.clone(), Style::default().fg(color)),
+                    ])
+                })
+                .collect()
+        };
+
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::app::ExportFormat;
+use crate::config::ExportFormat;
 use crate::theme::Theme;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
@@ -37,6 +37,7 @@ impl FormatSelectorDialog {
             ExportFormat::Json,
             ExportFormat::Prometheus,
             ExportFormat::Csv,
+            ExportFormat::Markdown,
         ];
 
         let items: Vec<ListItem> = formats
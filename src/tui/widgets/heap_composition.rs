@@ -0,0 +1,163 @@
+use crate::jvm::types::{HeapInfo, PoolType};
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+/// One stacked bar of each pool type's share of committed memory, so it's
+/// clear at a glance which pools the memory actually sits in.
+pub struct HeapCompositionBar;
+
+impl HeapCompositionBar {
+    pub fn render(frame: &mut Frame, area: Rect, heap: &HeapInfo, theme: &Theme) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Committed Memory by Pool");
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let shares = Self::shares(heap);
+        let total: u64 = shares.iter().map(|(_, bytes)| bytes).sum();
+        if total == 0 {
+            frame.render_widget(
+                Paragraph::new("No committed pool sizes reported")
+                    .style(Style::default().fg(theme.text_dim())),
+                inner,
+            );
+            return;
+        }
+
+        let widths = Self::segment_widths(&shares, inner.width as usize);
+        let spans: Vec<Span> = shares
+            .iter()
+            .zip(widths)
+            .filter(|(_, width)| *width > 0)
+            .map(|((pool_type, bytes), width)| {
+                let percent = *bytes as f64 / total as f64 * 100.0;
+                let label = [
+                    format!("{} {:.0}%", pool_type.label(), percent),
+                    format!("{:.0}%", percent),
+                ]
+                .into_iter()
+                .find(|label| label.chars().count() <= width)
+                .unwrap_or_default();
+                Span::styled(
+                    format!("{:^width$}", label, width = width),
+                    Style::default()
+                        .fg(theme.text_on_color())
+                        .bg(theme.pool_color(*pool_type)),
+                )
+            })
+            .collect();
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), inner);
+    }
+
+    /// Committed bytes per pool type, in the Memory tab's pool order.
+    fn shares(heap: &HeapInfo) -> Vec<(PoolType, u64)> {
+        PoolType::all()
+            .iter()
+            .map(|pool_type| {
+                let bytes = heap
+                    .pools
+                    .iter()
+                    .filter(|pool| pool.pool_type == *pool_type)
+                    .map(|pool| pool.committed_bytes)
+                    .sum();
+                (*pool_type, bytes)
+            })
+            .filter(|(_, bytes)| *bytes > 0)
+            .collect()
+    }
+
+    /// Splits `width` columns in proportion to each share, rounding the
+    /// running total so the segments always fill the bar exactly.
+    fn segment_widths(shares: &[(PoolType, u64)], width: usize) -> Vec<usize> {
+        let total: u64 = shares.iter().map(|(_, bytes)| bytes).sum();
+        if total == 0 {
+            return vec![0; shares.len()];
+        }
+
+        let mut running = 0;
+        let mut edge = 0;
+        shares
+            .iter()
+            .map(|(_, bytes)| {
+                running += bytes;
+                let next = (running as f64 / total as f64 * width as f64).round() as usize;
+                let segment = next - edge;
+                edge = next;
+                segment
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jvm::types::MemoryPool;
+    use chrono::Local;
+
+    fn pool(name: &str, pool_type: PoolType, committed_mb: u64) -> MemoryPool {
+        MemoryPool {
+            name: name.to_string(),
+            pool_type,
+            used_bytes: 0,
+            max_bytes: 0,
+            committed_bytes: committed_mb * 1024 * 1024,
+        }
+    }
+
+    #[test]
+    fn test_shares_group_pools_by_type() {
+        let heap = HeapInfo {
+            used_bytes: 0,
+            max_bytes: 0,
+            committed_bytes: 0,
+            pools: vec![
+                pool("Metaspace", PoolType::Metaspace, 40),
+                pool("Compressed Class Space", PoolType::Metaspace, 8),
+                pool("G1 Old Gen", PoolType::Old, 300),
+                pool("G1 Survivor Space", PoolType::Survivor, 0),
+                pool("G1 Eden Space", PoolType::Eden, 100),
+            ],
+            timestamp: Local::now(),
+        };
+
+        let shares = HeapCompositionBar::shares(&heap);
+        let mb = 1024 * 1024;
+        assert_eq!(
+            shares,
+            vec![
+                (PoolType::Eden, 100 * mb),
+                (PoolType::Old, 300 * mb),
+                (PoolType::Metaspace, 48 * mb),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segment_widths_fill_the_bar() {
+        let shares = [
+            (PoolType::Eden, 1),
+            (PoolType::Survivor, 1),
+            (PoolType::Old, 1),
+        ];
+        let widths = HeapCompositionBar::segment_widths(&shares, 10);
+        assert_eq!(widths.iter().sum::<usize>(), 10);
+        assert_eq!(widths, vec![3, 4, 3]);
+
+        let shares = [(PoolType::Eden, 25), (PoolType::Old, 75)];
+        assert_eq!(
+            HeapCompositionBar::segment_widths(&shares, 40),
+            vec![10, 30]
+        );
+        assert_eq!(
+            HeapCompositionBar::segment_widths(&[], 40),
+            Vec::<usize>::new()
+        );
+    }
+}
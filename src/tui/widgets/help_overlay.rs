@@ -1,4 +1,5 @@
 use crate::theme::Theme;
+use crate::tui::keybindings::{self, KeyMap, Section, SEARCH_KEYS};
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     prelude::*,
@@ -8,7 +9,7 @@ use ratatui::{
 pub struct HelpOverlay;
 
 impl HelpOverlay {
-    pub fn render(frame: &mut Frame, area: Rect, theme: &Theme) {
+    pub fn render(frame: &mut Frame, area: Rect, keymap: &KeyMap, theme: &Theme) {
         let popup_area = Self::centered_rect(80, 90, area);
 
         frame.render_widget(Clear, popup_area);
@@ -27,65 +28,26 @@ impl HelpOverlay {
             vertical: 1,
         });
 
-        let sections = Layout::default()
+        let sections: Vec<Vec<(String, String)>> = Section::all()
+            .iter()
+            .map(|section| Self::section_rows(*section, keymap))
+            .collect();
+
+        // One row for each section's title border
+        let mut constraints: Vec<Constraint> = sections
+            .iter()
+            .map(|rows| Constraint::Length(rows.len() as u16 + 1))
+            .collect();
+        constraints.push(Constraint::Min(0));
+
+        let areas = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([
-                Constraint::Length(3),
-                Constraint::Length(8),
-                Constraint::Length(6),
-                Constraint::Length(9),
-                Constraint::Min(0),
-            ])
+            .constraints(constraints)
             .split(inner_area);
 
-        Self::render_section(
-            frame,
-            sections[0],
-            "Global",
-            vec![("q", "Quit application"), ("?", "Toggle this help screen")],
-            theme,
-        );
-
-        Self::render_section(
-            frame,
-            sections[1],
-            "Navigation",
-            vec![
-                ("1-5", "Switch to tab (Overview/Memory/Threads/GC/Classes)"),
-                ("h / ←", "Previous tab"),
-                ("l / →", "Next tab"),
-                ("Tab", "Next tab"),
-                ("Shift+Tab", "Previous tab"),
-            ],
-            theme,
-        );
-
-        Self::render_section(
-            frame,
-            sections[2],
-            "Actions",
-            vec![
-                ("g", "Trigger garbage collection (with confirmation)"),
-                ("r", "Reset metrics store"),
-                ("e", "Export current view data"),
-            ],
-            theme,
-        );
-
-        Self::render_section(
-            frame,
-            sections[3],
-            "View-Specific",
-            vec![
-                ("j / ↓", "Scroll down (Threads/Classes views)"),
-                ("k / ↑", "Scroll up (Threads/Classes views)"),
-                ("/", "Search threads (Threads view)"),
-                ("n", "Next search result (during search)"),
-                ("N", "Previous search result (during search)"),
-                ("Esc", "Cancel search (during search)"),
-            ],
-            theme,
-        );
+        for ((section, rows), area) in Section::all().iter().zip(sections).zip(areas.iter()) {
+            Self::render_section(frame, *area, section.title(), rows, theme);
+        }
 
         let about_text = "JVM-TUI v0.1.0\n\
                          A beautiful, lightweight terminal interface for JVM monitoring.\n\
@@ -104,27 +66,41 @@ impl HelpOverlay {
             .alignment(Alignment::Center)
             .wrap(Wrap { trim: true });
 
-        frame.render_widget(about, sections[4]);
+        frame.render_widget(about, areas[Section::all().len()]);
+    }
+
+    fn section_rows(section: Section, keymap: &KeyMap) -> Vec<(String, String)> {
+        let mut rows: Vec<(String, String)> = keybindings::section_bindings(section)
+            .map(|binding| (keymap.label(binding), binding.help_text()))
+            .collect();
+        if section == Section::View {
+            rows.extend(
+                SEARCH_KEYS
+                    .iter()
+                    .map(|(key, desc)| (key.to_string(), desc.to_string())),
+            );
+        }
+        rows
     }
 
     fn render_section(
         frame: &mut Frame,
         area: Rect,
         title: &str,
-        keybindings: Vec<(&str, &str)>,
+        keybindings: Vec<(String, String)>,
         theme: &Theme,
     ) {
         let rows: Vec<Row> = keybindings
             .iter()
             .map(|(key, desc)| {
                 Row::new(vec![
-                    Cell::from(*key).style(Style::default().fg(theme.highlight()).bold()),
-                    Cell::from(*desc).style(Style::default().fg(theme.text())),
+                    Cell::from(key.as_str()).style(Style::default().fg(theme.highlight()).bold()),
+                    Cell::from(desc.as_str()).style(Style::default().fg(theme.text())),
                 ])
             })
             .collect();
 
-        let table = Table::new(rows, [Constraint::Length(15), Constraint::Percentage(85)])
+        let table = Table::new(rows, [Constraint::Length(18), Constraint::Percentage(85)])
             .block(
                 Block::default()
                     .borders(Borders::TOP)
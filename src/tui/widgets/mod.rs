@@ -1,11 +1,20 @@
+pub mod alert_history;
+pub mod column_menu;
+pub mod command_output;
+pub mod command_prompt;
 pub mod confirmation_dialog;
 pub mod error_screen;
+pub mod event_log;
 pub mod format_selector_dialog;
+pub mod heap_composition;
 pub mod help_footer;
 pub mod help_overlay;
 pub mod loading_screen;
 pub mod memory_gauge;
+pub mod process_info;
 pub mod search_bar;
 pub mod sparkline_panel;
 pub mod stat_card;
+pub mod system_properties;
 pub mod thread_table;
+pub mod toast;
@@ -0,0 +1,111 @@
+use crate::app::ProcessInfo;
+use crate::jvm::types::JvmInfo;
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+/// What the JVM is and where it runs from, to confirm the right process is
+/// attached.
+pub struct ProcessInfoPopup;
+
+impl ProcessInfoPopup {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        jvm_info: Option<&JvmInfo>,
+        info: &ProcessInfo,
+        theme: &Theme,
+    ) {
+        let popup_area = Self::centered_rect(80, 60, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let outer_block = Block::default()
+            .title(" Process Info ")
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused()))
+            .style(Style::default().bg(theme.background()));
+
+        frame.render_widget(outer_block, popup_area);
+
+        let inner_area = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(3)])
+            .split(inner_area);
+
+        let field = |label: &str, value: String| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<19}", label),
+                    Style::default().fg(theme.text_dim()),
+                ),
+                Span::styled(value, Style::default().fg(theme.text())),
+            ])
+        };
+
+        let lines = vec![
+            field(
+                "Main class:",
+                jvm_info.map_or_else(|| "unknown".to_string(), |jvm| jvm.main_class.clone()),
+            ),
+            field(
+                "PID:",
+                jvm_info.map_or_else(|| "unknown".to_string(), |jvm| jvm.pid.to_string()),
+            ),
+            field(
+                "Working directory:",
+                info.working_dir
+                    .clone()
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ),
+            Line::from(""),
+            Line::styled("Command line:", Style::default().fg(theme.text_dim())),
+            Line::styled(
+                info.command_line.clone(),
+                Style::default().fg(theme.highlight()),
+            ),
+        ];
+
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), chunks[0]);
+
+        let prompt = Paragraph::new("Esc/i: Close")
+            .style(Style::default().fg(theme.text_dim()))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border())),
+            );
+
+        frame.render_widget(prompt, chunks[1]);
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(r);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
+    }
+}
@@ -0,0 +1,116 @@
+use crate::theme::Theme;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    prelude::*,
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+};
+
+/// Key/value table of the JVM's system properties, filtered as the user types.
+pub struct SystemPropertiesPanel;
+
+impl SystemPropertiesPanel {
+    pub fn render(
+        frame: &mut Frame,
+        area: Rect,
+        properties: &[&(String, String)],
+        total: usize,
+        filter: &str,
+        scroll: usize,
+        theme: &Theme,
+    ) {
+        let popup_area = Self::centered_rect(85, 80, area);
+
+        frame.render_widget(Clear, popup_area);
+
+        let outer_block = Block::default()
+            .title(format!(
+                " System Properties ({} of {}) ",
+                properties.len(),
+                total
+            ))
+            .title_alignment(Alignment::Center)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.border_focused()))
+            .style(Style::default().bg(theme.background()));
+
+        frame.render_widget(outer_block, popup_area);
+
+        let inner_area = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Min(0),
+                Constraint::Length(3),
+            ])
+            .split(inner_area);
+
+        let filter_line = Line::from(vec![
+            Span::styled("Filter: ", Style::default().fg(theme.text_dim())),
+            Span::styled(filter.to_string(), Style::default().fg(theme.highlight())),
+        ]);
+        frame.render_widget(Paragraph::new(filter_line), chunks[0]);
+
+        let rows: Vec<Row> = properties
+            .iter()
+            .skip(scroll)
+            .map(|(key, value)| {
+                Row::new(vec![
+                    Cell::from(key.clone()).style(Style::default().fg(theme.highlight())),
+                    // Multi-line values such as line.separator stay on one row
+                    Cell::from(value.escape_debug().to_string()),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [Constraint::Percentage(35), Constraint::Percentage(65)],
+        )
+        .header(
+            Row::new(vec!["Key", "Value"]).style(
+                Style::default()
+                    .fg(theme.text())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .style(Style::default().fg(theme.text()));
+
+        frame.render_widget(table, chunks[1]);
+
+        let prompt = Paragraph::new("Type to filter | ↑ ↓: Scroll | PgUp/PgDn: Page | Esc: Close")
+            .style(Style::default().fg(theme.text_dim()))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_style(Style::default().fg(theme.border())),
+            );
+
+        frame.render_widget(prompt, chunks[2]);
+    }
+
+    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+        let popup_layout = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ])
+            .split(r);
+
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ])
+            .split(popup_layout[1])[1]
+    }
+}
@@ -0,0 +1,52 @@
+use crate::app::Toast;
+use crate::metrics::log::LogLevel;
+use crate::theme::Theme;
+use ratatui::{
+    layout::Rect,
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+/// Toasts need at least this much width to be readable.
+const MIN_TOAST_WIDTH: u16 = 20;
+
+/// Transient messages stacked in the bottom-right corner, newest last.
+pub struct ToastStack;
+
+impl ToastStack {
+    pub fn render(frame: &mut Frame, area: Rect, toasts: &[Toast], theme: &Theme) {
+        if toasts.is_empty() || area.width < MIN_TOAST_WIDTH {
+            return;
+        }
+
+        let longest = toasts
+            .iter()
+            .map(|toast| toast.text.chars().count() as u16)
+            .max()
+            .unwrap_or(0);
+        let width = (longest + 4).clamp(MIN_TOAST_WIDTH, (area.width * 2 / 3).max(MIN_TOAST_WIDTH));
+        let fits = (area.height / 3) as usize;
+
+        let mut bottom = area.bottom();
+        for toast in toasts.iter().rev().take(fits) {
+            let rect = Rect::new(area.right() - width, bottom - 3, width, 3);
+            bottom -= 3;
+
+            let color = match toast.level {
+                LogLevel::Info => theme.success(),
+                LogLevel::Error => theme.error(),
+            };
+            let paragraph = Paragraph::new(toast.text.as_str())
+                .style(Style::default().fg(theme.text()))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(color))
+                        .style(Style::default().bg(theme.background())),
+                );
+
+            frame.render_widget(Clear, rect);
+            frame.render_widget(paragraph, rect);
+        }
+    }
+}